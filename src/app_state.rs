@@ -1,10 +1,20 @@
+use std::collections::{HashMap, HashSet};
+
 use dioxus::prelude::*;
-use rand::seq::SliceRandom;
+use rand::Rng;
 use tracing::info;
 use uuid::Uuid;
 
+use crate::constants::LIBRARY_SEARCH_LIMIT;
+use crate::data::anniversaries::featured_anniversaries;
+use crate::data::duplicates::reconcile_merged;
+use crate::data::resurfacing::{record_impression, resurfacing_order};
+use crate::data::slideshow::{cycle_order, SlideshowFilter};
+use crate::data::SearchService;
 use crate::models::*;
+use crate::settings;
 use crate::storage;
+use crate::utils::today;
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct MnemonWithWork {
@@ -18,33 +28,115 @@ pub struct AppState {
     pub mnemons: Signal<Vec<Mnemon>>,
     pub shuffled_indices: Signal<Vec<usize>>,
     pub loaded: Signal<bool>,
+    /// `blob:` URLs of stored assets (uploaded covers and theme music), by asset URI
+    pub asset_urls: Signal<HashMap<String, String>>,
+    /// When each mnemon was last shown in the hero, by mnemon ID
+    pub impressions: Signal<HashMap<Uuid, Impression>>,
+    /// Narrows which mnemons the hero cycles through (saved in settings)
+    pub slideshow_filter: Signal<SlideshowFilter>,
+    /// `shuffled_indices` with the slideshow filter applied, recomputed when either
+    /// (or the library) changes
+    pub cycle_order: Memo<Vec<usize>>,
 }
 
 impl AppState {
     pub fn new() -> Self {
+        let works = Signal::new(Vec::new());
+        let mnemons = Signal::new(Vec::new());
+        let shuffled_indices = Signal::new(Vec::new());
+        let slideshow_filter = Signal::new(settings::slideshow_filter());
+        let cycle_order = Memo::new(move || {
+            cycle_order(
+                &shuffled_indices.read(),
+                &mnemons.read(),
+                &works.read(),
+                &slideshow_filter.read(),
+            )
+        });
         Self {
-            works: Signal::new(Vec::new()),
-            mnemons: Signal::new(Vec::new()),
-            shuffled_indices: Signal::new(Vec::new()),
+            works,
+            mnemons,
+            shuffled_indices,
             loaded: Signal::new(false),
+            asset_urls: Signal::new(HashMap::new()),
+            impressions: Signal::new(HashMap::new()),
+            slideshow_filter,
+            cycle_order,
         }
     }
 
     pub async fn load_from_storage(&mut self) {
-        let persisted = storage::load_all_async().await;
+        let mut persisted = storage::load_all_async().await;
         info!(
             "Loaded {} works and {} mnemons from IndexedDB",
             persisted.works.len(),
             persisted.mnemons.len()
         );
 
-        let mut indices: Vec<usize> = (0..persisted.mnemons.len()).collect();
-        let mut rng = rand::thread_rng();
-        indices.shuffle(&mut rng);
+        // Records of works that were merged away belong to their survivors
+        let (dropped, moved) = reconcile_merged(&mut persisted.works, &mut persisted.mnemons);
+        if !dropped.is_empty() || !moved.is_empty() {
+            info!(
+                "Folded {} merged works and {} mnemons into their survivors",
+                dropped.len(),
+                moved.len()
+            );
+            spawn(async move {
+                for mnemon in &moved {
+                    if let Err(e) = storage::save_mnemon(mnemon).await {
+                        tracing::error!("Failed to persist moved mnemon: {}", e);
+                    }
+                }
+                for id in &dropped {
+                    if let Err(e) = storage::delete_work(id).await {
+                        tracing::error!("Failed to delete merged work: {}", e);
+                    }
+                }
+            });
+        }
+
+        let asset_uris = referenced_asset_uris(&persisted.works);
+        let mut asset_urls = HashMap::new();
+        for uri in &asset_uris {
+            let Some(id) = storage::asset_id(uri) else {
+                continue;
+            };
+            match storage::load_asset(id).await {
+                Ok(Some(asset)) => {
+                    asset_urls.insert(uri.clone(), asset.object_url());
+                }
+                Ok(None) => {}
+                Err(e) => tracing::error!("Failed to load asset {}: {}", id, e),
+            }
+        }
+
+        let impressions: HashMap<Uuid, Impression> = persisted
+            .impressions
+            .into_iter()
+            .map(|impression| (impression.id, impression))
+            .collect();
+        let mut indices = resurfacing_order(
+            &persisted.mnemons,
+            &impressions,
+            chrono::Utc::now(),
+            rand::thread_rng().gen(),
+        );
+
+        // Today's anniversaries come up first
+        let featured = featured_anniversaries(&persisted.mnemons, today());
+        if !featured.is_empty() {
+            info!("{} anniversaries today", featured.len());
+            indices.sort_by_key(|&i| {
+                let id = persisted.mnemons[i].id;
+                !featured.iter().any(|a| a.mnemon_id == id)
+            });
+        }
 
         self.works.set(persisted.works);
         self.mnemons.set(persisted.mnemons);
         self.shuffled_indices.set(indices);
+        self.asset_urls.set(asset_urls);
+        self.impressions.set(impressions);
         self.loaded.set(true);
     }
 
@@ -52,6 +144,79 @@ impl AppState {
         *self.loaded.read()
     }
 
+    /// URL an image or track can be loaded from: network URLs as they are, stored
+    /// assets as `blob:` URLs (`None` if the asset is missing)
+    pub fn media_src(&self, uri: &str) -> Option<String> {
+        if storage::asset_id(uri).is_some() {
+            self.asset_urls.read().get(uri).cloned()
+        } else {
+            Some(uri.to_string())
+        }
+    }
+
+    /// Keep an asset in memory and persist it, returning the URI works refer to it by
+    ///
+    /// Until a saved work refers to it, `collect_unused_assets` deletes it again.
+    pub fn add_asset(&mut self, asset: storage::StoredAsset) -> String {
+        let uri = asset.uri();
+        self.asset_urls
+            .write()
+            .insert(uri.clone(), asset.object_url());
+
+        spawn(async move {
+            if let Err(e) = storage::save_asset(&asset).await {
+                tracing::error!("Failed to persist asset: {}", e);
+            }
+        });
+        uri
+    }
+
+    /// Delete the assets no work refers to, e.g. an upload whose form was cancelled,
+    /// a cover that was replaced, or one left behind by an earlier session
+    ///
+    /// Only call it while no upload is pending: an upload is kept until its form
+    /// closes. Stored assets are only deleted if, once listed, no work refers to them
+    /// and they aren't loaded in memory (so an upload started meanwhile is kept).
+    pub fn collect_unused_assets(&self) {
+        let works = self.works;
+        let referenced = referenced_asset_uris(&works.peek());
+        let mut asset_urls = self.asset_urls;
+        let unused: Vec<String> = asset_urls
+            .peek()
+            .keys()
+            .filter(|uri| !referenced.contains(*uri))
+            .cloned()
+            .collect();
+        if !unused.is_empty() {
+            let mut asset_urls = asset_urls.write();
+            for uri in &unused {
+                if let Some(url) = asset_urls.remove(uri) {
+                    storage::revoke_asset_url(&url);
+                }
+            }
+        }
+
+        spawn(async move {
+            let ids = match storage::load_asset_ids().await {
+                Ok(ids) => ids,
+                Err(e) => {
+                    tracing::error!("Failed to list assets: {}", e);
+                    return;
+                }
+            };
+            let referenced = referenced_asset_uris(&works.peek());
+            for id in ids {
+                let uri = storage::StoredAsset::uri_of(&id);
+                if referenced.contains(&uri) || asset_urls.peek().contains_key(&uri) {
+                    continue;
+                }
+                if let Err(e) = storage::delete_asset(&id).await {
+                    tracing::error!("Failed to delete unused asset {}: {}", id, e);
+                }
+            }
+        });
+    }
+
     pub fn get_mnemons_with_works(&self) -> Vec<MnemonWithWork> {
         let works = self.works.read();
         let mnemons = self.mnemons.read();
@@ -71,23 +236,47 @@ impl AppState {
     }
 
     pub fn get_shuffled_index(&self, shuffled_position: usize) -> Option<usize> {
-        self.shuffled_indices.read().get(shuffled_position).copied()
+        self.cycle_order.read().get(shuffled_position).copied()
+    }
+
+    /// Replace the slideshow filter, remembering it for the next session
+    pub fn set_slideshow_filter(&mut self, filter: SlideshowFilter) {
+        settings::save_slideshow_filter(&filter);
+        self.slideshow_filter.set(filter);
+    }
+
+    /// Position of a mnemon in the hero's cycling order (none if filtered out)
+    pub fn shuffled_position_of(&self, mnemon_id: Uuid) -> Option<usize> {
+        let mnemon_idx = self.mnemons.read().iter().position(|m| m.id == mnemon_id)?;
+        self.cycle_order
+            .read()
+            .iter()
+            .position(|&i| i == mnemon_idx)
+    }
+
+    /// Position of a mnemon the user asked to see, clearing the slideshow filter if
+    /// it hides the mnemon
+    pub fn reveal_mnemon(&mut self, mnemon_id: Uuid) -> Option<usize> {
+        if let Some(position) = self.shuffled_position_of(mnemon_id) {
+            return Some(position);
+        }
+        if !self.slideshow_filter.peek().is_empty() {
+            info!("Clearing the slideshow filter to show mnemon {}", mnemon_id);
+            self.set_slideshow_filter(SlideshowFilter::default());
+        }
+        self.shuffled_position_of(mnemon_id)
     }
 
+    /// Number of mnemons the hero cycles through
     pub fn mnemons_count(&self) -> usize {
-        self.shuffled_indices.read().len()
+        self.cycle_order.read().len()
     }
 
     pub fn find_work_by_provider_ref(&self, provider_ref: &ProviderRef) -> Option<Work> {
         self.works
             .read()
             .iter()
-            .find(|w| {
-                w.provider_ref
-                    .as_ref()
-                    .map(|pr| pr.matches(provider_ref))
-                    .unwrap_or(false)
-            })
+            .find(|w| w.has_provider_ref(provider_ref))
             .cloned()
     }
 
@@ -99,6 +288,26 @@ impl AppState {
         }
     }
 
+    pub fn has_mnemon_for_work(&self, work_id: Uuid) -> bool {
+        self.mnemons.read().iter().any(|m| m.work_id == work_id)
+    }
+
+    /// Works whose display, original or alternate title matches the query
+    ///
+    /// Returns each work with the title that matched, so callers can show the alias.
+    pub fn search_library(&self, query: &str, work_type: Option<&WorkType>) -> Vec<(Work, String)> {
+        self.works
+            .read()
+            .iter()
+            .filter(|w| work_type.is_none_or(|wt| w.work_type == *wt))
+            .filter_map(|w| {
+                let matched = w.matching_title(query)?.to_string();
+                Some((w.clone(), matched))
+            })
+            .take(LIBRARY_SEARCH_LIMIT)
+            .collect()
+    }
+
     pub fn add_work(&mut self, work: Work) -> Uuid {
         let id = work.id;
         let work_clone = work.clone();
@@ -113,17 +322,178 @@ impl AppState {
         id
     }
 
-    pub fn add_mnemon(&mut self, mnemon: Mnemon) -> usize {
+    /// Replace a stored work (matched by id) and persist it
+    pub fn update_work(&mut self, work: Work) {
+        let mut works = self.works.write();
+        let Some(existing) = works.iter_mut().find(|w| w.id == work.id) else {
+            return;
+        };
+        *existing = work.clone();
+        drop(works);
+
+        spawn(async move {
+            if let Err(e) = storage::save_work(&work).await {
+                tracing::error!("Failed to persist updated work: {}", e);
+            }
+        });
+    }
+
+    /// Replace a work's cover with the user's picks (first is the cover, the rest
+    /// fallbacks), kept across metadata refreshes
+    pub fn set_work_covers(&mut self, work_id: Uuid, covers: Vec<String>) {
+        let Some(mut work) = self.works.read().iter().find(|w| w.id == work_id).cloned() else {
+            return;
+        };
+        work.set_covers(covers);
+        info!("Picked covers for work {}", work_id);
+        self.update_work(work);
+    }
+
+    /// Attach theme music to a work (or remove it with `None`)
+    pub fn set_work_theme_music(
+        &mut self,
+        work_id: Uuid,
+        uri: Option<String>,
+        trim: Option<ThemeTrim>,
+    ) {
+        let Some(mut work) = self.works.read().iter().find(|w| w.id == work_id).cloned() else {
+            return;
+        };
+        work.theme_music_local_uri = uri;
+        work.theme_music_trim = trim;
+        info!("Updated theme music for work {}", work_id);
+        self.update_work(work);
+    }
+
+    /// Move every mnemon from one work to another and drop the emptied work
+    ///
+    /// `fields` are taken from the dropped work (see `Work::absorb`); its titles stay
+    /// searchable on the surviving work.
+    pub fn merge_work_into(&mut self, from: Uuid, into: Uuid, fields: &[WorkField]) {
+        if from == into {
+            return;
+        }
+        let mut works = self.works.write();
+        let from_pos = works.iter().position(|w| w.id == from);
+        let into_pos = works.iter().position(|w| w.id == into);
+        let (Some(from_pos), Some(into_pos)) = (from_pos, into_pos) else {
+            return;
+        };
+        let merged = works[from_pos].clone();
+        works[into_pos].absorb(&merged, fields);
+        let target = works[into_pos].clone();
+        works.remove(from_pos);
+        drop(works);
+
+        let moved: Vec<Mnemon> = self
+            .mnemons
+            .write()
+            .iter_mut()
+            .filter(|m| m.work_id == from)
+            .map(|m| {
+                m.work_id = into;
+                m.clone()
+            })
+            .collect();
+        info!(
+            "Merged work {} into {} ({} mnemons moved)",
+            from,
+            into,
+            moved.len()
+        );
+
+        spawn(async move {
+            for mnemon in &moved {
+                if let Err(e) = storage::save_mnemon(mnemon).await {
+                    tracing::error!("Failed to persist moved mnemon: {}", e);
+                }
+            }
+            if let Err(e) = storage::save_work(&target).await {
+                tracing::error!("Failed to persist merged work: {}", e);
+            }
+            if let Err(e) = storage::delete_work(&from).await {
+                tracing::error!("Failed to delete merged work: {}", e);
+            }
+        });
+    }
+
+    /// Link a manual work to a provider entry, keeping its mnemons
+    ///
+    /// If another work already uses that provider entry, the manual work is merged
+    /// into it instead. Returns the work that now holds the mnemons.
+    pub fn link_work_to_provider(&mut self, work_id: Uuid, result: &SearchResult) -> Option<Work> {
+        let existing = self
+            .find_work_by_provider_ref(&result.provider_ref)
+            .filter(|w| w.id != work_id);
+        if let Some(existing) = existing {
+            self.merge_work_into(work_id, existing.id, &[]);
+            return self
+                .works
+                .read()
+                .iter()
+                .find(|w| w.id == existing.id)
+                .cloned();
+        }
+
+        let mut work = self
+            .works
+            .read()
+            .iter()
+            .find(|w| w.id == work_id)
+            .cloned()?;
+        work.link_to_provider(result);
+        if work.title_en == result.title {
+            work.title_language =
+                SearchService::title_language(&result.provider_ref.provider_source);
+        }
+        info!("Linked work {} to {:?}", work_id, result.provider_ref);
+        self.update_work(work.clone());
+        Some(work)
+    }
+
+    /// Record that two works flagged as duplicates are different works
+    pub fn mark_distinct(&mut self, a: Uuid, b: Uuid) {
+        let mut changed = Vec::new();
+        for work in self.works.write().iter_mut() {
+            let other = if work.id == a {
+                b
+            } else if work.id == b {
+                a
+            } else {
+                continue;
+            };
+            if !work.distinct_from.contains(&other) {
+                work.distinct_from.push(other);
+                changed.push(work.clone());
+            }
+        }
+
+        spawn(async move {
+            for work in &changed {
+                if let Err(e) = storage::save_work(work).await {
+                    tracing::error!("Failed to persist work: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Add a mnemon, returning its position in the cycling order (the slideshow
+    /// filter is cleared if it leaves the mnemon out)
+    pub fn add_mnemon(&mut self, mnemon: Mnemon) -> Option<usize> {
+        let mnemon_id = mnemon.id;
         let mnemon_clone = mnemon.clone();
         let new_index = self.mnemons.read().len();
         self.mnemons.write().push(mnemon);
 
-        let mut indices = self.shuffled_indices.write();
-        let mut rng = rand::thread_rng();
-        indices.shuffle(&mut rng);
+        // Reorder the others, then show the new mnemon next
+        let mut indices = resurfacing_order(
+            &self.mnemons.peek()[..new_index],
+            &self.impressions.peek(),
+            chrono::Utc::now(),
+            rand::thread_rng().gen(),
+        );
         indices.push(new_index);
-        let shuffled_position = indices.len() - 1;
-        drop(indices);
+        self.shuffled_indices.set(indices);
 
         spawn(async move {
             if let Err(e) = storage::save_mnemon(&mnemon_clone).await {
@@ -131,10 +501,15 @@ impl AppState {
             }
         });
 
-        shuffled_position
+        self.reveal_mnemon(mnemon_id)
     }
 
-    pub fn remove_mnemon(&mut self, mnemon_id: Uuid) -> Option<(Mnemon, usize)> {
+    /// Remove a mnemon (and when it was last shown) from memory, returning what
+    /// `restore_mnemon` needs to undo it
+    pub fn remove_mnemon(
+        &mut self,
+        mnemon_id: Uuid,
+    ) -> Option<(Mnemon, usize, Option<Impression>)> {
         let mut mnemons = self.mnemons.write();
         let mut indices = self.shuffled_indices.write();
 
@@ -150,11 +525,18 @@ impl AppState {
             }
         }
 
+        let impression = self.impressions.write().remove(&mnemon_id);
+
         info!("Removed mnemon {} from memory", mnemon_id);
-        Some((mnemon, mnemon_idx))
+        Some((mnemon, mnemon_idx, impression))
     }
 
-    pub fn restore_mnemon(&mut self, mnemon: Mnemon, original_idx: usize) {
+    pub fn restore_mnemon(
+        &mut self,
+        mnemon: Mnemon,
+        original_idx: usize,
+        impression: Option<Impression>,
+    ) {
         let mut mnemons = self.mnemons.write();
         let mut indices = self.shuffled_indices.write();
 
@@ -168,6 +550,10 @@ impl AppState {
 
         indices.insert(0, original_idx);
 
+        if let Some(impression) = impression {
+            self.impressions.write().insert(mnemon.id, impression);
+        }
+
         info!("Restored mnemon {}", mnemon.id);
     }
 
@@ -176,6 +562,45 @@ impl AppState {
             if let Err(e) = storage::delete_mnemon(&mnemon_id).await {
                 info!("Failed to delete mnemon from storage: {}", e);
             }
+            if let Err(e) = storage::delete_impression(&mnemon_id).await {
+                info!("Failed to delete impression from storage: {}", e);
+            }
+        });
+    }
+
+    /// Record that a mnemon was shown in the hero
+    ///
+    /// Only touches `impressions`, which nothing renders from, so the hero isn't
+    /// re-rendered by its own impressions.
+    pub fn record_impression(&self, mnemon_id: Uuid) {
+        let mut impressions = self.impressions;
+        let impression = record_impression(
+            mnemon_id,
+            impressions.peek().get(&mnemon_id),
+            chrono::Utc::now(),
+        );
+        impressions.write().insert(mnemon_id, impression.clone());
+
+        spawn(async move {
+            if let Err(e) = storage::save_impression(&impression).await {
+                info!("Failed to persist impression: {}", e);
+            }
+        });
+    }
+
+    /// Mark or unmark a mnemon as a favorite (favorites resurface more often)
+    pub fn set_mnemon_favorite(&mut self, mnemon_id: Uuid, favorite: bool) {
+        let mut mnemons = self.mnemons.write();
+        let Some(mnemon) = mnemons.iter_mut().find(|m| m.id == mnemon_id) else {
+            return;
+        };
+        mnemon.favorite = favorite;
+        let mnemon_clone = mnemon.clone();
+
+        spawn(async move {
+            if let Err(e) = storage::save_mnemon(&mnemon_clone).await {
+                tracing::error!("Failed to persist favorite: {}", e);
+            }
         });
     }
 
@@ -203,3 +628,13 @@ impl AppState {
         }
     }
 }
+
+/// Asset URIs the works refer to (covers, cover fallbacks and theme music)
+fn referenced_asset_uris(works: &[Work]) -> HashSet<String> {
+    works
+        .iter()
+        .flat_map(|w| w.covers().chain(w.theme_music_local_uri.iter()))
+        .filter(|uri| storage::asset_id(uri).is_some())
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,515 @@
+//! Theme music playback
+//!
+//! One `AudioController` is shared through context, so only one theme plays at a time
+//! and every view shows the same progress. It drives a single `<audio>` element
+//! created off-DOM:
+//! - A trimmed theme plays only its segment; positions, progress and seeking are
+//!   relative to the segment
+//! - Browsers may reject `play()` until the user interacts with the page; the
+//!   controller then waits in `Blocked` for a tap
+//! - Progress is read from the element on a short poll while a track is loaded
+//! - Switching themes while one plays crossfades between two elements, shaped by the
+//!   user's `FadeCurve`; muting keeps playback going silently, so unmuting picks up
+//!   where the music is
+
+use dioxus::prelude::*;
+use uuid::Uuid;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::app_state::AppState;
+use crate::constants::{AUDIO_POLL_MS, THEME_FADE_STEP_MS};
+use crate::models::{ThemeTrim, Work};
+use crate::settings;
+
+/// A theme ready to play
+#[derive(Clone, PartialEq, Debug)]
+pub struct ThemeTrack {
+    pub work_id: Uuid,
+    /// URL the audio element loads (network URL or `blob:` URL of an asset)
+    pub src: String,
+    pub trim: Option<ThemeTrim>,
+}
+
+impl ThemeTrack {
+    /// The theme of a work, if it has one that can be loaded
+    pub fn for_work(work: &Work, app_state: &AppState) -> Option<Self> {
+        let uri = work.theme_music_local_uri.as_deref()?;
+        Some(Self {
+            work_id: work.id,
+            src: app_state.media_src(uri)?,
+            trim: work.theme_music_trim,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum PlaybackStatus {
+    /// Nothing loaded
+    Stopped,
+    Loading,
+    Playing,
+    Paused,
+    /// The browser refused to start playback without a user gesture
+    Blocked,
+    Failed(String),
+}
+
+/// Shape of a crossfade between two themes
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum FadeCurve {
+    /// Volumes change at a constant rate (dips in the middle)
+    Linear,
+    /// Constant perceived loudness throughout the fade
+    #[default]
+    EqualPower,
+    /// Eases in and out, holding each track a little longer
+    Smooth,
+}
+
+impl FadeCurve {
+    pub const ALL: [FadeCurve; 3] = [FadeCurve::Linear, FadeCurve::EqualPower, FadeCurve::Smooth];
+
+    /// Key stored in settings
+    pub fn key(&self) -> &'static str {
+        match self {
+            FadeCurve::Linear => "linear",
+            FadeCurve::EqualPower => "equal-power",
+            FadeCurve::Smooth => "smooth",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|curve| curve.key() == key)
+    }
+
+    /// The curve saved in settings, or the default
+    fn saved() -> Self {
+        settings::fade_curve_key()
+            .and_then(|key| Self::from_key(&key))
+            .unwrap_or_default()
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FadeCurve::Linear => "Linear",
+            FadeCurve::EqualPower => "Equal power",
+            FadeCurve::Smooth => "Smooth",
+        }
+    }
+
+    /// Gains of the outgoing and incoming themes at a point in the fade (0 to 1)
+    pub fn gains(&self, t: f64) -> (f64, f64) {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            FadeCurve::Linear => (1.0 - t, t),
+            FadeCurve::EqualPower => {
+                let angle = t * std::f64::consts::FRAC_PI_2;
+                (angle.cos(), angle.sin())
+            }
+            FadeCurve::Smooth => {
+                let eased = t * t * (3.0 - 2.0 * t);
+                (1.0 - eased, eased)
+            }
+        }
+    }
+}
+
+/// Start and end of the part of a track that plays
+pub fn segment_bounds(trim: Option<ThemeTrim>, duration: Option<f64>) -> (f64, Option<f64>) {
+    let start = trim.map(|t| t.start_secs).unwrap_or(0.0);
+    let end = match (trim.and_then(|t| t.end_secs), duration) {
+        (Some(end), Some(duration)) => Some(end.min(duration)),
+        (end, duration) => end.or(duration),
+    };
+    (start, end)
+}
+
+/// How far playback is through the segment, from 0 to 1
+pub fn segment_progress(position: f64, trim: Option<ThemeTrim>, duration: Option<f64>) -> f64 {
+    let (start, end) = segment_bounds(trim, duration);
+    match end {
+        Some(end) if end > start => ((position - start) / (end - start)).clamp(0.0, 1.0),
+        _ => 0.0,
+    }
+}
+
+/// Track position for a point in the segment (0 to 1)
+pub fn segment_position(fraction: f64, trim: Option<ThemeTrim>, duration: Option<f64>) -> f64 {
+    let (start, end) = segment_bounds(trim, duration);
+    match end {
+        Some(end) => start + (end - start) * fraction.clamp(0.0, 1.0),
+        None => start,
+    }
+}
+
+/// Shared theme music player
+#[derive(Clone, Copy)]
+pub struct AudioController {
+    /// Element playing the current track
+    element: Signal<Option<web_sys::HtmlAudioElement>>,
+    /// Element of the previous track while it fades out
+    outgoing: Signal<Option<web_sys::HtmlAudioElement>>,
+    /// Bumped by every crossfade, so an interrupted fade stops ramping
+    fade_generation: Signal<u32>,
+    pub track: Signal<Option<ThemeTrack>>,
+    pub status: Signal<PlaybackStatus>,
+    /// Position in the track, in seconds
+    pub position: Signal<f64>,
+    /// Length of the whole track, once known
+    pub duration: Signal<Option<f64>>,
+    /// Volume from 0 to 1
+    pub volume: Signal<f64>,
+    /// Silences theme music everywhere (saved in settings)
+    pub muted: Signal<bool>,
+    pub fade_curve: Signal<FadeCurve>,
+    /// Bumped each time a track's segment plays to the end
+    pub finished: Signal<u32>,
+}
+
+impl AudioController {
+    fn new() -> Self {
+        Self {
+            element: Signal::new(None),
+            track: Signal::new(None),
+            status: Signal::new(PlaybackStatus::Stopped),
+            position: Signal::new(0.0),
+            duration: Signal::new(None),
+            volume: Signal::new(1.0),
+            outgoing: Signal::new(None),
+            fade_generation: Signal::new(0),
+            muted: Signal::new(settings::theme_muted()),
+            fade_curve: Signal::new(FadeCurve::saved()),
+            finished: Signal::new(0),
+        }
+    }
+
+    fn create_element(&mut self) -> Option<web_sys::HtmlAudioElement> {
+        match web_sys::HtmlAudioElement::new() {
+            Ok(element) => Some(element),
+            Err(e) => {
+                self.status
+                    .set(PlaybackStatus::Failed(format!("No audio support: {:?}", e)));
+                None
+            }
+        }
+    }
+
+    /// The audio element, created on first use
+    fn element(&mut self) -> Option<web_sys::HtmlAudioElement> {
+        if self.element.peek().is_none() {
+            let element = self.create_element()?;
+            element.set_volume(self.output_volume());
+            self.element.set(Some(element));
+        }
+        self.element.peek().clone()
+    }
+
+    /// Volume the elements play at (silent while muted)
+    fn output_volume(&self) -> f64 {
+        if *self.muted.peek() {
+            0.0
+        } else {
+            *self.volume.peek()
+        }
+    }
+
+    /// Point an element at a track and make it the current one
+    fn load(&mut self, element: &web_sys::HtmlAudioElement, track: ThemeTrack) {
+        element.set_src(&track.src);
+        let start = track.trim.map(|t| t.start_secs).unwrap_or(0.0);
+        element.set_current_time(start);
+        self.position.set(start);
+        self.duration.set(None);
+        self.track.set(Some(track));
+    }
+
+    /// End a running crossfade at once: unload the outgoing theme and restore the
+    /// current one's volume
+    fn finish_fade(&mut self) {
+        let generation = self.fade_generation.peek().wrapping_add(1);
+        self.fade_generation.set(generation);
+        if let Some(outgoing) = self.outgoing.take() {
+            let _ = outgoing.pause();
+            outgoing.remove_attribute("src").ok();
+            outgoing.load();
+        }
+        if let Some(element) = self.element.peek().as_ref() {
+            element.set_volume(self.output_volume());
+        }
+    }
+
+    /// Whether a work's theme is the loaded track
+    pub fn is_current(&self, work_id: Uuid) -> bool {
+        self.track
+            .read()
+            .as_ref()
+            .is_some_and(|t| t.work_id == work_id)
+    }
+
+    /// Whether a work's theme is playing
+    pub fn is_playing(&self, work_id: Uuid) -> bool {
+        self.is_current(work_id) && *self.status.read() == PlaybackStatus::Playing
+    }
+
+    /// How far playback is through the current segment, from 0 to 1
+    pub fn progress(&self) -> f64 {
+        let trim = self.track.read().as_ref().and_then(|t| t.trim);
+        segment_progress(*self.position.read(), trim, *self.duration.read())
+    }
+
+    /// Play a theme, resuming it if it is already loaded
+    pub fn play(&mut self, track: ThemeTrack) {
+        self.finish_fade();
+        let Some(element) = self.element() else {
+            return;
+        };
+        if self.track.peek().as_ref() != Some(&track) {
+            self.load(&element, track);
+        }
+        self.start(element);
+    }
+
+    /// Fade from the playing theme into another one over `duration_ms`
+    ///
+    /// Plays the theme straight away when nothing is playing, and does nothing when
+    /// it is already the loaded track.
+    pub fn crossfade_to(&mut self, track: ThemeTrack, duration_ms: u32) {
+        if self.track.peek().as_ref() == Some(&track) {
+            return;
+        }
+        if *self.status.peek() != PlaybackStatus::Playing {
+            self.play(track);
+            return;
+        }
+        self.finish_fade();
+        let outgoing = self.element.peek().clone();
+        let (Some(outgoing), Some(incoming)) = (outgoing, self.create_element()) else {
+            self.play(track);
+            return;
+        };
+
+        incoming.set_volume(0.0);
+        self.outgoing.set(Some(outgoing.clone()));
+        self.element.set(Some(incoming.clone()));
+        self.load(&incoming, track);
+        self.start(incoming.clone());
+
+        let generation = *self.fade_generation.peek();
+        let curve = *self.fade_curve.peek();
+        let steps = (duration_ms / THEME_FADE_STEP_MS).max(1);
+        let mut controller = *self;
+        spawn(async move {
+            for step in 1..=steps {
+                gloo_timers::future::TimeoutFuture::new(THEME_FADE_STEP_MS).await;
+                if *controller.fade_generation.peek() != generation {
+                    return;
+                }
+                let (out_gain, in_gain) = curve.gains(step as f64 / steps as f64);
+                let volume = controller.output_volume();
+                outgoing.set_volume(volume * out_gain);
+                incoming.set_volume(volume * in_gain);
+            }
+            controller.finish_fade();
+        });
+    }
+
+    /// Start the loaded track, falling back to `Blocked` if autoplay is refused
+    fn start(&mut self, element: web_sys::HtmlAudioElement) {
+        self.status.set(PlaybackStatus::Loading);
+        let mut status = self.status;
+        let Ok(promise) = element.play() else {
+            status.set(PlaybackStatus::Failed("Playback failed".to_string()));
+            return;
+        };
+        spawn(async move {
+            match JsFuture::from(promise).await {
+                Ok(_) => status.set(PlaybackStatus::Playing),
+                Err(e) => {
+                    let name = js_sys::Reflect::get(&e, &"name".into())
+                        .ok()
+                        .and_then(|n| n.as_string())
+                        .unwrap_or_default();
+                    if name == "NotAllowedError" {
+                        status.set(PlaybackStatus::Blocked);
+                    } else if name != "AbortError" {
+                        // AbortError means a newer src or pause() replaced this play()
+                        status.set(PlaybackStatus::Failed(format!("{:?}", e)));
+                    }
+                }
+            }
+        });
+    }
+
+    pub fn pause(&mut self) {
+        self.finish_fade();
+        if let Some(element) = self.element.peek().as_ref() {
+            let _ = element.pause();
+        }
+        if *self.status.peek() != PlaybackStatus::Stopped {
+            self.status.set(PlaybackStatus::Paused);
+        }
+    }
+
+    /// Play or pause a theme, e.g. from a play button
+    pub fn toggle(&mut self, track: ThemeTrack) {
+        if self.is_current(track.work_id) && *self.status.peek() == PlaybackStatus::Playing {
+            self.pause();
+        } else {
+            self.play(track);
+        }
+    }
+
+    /// Stop playback and unload the track
+    pub fn stop(&mut self) {
+        self.finish_fade();
+        if let Some(element) = self.element.peek().as_ref() {
+            let _ = element.pause();
+            element.remove_attribute("src").ok();
+            element.load();
+        }
+        self.track.set(None);
+        self.status.set(PlaybackStatus::Stopped);
+        self.position.set(0.0);
+        self.duration.set(None);
+    }
+
+    /// Jump to a point in the current segment (0 to 1)
+    pub fn seek(&mut self, fraction: f64) {
+        let Some(element) = self.element.peek().clone() else {
+            return;
+        };
+        let trim = self.track.peek().as_ref().and_then(|t| t.trim);
+        let position = segment_position(fraction, trim, *self.duration.peek());
+        element.set_current_time(position);
+        self.position.set(position);
+    }
+
+    pub fn set_volume(&mut self, volume: f64) {
+        self.volume.set(volume.clamp(0.0, 1.0));
+        if let Some(element) = self.element.peek().as_ref() {
+            element.set_volume(self.output_volume());
+        }
+    }
+
+    /// Mute or unmute theme music, remembering the choice
+    pub fn set_muted(&mut self, muted: bool) {
+        self.muted.set(muted);
+        settings::save_theme_muted(muted);
+        if let Some(element) = self.element.peek().as_ref() {
+            element.set_volume(self.output_volume());
+        }
+    }
+
+    /// Change how themes fade into each other, remembering the choice
+    pub fn set_fade_curve(&mut self, curve: FadeCurve) {
+        self.fade_curve.set(curve);
+        settings::save_fade_curve((curve != FadeCurve::default()).then(|| curve.key()));
+    }
+
+    /// Pick up the saved mute and crossfade preferences again (after
+    /// `settings::clear_all_settings`)
+    #[allow(dead_code)]
+    pub fn reload_preferences(&mut self) {
+        self.muted.set(settings::theme_muted());
+        self.fade_curve.set(FadeCurve::saved());
+        if let Some(element) = self.element.peek().as_ref() {
+            element.set_volume(self.output_volume());
+        }
+    }
+
+    /// Read the element's state into the signals, and stop at the end of the segment
+    fn sync(&mut self) {
+        let Some(element) = self.element.peek().clone() else {
+            return;
+        };
+        let Some(trim) = self.track.peek().as_ref().map(|t| t.trim) else {
+            return;
+        };
+
+        if let Some(error) = element.error() {
+            let message = format!("Could not play theme music (error {})", error.code());
+            if *self.status.peek() != PlaybackStatus::Failed(message.clone()) {
+                self.status.set(PlaybackStatus::Failed(message));
+            }
+            return;
+        }
+
+        let duration = Some(element.duration()).filter(|d| d.is_finite());
+        if *self.duration.peek() != duration {
+            self.duration.set(duration);
+        }
+        let position = element.current_time();
+        if *self.position.peek() != position {
+            self.position.set(position);
+        }
+
+        let (start, end) = segment_bounds(trim, duration);
+        let finished = element.ended() || end.is_some_and(|end| position >= end);
+        if finished && *self.status.peek() == PlaybackStatus::Playing {
+            let _ = element.pause();
+            element.set_current_time(start);
+            self.position.set(start);
+            self.status.set(PlaybackStatus::Paused);
+            let finished = self.finished.peek().wrapping_add(1);
+            self.finished.set(finished);
+        }
+    }
+}
+
+/// Provide the shared audio controller to the app
+pub fn use_audio_controller_provider() -> AudioController {
+    let controller = use_context_provider(AudioController::new);
+
+    use_future(move || async move {
+        let mut controller = controller;
+        loop {
+            gloo_timers::future::TimeoutFuture::new(AUDIO_POLL_MS).await;
+            controller.sync();
+        }
+    });
+
+    controller
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_progress_is_relative_to_trim() {
+        let trim = Some(ThemeTrim {
+            start_secs: 30.0,
+            end_secs: Some(50.0),
+        });
+        assert_eq!(segment_bounds(trim, Some(200.0)), (30.0, Some(50.0)));
+        assert_eq!(segment_progress(40.0, trim, Some(200.0)), 0.5);
+        assert_eq!(segment_position(0.25, trim, Some(200.0)), 35.0);
+
+        // Untrimmed tracks use their length, once it is known
+        assert_eq!(segment_progress(50.0, None, Some(200.0)), 0.25);
+        assert_eq!(segment_progress(50.0, None, None), 0.0);
+
+        // A trim past the end of the track stops at the end
+        let open_ended = Some(ThemeTrim {
+            start_secs: 10.0,
+            end_secs: Some(500.0),
+        });
+        assert_eq!(segment_bounds(open_ended, Some(200.0)), (10.0, Some(200.0)));
+    }
+
+    #[test]
+    fn test_fade_curves() {
+        for curve in FadeCurve::ALL {
+            assert_eq!(curve.gains(0.0), (1.0, 0.0));
+            let (out_gain, in_gain) = curve.gains(1.0);
+            assert!(out_gain.abs() < 1e-9 && (in_gain - 1.0).abs() < 1e-9);
+            assert_eq!(FadeCurve::from_key(curve.key()), Some(curve));
+        }
+
+        // Equal power keeps the summed power constant; linear dips in the middle
+        let (out_gain, in_gain) = FadeCurve::EqualPower.gains(0.5);
+        assert!((out_gain.powi(2) + in_gain.powi(2) - 1.0).abs() < 1e-9);
+        let (out_gain, in_gain) = FadeCurve::Linear.gains(0.5);
+        assert!(out_gain.powi(2) + in_gain.powi(2) < 1.0);
+    }
+}
@@ -1,14 +1,21 @@
 //! Add mnemon flow - multi-step wizard for creating a new mnemon
 
+use chrono::Datelike;
 use dioxus::prelude::*;
 use tracing::info;
 
 use crate::app_state::AppState;
-use crate::components::{FeelingsSelector, FinishedDateInput, NotesInput};
+use crate::components::{
+    CoverUpload, FeelingsSelector, FinishedDateInput, NotesInput, ThemeMusicUpload,
+};
 use crate::constants::*;
-use crate::data::{SearchService, SearchStatus};
+use crate::data::links::parse_provider_url;
+use crate::data::query::parse_query;
+use crate::data::{SearchGroup, SearchService, SearchStatus};
 use crate::forms::MnemonForm;
 use crate::models::*;
+use crate::providers::images::{sized_image_url, ImageKind, ImageSize};
+use crate::providers::ConnectionCheck;
 use crate::settings;
 
 #[component]
@@ -62,6 +69,15 @@ pub fn AddMnemonFlow(
 // STEP 1: PICK THE WORK
 // =============================================================================
 
+/// Progress of looking up a pasted provider link
+#[derive(Clone, Debug, PartialEq)]
+enum LinkLookup {
+    /// Waiting for the named provider
+    Resolving(&'static str),
+    /// The link could not be resolved
+    Failed(String),
+}
+
 #[component]
 fn Step1ManualEntry(
     form: MnemonForm,
@@ -69,41 +85,137 @@ fn Step1ManualEntry(
     on_cancel: EventHandler<()>,
     on_settings: EventHandler<()>,
 ) -> Element {
+    // Type the user explicitly picked; narrows the search to a single group
+    let mut search_scope = use_signal(|| {
+        if form.provider_ref.is_none() {
+            form.work_type.clone()
+        } else {
+            None
+        }
+    });
     let mut local_form = use_signal(|| form);
-    let mut search_results = use_signal(Vec::<SearchResult>::new);
+    let mut search_groups = use_signal(Vec::<SearchGroup>::new);
     let mut show_results = use_signal(|| false);
     let mut existing_work_error = use_signal(|| false);
-    let mut is_searching = use_signal(|| false);
-    let mut search_status = use_signal(|| Option::<SearchStatus>::None);
+    let mut link_lookup = use_signal(|| Option::<LinkLookup>::None);
 
     // Search trigger signals - when these change, a search is triggered
     let mut search_query = use_signal(String::new);
-    let mut search_type = use_signal(|| Option::<WorkType>::None);
     let mut search_force = use_signal(|| false);
     let mut search_version = use_signal(|| 0u32);
 
+    // Query the current groups were searched with (used to fetch further pages)
+    let mut searched_query = use_signal(String::new);
+
     let app_state = use_context::<Signal<AppState>>();
 
     // Create search service once
     let search_service = use_hook(SearchService::new);
 
     let is_valid = local_form().is_step1_valid() && !existing_work_error();
+    let is_searching = search_groups().iter().any(|g| g.is_pending())
+        || matches!(link_lookup(), Some(LinkLookup::Resolving(_)));
+    let has_results = search_groups().iter().any(|g| !g.results.is_empty());
 
     // Check if APIs are configured (from localStorage or compile-time env)
     let is_tmdb_configured = settings::is_tmdb_configured();
     let is_rawg_configured = settings::is_rawg_configured();
 
+    // Warn up front about credentials that failed their last connection test
+    let rejected_credentials: Vec<(&str, String, String)> = [
+        ("TMDB", settings::load_tmdb_verification()),
+        ("RAWG", settings::load_rawg_verification()),
+    ]
+    .into_iter()
+    .filter_map(|(name, verification)| {
+        let verification = verification?;
+        let ConnectionCheck::Invalid(message) = verification.outcome else {
+            return None;
+        };
+        let checked_on = verification
+            .checked_at
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d")
+            .to_string();
+        Some((name, checked_on, message))
+    })
+    .collect();
+
     // Check if provider ref already exists
     let check_existing_work = move |provider_ref: &ProviderRef| -> bool {
         app_state().has_mnemon_for_provider_ref(provider_ref)
     };
 
-    // Effect-based async search with debouncing
+    // Autofill the form from a picked search result
+    let mut select_result = move |result: SearchResult| {
+        info!("Result selected: {}", result.title);
+
+        if check_existing_work(&result.provider_ref) {
+            info!("Work already has a mnemon - showing error");
+            existing_work_error.set(true);
+            show_results.set(false);
+        } else {
+            info!("Autofilling form with result");
+            local_form.with_mut(|f| {
+                f.work_type = Some(result.work_type.clone());
+                f.title = result.title.clone();
+                f.year = result.year.map(|y| y.to_string()).unwrap_or_default();
+                f.provider_ref = Some(result.provider_ref.clone());
+                f.original_title = result.original_title.clone();
+                f.existing_work_id = None;
+                f.cover_url = result.cover_url.clone();
+                f.backdrop_url = result.backdrop_url.clone();
+                f.theme_music_url = result.theme_music_url.clone();
+                f.theme_music_trim = None;
+            });
+            existing_work_error.set(false);
+            show_results.set(false);
+        }
+    };
+
+    // Works already in the library matching the query by any of their titles
+    let library_matches = {
+        let query = search_query();
+        if query.trim().chars().count() < SEARCH_MIN_CHARS || parse_provider_url(&query).is_some() {
+            Vec::new()
+        } else {
+            let parsed = parse_query(&query, chrono::Utc::now().year());
+            let scope = search_scope().or(parsed.work_type);
+            app_state().search_library(&parsed.text, scope.as_ref())
+        }
+    };
+
+    // Attach the mnemon to a work already in the library
+    let mut select_library_work = move |work: Work| {
+        info!("Library work selected: {}", work.title_en);
+
+        if app_state().has_mnemon_for_work(work.id) {
+            info!("Work already has a mnemon - showing error");
+            existing_work_error.set(true);
+        } else {
+            local_form.with_mut(|f| {
+                f.work_type = Some(work.work_type.clone());
+                f.title = work.title_en.clone();
+                f.year = work.release_year.map(|y| y.to_string()).unwrap_or_default();
+                f.provider_ref = work.provider_ref.clone();
+                f.original_title = work.original_title.clone();
+                f.existing_work_id = Some(work.id);
+                f.cover_url = work.cover_image_local_uri.clone();
+                f.backdrop_url = work.backdrop_url.clone();
+                f.theme_music_url = work.theme_music_local_uri.clone();
+                f.theme_music_trim = work.theme_music_trim;
+            });
+            existing_work_error.set(false);
+        }
+        show_results.set(false);
+    };
+
+    // Effect-based async fan-out search with debouncing
     {
         let service = search_service.clone();
         use_effect(move || {
             let query = search_query();
-            let work_type = search_type();
+            let scope = search_scope();
             let force = search_force();
             let version = search_version();
 
@@ -112,20 +224,61 @@ fn Step1ManualEntry(
                 search_force.set(false);
             }
 
-            // Don't search if no work type selected
-            let Some(wt) = work_type else {
+            // Pasted provider links are looked up directly instead of searched
+            if let Some(link) = parse_provider_url(&query) {
+                search_groups.set(Vec::new());
+                show_results.set(false);
+                link_lookup.set(Some(LinkLookup::Resolving(link.provider_name())));
+
+                let service = service.clone();
+                spawn(async move {
+                    info!("Resolving {} link: {:?}", link.provider_name(), link);
+                    let result = service.resolve_link(&link).await;
+
+                    if search_version() != version {
+                        info!("Link lookup discarded (superseded)");
+                        return;
+                    }
+
+                    match result {
+                        Ok(result) => {
+                            link_lookup.set(None);
+                            select_result(result);
+                        }
+                        Err(e) => {
+                            info!("Link lookup failed: {}", e);
+                            link_lookup.set(Some(LinkLookup::Failed(format!(
+                                "Couldn't open that {} link: {}",
+                                link.provider_name(),
+                                e
+                            ))));
+                        }
+                    }
+                });
                 return;
-            };
+            }
+            link_lookup.set(None);
 
             // Don't search if query is too short (unless forced or empty)
             if !force && !query.is_empty() && query.len() < SEARCH_MIN_CHARS {
-                search_results.set(Vec::new());
+                search_groups.set(Vec::new());
                 show_results.set(false);
                 return;
             }
 
-            is_searching.set(true);
-            search_status.set(None);
+            // Search the picked (or `t:` hinted) type only, or every type grouped
+            let hinted = parse_query(&query, chrono::Utc::now().year()).work_type;
+            let work_types = match scope.or(hinted) {
+                Some(wt) => vec![wt],
+                None => WorkType::ALL.to_vec(),
+            };
+            search_groups.set(
+                work_types
+                    .iter()
+                    .cloned()
+                    .map(SearchGroup::pending)
+                    .collect(),
+            );
 
             let service = service.clone();
             spawn(async move {
@@ -140,47 +293,91 @@ fn Step1ManualEntry(
                     return;
                 }
 
-                info!("Executing search for '{}' ({:?})", query, wt);
-                let response = service.search(&query, wt, 0).await;
-
-                // Check again after async operation
-                if search_version() != version {
-                    info!("Search results discarded (superseded)");
-                    return;
-                }
-
-                is_searching.set(false);
-                search_status.set(Some(response.status.clone()));
+                info!(
+                    "Executing search for '{}' across {} group(s)",
+                    query,
+                    work_types.len()
+                );
+                searched_query.set(query.clone());
+
+                // Each group streams into the dropdown as soon as it resolves
+                for (work_type, group_search) in service.search_all(&query, &work_types, 0) {
+                    spawn(async move {
+                        let group = group_search.await;
+
+                        // Check again after async operation
+                        if search_version() != version {
+                            info!("{} results discarded (superseded)", work_type.label());
+                            return;
+                        }
 
-                match response.status {
-                    SearchStatus::Success | SearchStatus::UsingFixtures => {
-                        info!("Search returned {} results", response.results.len());
-                        search_results.set(response.results);
-                        show_results.set(true);
-                    }
-                    SearchStatus::ProviderNotConfigured => {
-                        info!("Provider not configured");
-                        search_results.set(Vec::new());
-                        show_results.set(true);
-                    }
-                    SearchStatus::NetworkError(ref msg) => {
-                        info!("Network error: {}", msg);
-                        search_results.set(Vec::new());
-                        show_results.set(true);
-                    }
-                    SearchStatus::ApiError {
-                        status,
-                        ref message,
-                    } => {
-                        info!("API error ({}): {}", status, message);
-                        search_results.set(Vec::new());
+                        info!(
+                            "{} search returned {} results ({:?})",
+                            work_type.label(),
+                            group.results.len(),
+                            group.status
+                        );
+                        search_groups.with_mut(|groups| {
+                            if let Some(slot) = groups.iter_mut().find(|g| g.work_type == work_type)
+                            {
+                                *slot = group;
+                            }
+                        });
                         show_results.set(true);
-                    }
+                    });
                 }
             });
         });
     }
 
+    // Fetch the next page for every group that has more results
+    let mut load_next_pages = {
+        let service = search_service.clone();
+        move || {
+            let version = search_version();
+            let query = searched_query();
+            let next_pages: Vec<(WorkType, usize)> = search_groups
+                .peek()
+                .iter()
+                .filter(|g| g.can_load_more())
+                .map(|g| (g.work_type.clone(), g.page + 1))
+                .collect();
+
+            for (work_type, page) in next_pages {
+                search_groups.with_mut(|groups| {
+                    if let Some(group) = groups.iter_mut().find(|g| g.work_type == work_type) {
+                        group.loading_more = true;
+                    }
+                });
+
+                let service = service.clone();
+                let query = query.clone();
+                spawn(async move {
+                    info!(
+                        "Loading {} page {} for '{}'",
+                        work_type.label(),
+                        page + 1,
+                        query
+                    );
+                    let response = service.search(&query, work_type.clone(), page).await;
+
+                    if search_version() != version {
+                        info!("{} page discarded (superseded)", work_type.label());
+                        return;
+                    }
+
+                    search_groups.with_mut(|groups| {
+                        if let Some(group) = groups.iter_mut().find(|g| g.work_type == work_type) {
+                            group.append_page(response);
+                        }
+                    });
+                });
+            }
+        }
+    };
+
+    let mut load_more_pages = load_next_pages.clone();
+
     rsx! {
         div {
             class: "px-4 py-6 sm:p-8",
@@ -254,6 +451,22 @@ fn Step1ManualEntry(
                 }
             }
 
+            // Credentials known to be invalid (from the last Settings connection test)
+            for (name, checked_on, message) in rejected_credentials {
+                div {
+                    class: "mb-4 px-4 py-3 bg-red-900/30 border border-red-700/50 rounded-lg",
+                    div {
+                        class: "flex items-center gap-2 text-red-200",
+                        span { "⚠️" }
+                        span { class: "font-medium", "{name} credentials were rejected" }
+                    }
+                    p {
+                        class: "text-red-200/70 text-sm mt-1",
+                        "The connection test on {checked_on} failed: {message}. Update them in ⚙️ Settings, or enter the title manually."
+                    }
+                }
+            }
+
             // Type selection
             div {
                 class: "mb-6",
@@ -261,10 +474,11 @@ fn Step1ManualEntry(
                     class: "block text-white text-sm font-semibold mb-3",
                     "Type"
                     span { class: "text-red-400 ml-1", "*" }
+                    span { class: "text-gray-400 ml-2 text-xs font-normal", "(pick one to narrow the search)" }
                 }
                 div {
                     class: "flex gap-3",
-                    for work_type in [WorkType::Movie, WorkType::TvAnime, WorkType::Game] {
+                    for work_type in WorkType::ALL {
                         button {
                             class: if local_form().work_type == Some(work_type.clone()) {
                                 "flex items-center gap-2 px-4 py-3 bg-transparent text-white rounded-lg border-2 border-white font-medium"
@@ -272,19 +486,27 @@ fn Step1ManualEntry(
                                 "flex items-center gap-2 px-4 py-3 bg-gray-700 text-gray-300 rounded-lg border-2 border-gray-600 hover:border-gray-500 font-medium"
                             },
                             onclick: move |_| {
-                                info!("Type selected: {}", work_type.label());
+                                // Clicking the selected type again widens the search back to all types
+                                let deselect = search_scope() == Some(work_type.clone());
+                                info!("Type {}: {}", if deselect { "cleared" } else { "selected" }, work_type.label());
+                                let selected = if deselect { None } else { Some(work_type.clone()) };
                                 local_form.with_mut(|f| {
-                                    f.work_type = Some(work_type.clone());
+                                    f.work_type = selected.clone();
                                     // Clear provider data when changing type
                                     f.provider_ref = None;
+                                    f.original_title = None;
+                                    f.existing_work_id = None;
                                     f.cover_url = None;
+                                    f.backdrop_url = None;
                                     f.theme_music_url = None;
+                                    f.theme_music_trim = None;
                                 });
                                 existing_work_error.set(false);
-                                search_results.set(Vec::new());
+                                search_groups.set(Vec::new());
                                 show_results.set(false);
-                                search_status.set(None);
-                                search_type.set(Some(work_type.clone()));
+                                search_version.set(search_version() + 1);
+                                search_query.set(local_form().title.clone());
+                                search_scope.set(selected);
                             },
                             span { class: "text-xl", "{work_type.icon()}" }
                             span { "{work_type.label()}" }
@@ -298,7 +520,7 @@ fn Step1ManualEntry(
                 class: "mb-6 relative",
                 label {
                     class: "block text-white text-sm font-semibold mb-2",
-                    "Title (English)"
+                    "Title"
                     span { class: "text-red-400 ml-1", "*" }
                 }
                 div {
@@ -306,20 +528,15 @@ fn Step1ManualEntry(
                     input {
                         class: "w-full px-4 py-3 bg-gray-700 text-white rounded-lg border-2 border-gray-600 focus:border-white focus:outline-none pr-10",
                         r#type: "text",
-                        placeholder: if local_form().work_type.is_some() {
-                            "Search or enter title..."
-                        } else {
-                            "Select a type first..."
+                        placeholder: match search_scope() {
+                            Some(_) => "Search or enter title...",
+                            None => "Search movies, TV/anime and games, or paste a link...",
                         },
                         value: "{local_form().title}",
-                        disabled: local_form().work_type.is_none(),
                         onfocus: move |_| {
                             info!("Title field focused");
-                            if local_form().work_type.is_some() {
-                                search_version.set(search_version() + 1);
-                                search_query.set(local_form().title.clone());
-                                search_type.set(local_form().work_type.clone());
-                            }
+                            search_version.set(search_version() + 1);
+                            search_query.set(local_form().title.clone());
                         },
                         onblur: move |_| {
                             // Delay hiding to allow click on results
@@ -328,13 +545,10 @@ fn Step1ManualEntry(
                                 gloo_timers::future::TimeoutFuture::new(150).await;
                                 // Only hide if no new search was triggered and there's no error/status to show
                                 if search_version() == current_version {
-                                    // Don't hide if there's an error or status message to display
-                                    let should_keep_visible = matches!(
-                                        search_status(),
-                                        Some(SearchStatus::NetworkError(_))
-                                            | Some(SearchStatus::ApiError { .. })
-                                            | Some(SearchStatus::ProviderNotConfigured)
-                                    );
+                                    // Don't hide if every group failed and the error is all there is to display
+                                    let groups = search_groups();
+                                    let should_keep_visible = groups.iter().all(|g| g.results.is_empty())
+                                        && groups.iter().any(|g| g.has_error());
 
                                     if !should_keep_visible {
                                         show_results.set(false);
@@ -345,35 +559,39 @@ fn Step1ManualEntry(
                         oninput: move |e| {
                             let value = e.value();
                             local_form.with_mut(|f| {
+                                // A type autofilled from a result goes back to the user's pick
+                                if f.provider_ref.is_some() || f.existing_work_id.is_some() {
+                                    f.work_type = search_scope();
+                                }
                                 f.title = value.clone();
                                 // Clear provider data when typing
                                 f.provider_ref = None;
+                                f.original_title = None;
+                                f.existing_work_id = None;
                                 f.cover_url = None;
+                                f.backdrop_url = None;
                                 f.theme_music_url = None;
+                                f.theme_music_trim = None;
                                 f.year = String::new();
                             });
                             existing_work_error.set(false);
                             // Trigger debounced search
                             search_version.set(search_version() + 1);
                             search_query.set(value);
-                            search_type.set(local_form().work_type.clone());
                         },
                         onkeydown: move |e| {
                             if e.key() == Key::Enter {
                                 e.prevent_default();
                                 // Force search on Enter regardless of query length
-                                if local_form().work_type.is_some() {
-                                    search_version.set(search_version() + 1);
-                                    search_query.set(local_form().title.clone());
-                                    search_type.set(local_form().work_type.clone());
-                                    search_force.set(true);
-                                }
+                                search_version.set(search_version() + 1);
+                                search_query.set(local_form().title.clone());
+                                search_force.set(true);
                             }
                         }
                     }
 
                     // Loading spinner
-                    if is_searching() {
+                    if is_searching {
                         div {
                             class: "absolute right-3 top-1/2 -translate-y-1/2",
                             div {
@@ -383,113 +601,220 @@ fn Step1ManualEntry(
                     }
                 }
 
-                // Search results dropdown
+                // Search results dropdown, grouped by type
                 if show_results() {
                     div {
                         class: "absolute z-10 w-full mt-1 bg-gray-700 border-2 border-gray-600 rounded-lg shadow-lg max-h-64 overflow-y-auto",
+                        // Infinite scroll: load further pages when nearing the bottom
+                        onscroll: move |e: ScrollEvent| {
+                            let remaining = e.scroll_height() as f64 - e.scroll_top() - e.client_height() as f64;
+                            if remaining <= SEARCH_SCROLL_THRESHOLD_PX {
+                                load_next_pages();
+                            }
+                        },
 
-                        // Status messages
-                        match search_status() {
-                            Some(SearchStatus::ProviderNotConfigured) => rsx! {
+                        // Works already in the library, matched by any of their titles
+                        if !library_matches.is_empty() {
+                            div {
                                 div {
-                                    class: "px-4 py-3 text-gray-400 text-sm",
-                                    "Provider not configured. Enter title manually below."
+                                    class: "sticky top-0 px-4 py-2 bg-gray-800 text-gray-300 text-xs font-semibold uppercase tracking-wide flex items-center gap-2",
+                                    span { "📚" }
+                                    span { "In your library" }
                                 }
-                            },
-                            Some(SearchStatus::ApiError { status, .. }) => rsx! {
-                                div {
-                                    class: "px-4 py-3 text-yellow-400 text-sm",
-                                    if status == 401 {
-                                        "Invalid API key. Please check your API key in Settings (⚙️)."
-                                    } else if status == 429 {
-                                        "Rate limit exceeded. Please try again later."
-                                    } else {
-                                        "API error ({status}). Please check your API key in Settings (⚙️)."
+                                for (work, matched) in library_matches.iter() {
+                                    button {
+                                        key: "{work.id}",
+                                        class: "w-full px-4 py-3 flex items-center gap-3 hover:bg-gray-600 border-b border-gray-600 text-left",
+                                        onmousedown: {
+                                            let work_clone = work.clone();
+                                            move |e: MouseEvent| {
+                                                e.prevent_default();
+                                                select_library_work(work_clone.clone());
+                                            }
+                                        },
+                                        span { class: "text-2xl", "{work.work_type.icon()}" }
+                                        div {
+                                            class: "flex-1",
+                                            div {
+                                                class: "text-white font-medium",
+                                                "{work.title_en}"
+                                            }
+                                            if *matched != work.title_en {
+                                                div {
+                                                    class: "text-gray-400 text-sm",
+                                                    "Also known as {matched}"
+                                                }
+                                            }
+                                        }
                                     }
                                 }
-                            },
-                            Some(SearchStatus::NetworkError(msg)) => rsx! {
-                                div {
-                                    class: "px-4 py-3 text-yellow-400 text-sm",
-                                    "Network error: {msg}. You can enter the title manually."
-                                }
-                            },
-                            _ if search_results().is_empty() && !is_searching() && !local_form().title.is_empty() => rsx! {
-                                div {
-                                    class: "px-4 py-3 text-gray-400 text-sm",
+                            }
+                        }
+
+                        // Overall status when no group produced results
+                        if !has_results && !is_searching && !search_groups().iter().any(|g| g.has_error()) {
+                            div {
+                                class: "px-4 py-3 text-gray-400 text-sm",
+                                if local_form().title.is_empty() {
+                                    "Type to search or enter title manually."
+                                } else {
                                     "No results found for \"{local_form().title}\". You can enter it manually."
                                 }
-                            },
-                            _ if search_results().is_empty() && !is_searching() => rsx! {
+                            }
+                        }
+
+                        for group in search_groups() {
+                            div {
+                                key: "{group.work_type.label()}",
+
+                                // Group header
                                 div {
-                                    class: "px-4 py-3 text-gray-400 text-sm",
-                                    "Type to search or enter title manually."
+                                    class: "sticky top-0 px-4 py-2 bg-gray-800 text-gray-300 text-xs font-semibold uppercase tracking-wide flex items-center gap-2",
+                                    span { "{group.work_type.icon()}" }
+                                    span { "{group.work_type.label()}" }
+                                    if !group.results.is_empty() {
+                                        span { class: "text-gray-500", "({group.results.len()})" }
+                                    }
                                 }
-                            },
-                            _ => rsx! {}
-                        }
 
-                        // Results list
-                        for result in search_results().iter() {
-                            button {
-                                class: "w-full px-4 py-3 flex items-center gap-3 hover:bg-gray-600 border-b border-gray-600 last:border-b-0 text-left",
-                                onmousedown: {
-                                    let result_clone = result.clone();
-                                    move |e: MouseEvent| {
-                                        e.prevent_default();
-                                        info!("Result selected: {}", result_clone.title);
-
-                                        if check_existing_work(&result_clone.provider_ref) {
-                                            info!("Work already has a mnemon - showing error");
-                                            existing_work_error.set(true);
-                                            show_results.set(false);
+                                // Per-group status messages
+                                match group.status.clone() {
+                                    None => rsx! {
+                                        div {
+                                            class: "px-4 py-3 text-gray-400 text-sm",
+                                            "Searching..."
+                                        }
+                                    },
+                                    Some(SearchStatus::ProviderNotConfigured) => rsx! {
+                                        div {
+                                            class: "px-4 py-3 text-gray-400 text-sm",
+                                            "Provider not configured. Enter title manually below."
+                                        }
+                                    },
+                                    Some(SearchStatus::ApiError { status, .. }) => rsx! {
+                                        div {
+                                            class: "px-4 py-3 text-yellow-400 text-sm",
+                                            if status == 401 {
+                                                "Invalid API key. Please check your API key in Settings (⚙️)."
+                                            } else if status == 429 {
+                                                "Rate limit exceeded. Please try again later."
+                                            } else {
+                                                "API error ({status}). Please check your API key in Settings (⚙️)."
+                                            }
+                                        }
+                                    },
+                                    Some(SearchStatus::RateLimited { retry_after_secs }) => rsx! {
+                                        div {
+                                            class: "px-4 py-3 text-yellow-400 text-sm",
+                                            "Rate limit exceeded. Try again in {retry_after_secs}s or enter the title manually."
+                                        }
+                                    },
+                                    Some(SearchStatus::StaleCache { cached_at, .. }) => rsx! {
+                                        div {
+                                            class: "px-4 py-3 text-yellow-400 text-sm",
+                                            "Provider unreachable: showing results cached on {cached_at.format(\"%Y-%m-%d\")}."
+                                        }
+                                    },
+                                    Some(SearchStatus::NetworkError(msg)) => rsx! {
+                                        div {
+                                            class: "px-4 py-3 text-yellow-400 text-sm",
+                                            "Network error: {msg}. You can enter the title manually."
+                                        }
+                                    },
+                                    Some(_) if group.results.is_empty() && has_results => rsx! {
+                                        div {
+                                            class: "px-4 py-3 text-gray-500 text-sm",
+                                            "No matches"
+                                        }
+                                    },
+                                    Some(_) => rsx! {}
+                                }
+
+                                // Results list
+                                for result in group.results.iter() {
+                                    button {
+                                        class: "w-full px-4 py-3 flex items-center gap-3 hover:bg-gray-600 border-b border-gray-600 last:border-b-0 text-left",
+                                        onmousedown: {
+                                            let result_clone = result.clone();
+                                            move |e: MouseEvent| {
+                                                e.prevent_default();
+                                                select_result(result_clone.clone());
+                                            }
+                                        },
+
+                                        // Cover thumbnail
+                                        if let Some(ref cover) = result.cover_url {
+                                            img {
+                                                class: "w-12 h-16 object-cover rounded",
+                                                src: sized_image_url(cover, ImageKind::Poster, ImageSize::Thumbnail),
+                                                alt: "{result.title}"
+                                            }
                                         } else {
-                                            info!("Autofilling form with result");
-                                            local_form.with_mut(|f| {
-                                                f.title = result_clone.title.clone();
-                                                f.year = result_clone.year.map(|y| y.to_string()).unwrap_or_default();
-                                                f.provider_ref = Some(result_clone.provider_ref.clone());
-                                                f.cover_url = result_clone.cover_url.clone();
-                                                f.theme_music_url = result_clone.theme_music_url.clone();
-                                            });
-                                            existing_work_error.set(false);
-                                            show_results.set(false);
+                                            div {
+                                                class: "w-12 h-16 bg-gray-800 rounded flex items-center justify-center",
+                                                span { class: "text-2xl", "{result.work_type.icon()}" }
+                                            }
+                                        }
+
+                                        div {
+                                            class: "flex-1",
+                                            div {
+                                                class: "text-white font-medium",
+                                                "{result.title}"
+                                            }
+                                            if let Some(ref original) = result.original_title {
+                                                div {
+                                                    class: "text-gray-400 text-sm",
+                                                    "{original}"
+                                                }
+                                            }
+                                            if let Some(year) = result.year {
+                                                div {
+                                                    class: "text-gray-400 text-sm",
+                                                    "{year}"
+                                                }
+                                            }
                                         }
-                                    }
-                                },
-
-                                // Cover thumbnail
-                                if let Some(ref cover) = result.cover_url {
-                                    img {
-                                        class: "w-12 h-16 object-cover rounded",
-                                        src: "{cover}",
-                                        alt: "{result.title}"
-                                    }
-                                } else {
-                                    div {
-                                        class: "w-12 h-16 bg-gray-800 rounded flex items-center justify-center",
-                                        span { class: "text-2xl", "{result.work_type.icon()}" }
                                     }
                                 }
 
-                                div {
-                                    class: "flex-1",
+                                if group.loading_more {
                                     div {
-                                        class: "text-white font-medium",
-                                        "{result.title}"
-                                    }
-                                    if let Some(year) = result.year {
-                                        div {
-                                            class: "text-gray-400 text-sm",
-                                            "{year}"
-                                        }
+                                        class: "px-4 py-3 text-gray-400 text-sm",
+                                        "Loading more..."
                                     }
                                 }
                             }
                         }
+
+                        // A list too short to scroll never triggers the infinite scroll
+                        if search_groups.read().iter().any(|g| g.can_load_more()) {
+                            button {
+                                class: "w-full px-4 py-3 text-left text-gray-300 hover:bg-gray-600 text-sm transition-colors",
+                                onclick: move |_| load_more_pages(),
+                                "Load more results"
+                            }
+                        }
                     }
                 }
 
+                // Pasted link lookup status
+                match link_lookup() {
+                    Some(LinkLookup::Resolving(provider)) => rsx! {
+                        div {
+                            class: "mt-2 px-3 py-2 text-gray-400 text-sm",
+                            "Looking up {provider} link..."
+                        }
+                    },
+                    Some(LinkLookup::Failed(message)) => rsx! {
+                        div {
+                            class: "mt-2 px-3 py-2 bg-yellow-900/30 border border-yellow-700/50 rounded text-yellow-200 text-sm",
+                            "⚠️ {message}. You can search by title instead."
+                        }
+                    },
+                    None => rsx! {}
+                }
+
                 // Existing work error message
                 if existing_work_error() {
                     div {
@@ -498,8 +823,14 @@ fn Step1ManualEntry(
                     }
                 }
 
-                // Manual entry hint when provider selected result
-                if local_form().provider_ref.is_some() {
+                // Hint when the mnemon will be attached to a library work
+                if local_form().existing_work_id.is_some() {
+                    div {
+                        class: "mt-2 px-3 py-2 bg-green-900/30 border border-green-700/50 rounded text-green-200 text-sm flex items-center gap-2",
+                        span { "✓" }
+                        span { "Selected from your library" }
+                    }
+                } else if local_form().provider_ref.is_some() {
                     div {
                         class: "mt-2 px-3 py-2 bg-green-900/30 border border-green-700/50 rounded text-green-200 text-sm flex items-center gap-2",
                         span { "✓" }
@@ -567,7 +898,7 @@ fn Step2Personalize(
                 }
                 p {
                     class: "text-gray-400",
-                    "Step 2: Optional dates"
+                    "Step 2: Optional dates, cover and music"
                 }
             }
 
@@ -583,7 +914,7 @@ fn Step2Personalize(
                     r#type: "text",
                     placeholder: "YYYY",
                     value: "{local_form().year}",
-                    readonly: local_form().provider_ref.is_some(),
+                    readonly: local_form().provider_ref.is_some() || local_form().existing_work_id.is_some(),
                     maxlength: 4,
                     oninput: move |e| {
                         let value = e.value();
@@ -593,7 +924,12 @@ fn Step2Personalize(
                         }
                     }
                 }
-                if local_form().provider_ref.is_some() {
+                if local_form().existing_work_id.is_some() {
+                    p {
+                        class: "text-gray-500 text-xs mt-1",
+                        "From the work in your library"
+                    }
+                } else if local_form().provider_ref.is_some() {
                     p {
                         class: "text-gray-500 text-xs mt-1",
                         "Autofilled from search result"
@@ -604,6 +940,12 @@ fn Step2Personalize(
             // Finished date
             FinishedDateInput { form: local_form }
 
+            // Custom cover
+            CoverUpload { form: local_form }
+
+            // Theme music from a local file
+            ThemeMusicUpload { form: local_form }
+
             // Actions
             div {
                 class: "flex gap-3 justify-between",
@@ -0,0 +1,120 @@
+//! "On this day" anniversaries list
+
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use crate::app_state::AppState;
+use crate::data::anniversaries::{anniversaries, AnniversaryMatch};
+use crate::providers::images::{sized_image_url, ImageKind, ImageSize};
+use crate::utils::today;
+
+/// Memories finished around this date in earlier years, grouped by how close the
+/// date is; picking one shows it in the hero
+#[component]
+pub fn AnniversariesModal(on_select: EventHandler<Uuid>, on_close: EventHandler<()>) -> Element {
+    let app_state = use_context::<Signal<AppState>>();
+
+    let all = anniversaries(&app_state.read().mnemons.read(), today());
+    let works = app_state.read().works.read().clone();
+    let mnemons = app_state.read().mnemons.read().clone();
+    let groups: Vec<(AnniversaryMatch, Vec<_>)> = [
+        AnniversaryMatch::Day,
+        AnniversaryMatch::Week,
+        AnniversaryMatch::Month,
+    ]
+    .into_iter()
+    .map(|matched| {
+        let entries = all
+            .iter()
+            .filter(|a| a.matched == matched)
+            .filter_map(|a| {
+                let mnemon = mnemons.iter().find(|m| m.id == a.mnemon_id)?;
+                let work = works.iter().find(|w| w.id == mnemon.work_id)?;
+                let ago = match a.years_ago {
+                    1 => "1 year ago".to_string(),
+                    years => format!("{} years ago", years),
+                };
+                let detail = format!("Finished {} · {}", a.finished_on.format("%-d %B %Y"), ago);
+                Some((a.mnemon_id, work.clone(), detail))
+            })
+            .collect::<Vec<_>>();
+        (matched, entries)
+    })
+    .filter(|(_, entries)| !entries.is_empty())
+    .collect();
+
+    rsx! {
+        div {
+            class: "fixed inset-0 z-50 flex items-center justify-center bg-black/80 backdrop-blur-sm",
+            onclick: move |_| on_close.call(()),
+
+            div {
+                class: "bg-gray-800 rounded-lg shadow-2xl w-full max-w-lg mx-2 sm:mx-4 md:mx-auto max-h-[90vh] overflow-y-auto",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "px-6 py-4 border-b border-gray-700 flex items-center justify-between",
+                    h2 {
+                        class: "text-xl font-semibold text-white",
+                        "On this day"
+                    }
+                    button {
+                        class: "text-gray-400 hover:text-white transition-colors",
+                        onclick: move |_| on_close.call(()),
+                        "✕"
+                    }
+                }
+
+                div {
+                    class: "px-6 py-4 space-y-6",
+
+                    if groups.is_empty() {
+                        p {
+                            class: "text-gray-400 text-sm",
+                            "No anniversaries around today. Memories with a finished date show up here when the date comes round again."
+                        }
+                    }
+
+                    for (matched, entries) in groups {
+                        div {
+                            key: "{matched.label()}",
+                            h3 {
+                                class: "text-white/60 text-xs font-semibold uppercase tracking-wide mb-2",
+                                "{matched.label()}"
+                            }
+                            div {
+                                class: "space-y-2",
+                                for (mnemon_id, work, detail) in entries {
+                                    button {
+                                        key: "{mnemon_id}",
+                                        class: "w-full flex items-center gap-3 p-2 rounded-lg bg-gray-700/50 hover:bg-gray-700 text-left transition-colors",
+                                        onclick: move |_| on_select.call(mnemon_id),
+
+                                        if let Some(src) = work.covers().next().and_then(|uri| app_state.read().media_src(uri)) {
+                                            img {
+                                                class: "w-10 h-14 rounded object-cover flex-shrink-0",
+                                                src: sized_image_url(&src, ImageKind::Poster, ImageSize::Thumbnail),
+                                                alt: "",
+                                            }
+                                        } else {
+                                            div {
+                                                class: "w-10 h-14 rounded bg-gray-600 flex items-center justify-center flex-shrink-0",
+                                                "{work.work_type.icon()}"
+                                            }
+                                        }
+
+                                        div {
+                                            class: "min-w-0",
+                                            p { class: "text-white text-sm font-semibold truncate", "{work.title_en}" }
+                                            p { class: "text-gray-400 text-xs", "{detail}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
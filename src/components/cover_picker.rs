@@ -0,0 +1,189 @@
+use dioxus::prelude::*;
+
+use crate::app_state::AppState;
+use crate::data::SearchService;
+use crate::forms::MnemonForm;
+use crate::models::ProviderImage;
+use crate::providers::images::{sized_image_url, ImageKind, ImageSize};
+
+#[derive(Clone, PartialEq)]
+enum ImagesState {
+    Idle,
+    Loading,
+    Loaded(Vec<ProviderImage>),
+    Failed(String),
+}
+
+fn image_kind(image: &ProviderImage) -> ImageKind {
+    if image.is_landscape() {
+        ImageKind::Backdrop
+    } else {
+        ImageKind::Poster
+    }
+}
+
+/// Move a picked cover one place towards the front (`-1`) or the back (`1`)
+fn move_cover(covers: &mut [String], index: usize, offset: isize) {
+    let Some(target) = index.checked_add_signed(offset) else {
+        return;
+    };
+    if target < covers.len() {
+        covers.swap(index, target);
+    }
+}
+
+/// Cover picker for provider-backed works
+///
+/// Lists the provider's alternative images (TMDB posters and backdrops, RAWG
+/// screenshots). The picked covers are kept in order: the first is the cover, the
+/// others are shown when it fails to load.
+#[component]
+pub fn CoverPicker(form: Signal<MnemonForm>) -> Element {
+    let app_state = use_context::<Signal<AppState>>();
+    let mut state = use_signal(|| ImagesState::Idle);
+
+    let Some(provider_ref) = form().provider_ref else {
+        return rsx! {};
+    };
+    let Some(work_type) = form().work_type else {
+        return rsx! {};
+    };
+
+    let load_images = move |_| {
+        let provider_ref = provider_ref.clone();
+        let work_type = work_type.clone();
+        state.set(ImagesState::Loading);
+        spawn(async move {
+            match SearchService::new().images(&provider_ref, work_type).await {
+                Ok(images) => state.set(ImagesState::Loaded(images)),
+                Err(e) => state.set(ImagesState::Failed(e.to_string())),
+            }
+        });
+    };
+
+    let mut update_covers = move |change: &dyn Fn(&mut Vec<String>)| {
+        form.with_mut(|f| {
+            let mut covers = f.covers();
+            change(&mut covers);
+            f.set_covers(covers);
+        });
+    };
+
+    let covers = form().covers();
+    let cover_count = covers.len();
+
+    rsx! {
+        div {
+            class: "mb-6",
+            label {
+                class: "block text-white text-sm font-semibold mb-2",
+                "Cover"
+            }
+
+            if !covers.is_empty() {
+                div {
+                    class: "flex gap-2 overflow-x-auto pb-2 mb-2",
+                    for (index, url) in covers.into_iter().enumerate() {
+                        div {
+                            key: "{url}",
+                            class: if index == 0 {
+                                "flex-shrink-0 w-24 rounded border-2 border-blue-500 overflow-hidden bg-gray-700"
+                            } else {
+                                "flex-shrink-0 w-24 rounded border-2 border-gray-600 overflow-hidden bg-gray-700"
+                            },
+                            if let Some(src) = app_state.read().media_src(&url) {
+                                img {
+                                    class: "w-full h-28 object-cover",
+                                    src: sized_image_url(&src, ImageKind::Poster, ImageSize::Thumbnail),
+                                    alt: "",
+                                }
+                            }
+                            div {
+                                class: "flex justify-between px-1 text-xs text-gray-300",
+                                button {
+                                    class: "hover:text-white disabled:opacity-30",
+                                    disabled: index == 0,
+                                    title: "Move earlier",
+                                    onclick: move |_| update_covers(&|c| move_cover(c, index, -1)),
+                                    "◀"
+                                }
+                                button {
+                                    class: "hover:text-white",
+                                    title: "Remove",
+                                    onclick: move |_| update_covers(&|c| {
+                                        c.remove(index);
+                                    }),
+                                    "✕"
+                                }
+                                button {
+                                    class: "hover:text-white disabled:opacity-30",
+                                    disabled: index + 1 == cover_count,
+                                    title: "Move later",
+                                    onclick: move |_| update_covers(&|c| move_cover(c, index, 1)),
+                                    "▶"
+                                }
+                            }
+                        }
+                    }
+                }
+                p {
+                    class: "text-gray-500 text-xs mb-2",
+                    "The first image is the cover; the others are used if it can't be loaded."
+                }
+            }
+
+            match state() {
+                ImagesState::Idle => rsx! {
+                    button {
+                        class: "px-4 py-2 bg-gray-700 hover:bg-gray-600 text-white rounded-lg text-sm transition-colors",
+                        onclick: load_images,
+                        "Choose another image"
+                    }
+                },
+                ImagesState::Loading => rsx! {
+                    p { class: "text-gray-400 text-sm", "Loading images..." }
+                },
+                ImagesState::Failed(message) => rsx! {
+                    p { class: "text-red-400 text-sm", "Could not load images: {message}" }
+                },
+                ImagesState::Loaded(images) if images.is_empty() => rsx! {
+                    p { class: "text-gray-400 text-sm", "No other images available." }
+                },
+                ImagesState::Loaded(images) => rsx! {
+                    div {
+                        class: "grid grid-cols-3 sm:grid-cols-4 gap-2 max-h-72 overflow-y-auto",
+                        for image in images.into_iter() {
+                            {
+                                let picked = form().covers().contains(&image.url);
+                                let url = image.url.clone();
+                                rsx! {
+                                    button {
+                                        key: "{image.url}",
+                                        class: if picked {
+                                            "rounded overflow-hidden border-2 border-blue-500"
+                                        } else {
+                                            "rounded overflow-hidden border-2 border-transparent hover:border-gray-400"
+                                        },
+                                        title: "Use as cover",
+                                        onclick: move |_| {
+                                            let url = url.clone();
+                                            update_covers(&move |c| {
+                                                c.retain(|u| *u != url);
+                                                c.insert(0, url.clone());
+                                            });
+                                        },
+                                        img {
+                                            class: "w-full h-24 object-cover",
+                                            src: sized_image_url(&image.url, image_kind(&image), ImageSize::Thumbnail),
+                                            alt: "",
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use dioxus::html::FileData;
+use dioxus::prelude::*;
+
+use crate::app_state::AppState;
+use crate::forms::MnemonForm;
+use crate::uploads::prepare_cover;
+
+#[derive(Clone, PartialEq)]
+enum UploadState {
+    Idle,
+    Processing,
+    Failed(String),
+}
+
+/// Drop zone and file picker for a cover of the user's own
+///
+/// The image is cropped and downscaled, stored as an asset, and becomes the cover;
+/// any previous cover is kept as a fallback.
+#[component]
+pub fn CoverUpload(form: Signal<MnemonForm>) -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+    let mut state = use_signal(|| UploadState::Idle);
+    let mut dragging = use_signal(|| false);
+
+    let mut upload = move |files: Vec<FileData>| {
+        let Some(file) = files.into_iter().next() else {
+            return;
+        };
+        state.set(UploadState::Processing);
+        spawn(async move {
+            match prepare_cover(&file).await {
+                Ok(asset) => {
+                    let uri = app_state.write().add_asset(asset);
+                    form.with_mut(|f| {
+                        let mut covers = f.covers();
+                        covers.insert(0, uri);
+                        f.set_covers(covers);
+                    });
+                    state.set(UploadState::Idle);
+                }
+                Err(e) => state.set(UploadState::Failed(e.to_string())),
+            }
+        });
+    };
+
+    let uploaded_preview = form()
+        .cover_url
+        .filter(|_| form().has_uploaded_cover())
+        .and_then(|uri| app_state.read().media_src(&uri));
+
+    rsx! {
+        div {
+            class: "mb-6",
+            label {
+                class: "block text-white text-sm font-semibold mb-2",
+                "Your own cover"
+            }
+
+            div {
+                class: if dragging() {
+                    "relative flex items-center gap-4 p-4 rounded-lg border-2 border-dashed border-white bg-white/10"
+                } else {
+                    "relative flex items-center gap-4 p-4 rounded-lg border-2 border-dashed border-gray-600"
+                },
+                ondragover: move |e| {
+                    e.prevent_default();
+                    dragging.set(true);
+                },
+                ondragleave: move |_| dragging.set(false),
+                ondrop: move |e| {
+                    e.prevent_default();
+                    dragging.set(false);
+                    upload(e.data_transfer().files());
+                },
+
+                if let Some(src) = uploaded_preview {
+                    img {
+                        class: "w-32 aspect-video object-cover rounded",
+                        src: "{src}",
+                        alt: "",
+                    }
+                }
+
+                div {
+                    class: "flex-1 text-sm text-gray-400",
+                    match state() {
+                        UploadState::Idle => rsx! {
+                            p { "Drop an image here, or" }
+                            label {
+                                class: "inline-block mt-1 px-3 py-1 bg-gray-700 hover:bg-gray-600 text-white rounded cursor-pointer transition-colors",
+                                "Choose a file"
+                                input {
+                                    class: "hidden",
+                                    r#type: "file",
+                                    accept: "image/*",
+                                    onchange: move |e| upload(e.files()),
+                                }
+                            }
+                        },
+                        UploadState::Processing => rsx! {
+                            p { "Preparing image..." }
+                        },
+                        UploadState::Failed(message) => rsx! {
+                            p { class: "text-red-400", "{message}" }
+                            button {
+                                class: "mt-1 text-gray-300 hover:text-white",
+                                onclick: move |_| state.set(UploadState::Idle),
+                                "Try another file"
+                            }
+                        },
+                    }
+                }
+
+                if form().has_uploaded_cover() {
+                    button {
+                        class: "text-gray-400 hover:text-white transition-colors",
+                        title: "Remove this cover",
+                        onclick: move |_| {
+                            form.with_mut(|f| {
+                                let covers = f.covers().into_iter().skip(1).collect();
+                                f.set_covers(covers);
+                            });
+                        },
+                        "✕"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,8 +1,40 @@
 use dioxus::prelude::*;
 use uuid::Uuid;
 
-use crate::app_state::MnemonWithWork;
+use crate::app_state::{AppState, MnemonWithWork};
+use crate::audio::{segment_bounds, AudioController, PlaybackStatus, ThemeTrack};
 use crate::components::EditIcon;
+use crate::data::query::{rank_results, ParsedQuery};
+use crate::data::refresh::{self, apply_details, diff_work, FieldChange};
+use crate::data::SearchService;
+use crate::models::{SearchResult, WorkDetails, WorkField, WorkType};
+use crate::providers::images::{sized_image_url, ImageKind, ImageSize};
+use crate::radio::Radio;
+use crate::utils::format_timestamp;
+
+/// State of a manual metadata refresh
+#[derive(Clone, PartialEq, Debug)]
+enum RefreshState {
+    Idle,
+    Loading,
+    /// Changes found; the user picks which to apply
+    Review {
+        details: Box<WorkDetails>,
+        changes: Vec<FieldChange>,
+    },
+    UpToDate,
+    Failed(String),
+}
+
+/// State of the "Find online match" lookup for a manual work
+#[derive(Clone, PartialEq, Debug)]
+enum MatchState {
+    Idle,
+    Searching,
+    /// Candidates ranked by title and year; the user picks one
+    Results(Vec<SearchResult>),
+    Failed(String),
+}
 
 #[component]
 pub fn MemoryDetails(
@@ -13,8 +45,143 @@ pub fn MemoryDetails(
     let work = &mnemon_with_work.work;
     let mnemon = &mnemon_with_work.mnemon;
     let mnemon_id = mnemon.id;
+    let is_favorite = mnemon.favorite;
+
+    let mut app_state = use_context::<Signal<AppState>>();
+    let mut audio = use_context::<AudioController>();
+    let theme_track = ThemeTrack::for_work(work, &app_state.read());
+
+    // Start the theme when the details open (browsers may hold it until a tap),
+    // unless the radio is playing
+    let radio = use_context::<Radio>();
+    let autoplay_track = theme_track.clone();
+    use_hook(move || {
+        if *radio.active.peek() {
+            return;
+        }
+        if let Some(track) = autoplay_track {
+            if audio.track.peek().as_ref().map(|t| t.work_id) != Some(track.work_id) {
+                audio.play(track);
+            }
+        }
+    });
 
-    let mut is_playing = use_signal(|| false);
+    // Label/value rows for the metadata summary
+    let creators_label = match work.work_type {
+        WorkType::Movie => "Directed by",
+        WorkType::TvAnime => "Created by",
+        WorkType::Game => "Developed by",
+    };
+    let facts: Vec<(&str, String)> = [
+        (creators_label, work.metadata.creators.join(", ")),
+        ("Studio", work.metadata.studios.join(", ")),
+        (
+            "Released",
+            work.release_year.map(|y| y.to_string()).unwrap_or_default(),
+        ),
+        ("Length", work.metadata.length_label().unwrap_or_default()),
+        ("Platforms", work.metadata.platforms.join(", ")),
+    ]
+    .into_iter()
+    .filter(|(_, value)| !value.is_empty())
+    .collect();
+
+    let mut refresh_state = use_signal(|| RefreshState::Idle);
+    // Fields ticked for update in the review panel
+    let mut accepted_fields = use_signal(Vec::<WorkField>::new);
+
+    let can_refresh = work.provider_ref.is_some();
+    let refresh_work = work.clone();
+    let refresh_metadata = move |_| {
+        let work = refresh_work.clone();
+        let Some(provider_ref) = work.provider_ref.clone() else {
+            return;
+        };
+        refresh_state.set(RefreshState::Loading);
+        spawn(async move {
+            let service = SearchService::new();
+            match service.details(&provider_ref, work.work_type.clone()).await {
+                Ok(details) => {
+                    let changes = diff_work(&work, &details);
+                    if changes.is_empty() {
+                        let mut updated = work.clone();
+                        apply_details(&mut updated, &details, &[], chrono::Utc::now());
+                        app_state.write().update_work(updated);
+                        refresh_state.set(RefreshState::UpToDate);
+                    } else {
+                        accepted_fields.set(changes.iter().map(|c| c.field).collect());
+                        refresh_state.set(RefreshState::Review {
+                            details: Box::new(details),
+                            changes,
+                        });
+                    }
+                }
+                Err(e) => refresh_state.set(RefreshState::Failed(e.to_string())),
+            }
+        });
+    };
+
+    let mut match_state = use_signal(|| MatchState::Idle);
+
+    // Manual works can be linked to a provider entry after the fact
+    let can_match = work.provider_ref.is_none();
+    let match_work = work.clone();
+    let find_match = move |_| {
+        let work = match_work.clone();
+        match_state.set(MatchState::Searching);
+        spawn(async move {
+            let response = SearchService::new()
+                .search(&work.title_en, work.work_type.clone(), 0)
+                .await;
+            if let Some(problem) = response.status.problem() {
+                if response.results.is_empty() {
+                    match_state.set(MatchState::Failed(problem));
+                    return;
+                }
+            }
+            // The stored year ranks candidates without filtering them out
+            let query = ParsedQuery {
+                text: work.title_en.clone(),
+                year: work.release_year,
+                ..Default::default()
+            };
+            match_state.set(MatchState::Results(rank_results(response.results, &query)));
+        });
+    };
+
+    let match_work_id = work.id;
+    let mut pick_match = move |result: SearchResult| {
+        let linked = app_state
+            .write()
+            .link_work_to_provider(match_work_id, &result);
+        match_state.set(MatchState::Idle);
+        let Some(linked) = linked else {
+            return;
+        };
+        // Fill in descriptive metadata for the newly linked work
+        spawn(async move {
+            match refresh::refresh_work(&SearchService::new(), linked).await {
+                Ok(refresh) => app_state.write().update_work(refresh.work),
+                Err(e) => tracing::info!("Could not fetch details for linked work: {}", e),
+            }
+        });
+    };
+
+    let apply_work = work.clone();
+    let apply_refresh = move |_| {
+        let RefreshState::Review { details, changes } = refresh_state() else {
+            return;
+        };
+        let accepted = accepted_fields();
+        let mut updated = apply_work.clone();
+        apply_details(&mut updated, &details, &accepted, chrono::Utc::now());
+        // Unticked changes are kept as the user's own values from now on
+        for change in changes.iter().filter(|c| !accepted.contains(&c.field)) {
+            updated.set_overridden(change.field);
+        }
+        app_state.write().update_work(updated);
+        refresh_state.set(RefreshState::Idle);
+    };
 
     rsx! {
         div {
@@ -28,43 +195,327 @@ pub fn MemoryDetails(
                     "{work.title_en}"
                 }
 
-                button {
-                    class: "px-4 py-2 bg-white/10 hover:bg-white/20 text-white rounded-lg transition-colors flex items-center gap-2",
-                    onclick: move |_| on_edit.call(mnemon_id),
+                div {
+                    class: "flex items-center gap-2",
+
+                    if can_match {
+                        button {
+                            class: "px-4 py-2 bg-white/10 hover:bg-white/20 text-white rounded-lg transition-colors disabled:opacity-50",
+                            title: "Search the providers for this work",
+                            disabled: match_state() == MatchState::Searching,
+                            onclick: find_match,
+                            if match_state() == MatchState::Searching { "Searching..." } else { "🔍 Find online match" }
+                        }
+                    }
+
+                    if can_refresh {
+                        button {
+                            class: "px-4 py-2 bg-white/10 hover:bg-white/20 text-white rounded-lg transition-colors disabled:opacity-50",
+                            title: "Refresh metadata from the provider",
+                            disabled: refresh_state() == RefreshState::Loading,
+                            onclick: refresh_metadata,
+                            if refresh_state() == RefreshState::Loading { "Refreshing..." } else { "↻ Refresh" }
+                        }
+                    }
+
+                    button {
+                        class: "px-4 py-2 bg-white/10 hover:bg-white/20 text-white rounded-lg transition-colors",
+                        title: if is_favorite { "Remove from favorites" } else { "Favorite: show this memory more often" },
+                        "aria-pressed": if is_favorite { "true" } else { "false" },
+                        onclick: move |_| app_state.write().set_mnemon_favorite(mnemon_id, !is_favorite),
+                        if is_favorite { "★" } else { "☆" }
+                    }
+
+                    button {
+                        class: "px-4 py-2 bg-white/10 hover:bg-white/20 text-white rounded-lg transition-colors flex items-center gap-2",
+                        onclick: move |_| on_edit.call(mnemon_id),
 
-                    EditIcon {}
-                    span { "Edit" }
+                        EditIcon {}
+                        span { "Edit" }
+                    }
                 }
             }
 
-            if work.theme_music_local_uri.is_some() {
-                div {
-                    class: "mb-6 flex items-center gap-4",
+            match match_state() {
+                MatchState::Results(results) => rsx! {
+                    div {
+                        class: "mb-6 p-4 bg-white/5 border border-white/20 rounded-lg",
 
-                    button {
-                        class: "w-14 h-14 rounded-full bg-white/10 hover:bg-white/20 flex items-center justify-center transition-colors",
-                        onclick: move |_| is_playing.toggle(),
+                        h3 {
+                            class: "text-sm text-white/50 uppercase tracking-wide mb-3",
+                            "Pick the matching entry"
+                        }
 
-                        span {
-                            class: "text-2xl text-white",
-                            if is_playing() { "⏸" } else { "▶" }
+                        if results.is_empty() {
+                            p {
+                                class: "text-sm text-white/60 mb-3",
+                                "No matches found for \"{work.title_en}\"."
+                            }
+                        }
+
+                        div {
+                            class: "space-y-2 mb-4 max-h-80 overflow-y-auto",
+                            for result in results {
+                                button {
+                                    key: "{result.provider_ref.provider_source}-{result.provider_ref.provider_id}",
+                                    class: "w-full p-2 flex items-center gap-3 hover:bg-white/10 rounded-lg text-left transition-colors",
+                                    onclick: {
+                                        let result = result.clone();
+                                        move |_| pick_match(result.clone())
+                                    },
+                                    if let Some(ref cover) = result.cover_url {
+                                        img {
+                                            class: "w-10 h-14 object-cover rounded",
+                                            src: sized_image_url(cover, ImageKind::Poster, ImageSize::Thumbnail),
+                                        }
+                                    } else {
+                                        div {
+                                            class: "w-10 h-14 bg-white/5 rounded flex items-center justify-center",
+                                            "{result.work_type.icon()}"
+                                        }
+                                    }
+                                    div {
+                                        class: "flex-1",
+                                        div { class: "text-white", "{result.title}" }
+                                        if let Some(year) = result.year {
+                                            div { class: "text-sm text-white/50", "{year}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        p {
+                            class: "text-xs text-white/40 mb-3",
+                            "This memory moves to the picked entry. If it's already in your library, the two are merged."
+                        }
+
+                        div {
+                            class: "flex justify-end",
+                            button {
+                                class: "px-4 py-2 text-white/60 hover:text-white transition-colors",
+                                onclick: move |_| match_state.set(MatchState::Idle),
+                                "Cancel"
+                            }
                         }
                     }
+                },
+                MatchState::Failed(message) => rsx! {
+                    p { class: "mb-6 text-sm text-red-400", "Could not search for a match: {message}" }
+                },
+                MatchState::Idle | MatchState::Searching => rsx! {},
+            }
 
+            match refresh_state() {
+                RefreshState::Review { changes, .. } => rsx! {
                     div {
-                        class: "flex-1",
+                        class: "mb-6 p-4 bg-white/5 border border-white/20 rounded-lg",
+
+                        h3 {
+                            class: "text-sm text-white/50 uppercase tracking-wide mb-3",
+                            "Provider metadata changed"
+                        }
 
                         div {
-                            class: "h-1 bg-white/20 rounded-full overflow-hidden",
-                            div {
-                                class: "h-full bg-white/60 rounded-full",
-                                style: "width: 0%;",
+                            class: "space-y-2 mb-4",
+                            for change in changes.iter() {
+                                {
+                                    let field = change.field;
+                                    let checked = accepted_fields().contains(&field);
+                                    rsx! {
+                                        label {
+                                            key: "{field.label()}",
+                                            class: "flex items-start gap-3 text-white/80 cursor-pointer",
+                                            input {
+                                                r#type: "checkbox",
+                                                class: "mt-1",
+                                                checked: checked,
+                                                onchange: move |_| {
+                                                    accepted_fields.with_mut(|fields| {
+                                                        if let Some(pos) = fields.iter().position(|f| *f == field) {
+                                                            fields.remove(pos);
+                                                        } else {
+                                                            fields.push(field);
+                                                        }
+                                                    });
+                                                },
+                                            }
+                                            div {
+                                                class: "flex-1",
+                                                span { class: "font-medium", "{field.label()}: " }
+                                                if field == WorkField::Cover {
+                                                    div {
+                                                        class: "flex items-center gap-2 mt-1",
+                                                        if let Some(ref old) = change.old {
+                                                            img {
+                                                                class: "h-16 rounded opacity-60",
+                                                                src: sized_image_url(old, ImageKind::Poster, ImageSize::Thumbnail),
+                                                            }
+                                                        }
+                                                        span { "→" }
+                                                        if let Some(ref new) = change.new {
+                                                            img {
+                                                                class: "h-16 rounded",
+                                                                src: sized_image_url(new, ImageKind::Poster, ImageSize::Thumbnail),
+                                                            }
+                                                        }
+                                                    }
+                                                } else {
+                                                    span { class: "line-through text-white/40", "{change.old_label()}" }
+                                                    span { " → {change.new_label()}" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
                             }
                         }
 
                         p {
-                            class: "text-sm text-white/40 mt-1",
-                            "Theme music"
+                            class: "text-xs text-white/40 mb-3",
+                            "Unticked fields keep your current value and won't be refreshed again."
+                        }
+
+                        div {
+                            class: "flex justify-end gap-2",
+                            button {
+                                class: "px-4 py-2 text-white/60 hover:text-white transition-colors",
+                                onclick: move |_| refresh_state.set(RefreshState::Idle),
+                                "Cancel"
+                            }
+                            button {
+                                class: "px-4 py-2 bg-transparent border-2 border-white hover:bg-white/10 text-white rounded-lg font-medium transition-colors",
+                                onclick: apply_refresh,
+                                "Apply"
+                            }
+                        }
+                    }
+                },
+                RefreshState::UpToDate => rsx! {
+                    p { class: "mb-6 text-sm text-green-400", "Metadata is up to date." }
+                },
+                RefreshState::Failed(message) => rsx! {
+                    p { class: "mb-6 text-sm text-red-400", "Could not refresh metadata: {message}" }
+                },
+                RefreshState::Idle | RefreshState::Loading => rsx! {},
+            }
+
+            // Provider metadata
+            if let Some(ref synopsis) = work.metadata.synopsis {
+                p {
+                    class: "mb-6 text-white/70 leading-relaxed",
+                    "{synopsis}"
+                }
+            }
+
+            if !facts.is_empty() {
+                dl {
+                    class: "mb-6 grid grid-cols-[auto_1fr] gap-x-4 gap-y-1 text-sm",
+                    for (label, value) in facts {
+                        dt { class: "text-white/50", "{label}" }
+                        dd { class: "text-white/80", "{value}" }
+                    }
+                }
+            }
+
+            if !work.metadata.genres.is_empty() {
+                div {
+                    class: "mb-6 flex flex-wrap gap-2",
+                    for genre in work.metadata.genres.iter() {
+                        span {
+                            class: "px-3 py-1 bg-white/5 text-white/60 text-sm rounded-full border border-white/10",
+                            "{genre}"
+                        }
+                    }
+                }
+            }
+
+            if let Some(track) = theme_track {
+                {
+                    let is_current = audio.is_current(track.work_id);
+                    let is_playing = audio.is_playing(track.work_id);
+                    let progress = if is_current { audio.progress() } else { 0.0 };
+                    let status_label = match (*audio.status.read()).clone() {
+                        _ if !is_current => "Theme music".to_string(),
+                        PlaybackStatus::Loading => "Loading...".to_string(),
+                        PlaybackStatus::Blocked => "Tap to play the theme".to_string(),
+                        PlaybackStatus::Failed(message) => message,
+                        _ => {
+                            let (start, end) = segment_bounds(track.trim, *audio.duration.read());
+                            let elapsed = format_timestamp(*audio.position.read() - start);
+                            match end {
+                                Some(end) => format!("{} / {}", elapsed, format_timestamp(end - start)),
+                                None => elapsed,
+                            }
+                        }
+                    };
+                    let toggle_track = track.clone();
+                    rsx! {
+                        div {
+                            class: "mb-6 flex items-center gap-4",
+
+                            button {
+                                class: "w-14 h-14 rounded-full bg-white/10 hover:bg-white/20 flex items-center justify-center transition-colors",
+                                "aria-label": if is_playing { "Pause theme music" } else { "Play theme music" },
+                                onclick: move |_| audio.toggle(toggle_track.clone()),
+
+                                span {
+                                    class: "text-2xl text-white",
+                                    if is_playing { "⏸" } else { "▶" }
+                                }
+                            }
+
+                            div {
+                                class: "flex-1",
+
+                                input {
+                                    class: "w-full h-1 accent-white cursor-pointer",
+                                    r#type: "range",
+                                    min: "0",
+                                    max: "1000",
+                                    "aria-label": "Theme music position",
+                                    disabled: !is_current,
+                                    value: "{(progress * 1000.0).round()}",
+                                    oninput: move |e| {
+                                        if let Ok(value) = e.value().parse::<f64>() {
+                                            audio.seek(value / 1000.0);
+                                        }
+                                    },
+                                }
+
+                                div {
+                                    class: "flex items-center justify-between mt-1",
+                                    p {
+                                        class: "text-sm text-white/40",
+                                        if *audio.muted.read() { "Muted · {status_label}" } else { "{status_label}" }
+                                    }
+                                    div {
+                                        class: "flex items-center gap-2",
+                                        button {
+                                            class: "text-white/60 hover:text-white transition-colors",
+                                            "aria-label": if *audio.muted.read() { "Unmute theme music" } else { "Mute theme music" },
+                                            onclick: move |_| {
+                                                let muted = *audio.muted.peek();
+                                                audio.set_muted(!muted);
+                                            },
+                                            if *audio.muted.read() { "🔇" } else { "🔊" }
+                                        }
+                                        input {
+                                            class: "w-20 h-1 accent-white cursor-pointer",
+                                            r#type: "range",
+                                            min: "0",
+                                            max: "100",
+                                            "aria-label": "Theme music volume",
+                                            value: "{(*audio.volume.read() * 100.0).round()}",
+                                            oninput: move |e| {
+                                                if let Ok(value) = e.value().parse::<f64>() {
+                                                    audio.set_volume(value / 100.0);
+                                                }
+                                            },
+                                        }
+                                    }
+                                }
+                            }
                         }
                     }
                 }
@@ -128,7 +579,7 @@ pub fn MemoryDetails(
                 }
             }
 
-            if mnemon.feelings.is_empty() && mnemon.finished_date.is_none() && mnemon.notes.is_empty() && work.theme_music_local_uri.is_none() {
+            if mnemon.feelings.is_empty() && mnemon.finished_date.is_none() && mnemon.notes.is_empty() && work.theme_music_local_uri.is_none() && work.metadata.is_empty() {
                 div {
                     class: "flex items-center justify-center flex-1 text-white/40 italic",
                     "No additional details for this memory"
@@ -0,0 +1,254 @@
+//! Duplicate works review modal
+
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use crate::app_state::AppState;
+use crate::data::duplicates::find_duplicates;
+use crate::models::{Work, WorkField};
+use crate::providers::images::{sized_image_url, ImageKind, ImageSize};
+
+/// Where a work came from, for telling two duplicates apart
+fn source_label(work: &Work) -> String {
+    match &work.provider_ref {
+        Some(provider_ref) => provider_ref.provider_source.to_uppercase(),
+        None => "Added by hand".to_string(),
+    }
+}
+
+/// Display value of a field that can be picked from either work
+fn field_value(work: &Work, field: WorkField) -> String {
+    match field {
+        WorkField::Title => work.title_en.clone(),
+        WorkField::ReleaseYear => work
+            .release_year
+            .map(|y| y.to_string())
+            .unwrap_or_else(|| "No year".to_string()),
+        WorkField::Cover => match work.cover_image_local_uri {
+            Some(_) => "Cover image".to_string(),
+            None => "No cover".to_string(),
+        },
+    }
+}
+
+/// One duplicate pair with the choice of surviving work and fields
+#[component]
+fn DuplicatePair(
+    first: Work,
+    second: Work,
+    first_mnemons: usize,
+    second_mnemons: usize,
+    on_merge: EventHandler<(Uuid, Uuid, Vec<WorkField>)>,
+    on_keep_both: EventHandler<()>,
+) -> Element {
+    // Which work survives, and the fields the user picked (true if from the first work);
+    // fields not picked keep the surviving work's value
+    let app_state = use_context::<Signal<AppState>>();
+    let mut keep_first = use_signal(|| true);
+    let mut from_first = use_signal(Vec::<(WorkField, bool)>::new);
+
+    let differing: Vec<WorkField> = [WorkField::Title, WorkField::ReleaseYear, WorkField::Cover]
+        .into_iter()
+        .filter(|f| first.field_differs(&second, *f))
+        .collect();
+
+    let merge_first = first.clone();
+    let merge_second = second.clone();
+    let merge = move |_| {
+        let (keep, drop) = if keep_first() {
+            (merge_first.id, merge_second.id)
+        } else {
+            (merge_second.id, merge_first.id)
+        };
+        // Fields whose picked value lives on the work being dropped
+        let fields: Vec<WorkField> = from_first()
+            .into_iter()
+            .filter(|(_, picked_first)| *picked_first != keep_first())
+            .map(|(field, _)| field)
+            .collect();
+        on_merge.call((keep, drop, fields));
+    };
+
+    let cards = [
+        (first.clone(), first_mnemons, true),
+        (second.clone(), second_mnemons, false),
+    ];
+
+    rsx! {
+        div {
+            class: "space-y-4",
+
+            div {
+                class: "grid grid-cols-2 gap-3",
+                for (work, mnemons, is_first) in cards {
+                    button {
+                        key: "{work.id}",
+                        class: if keep_first() == is_first {
+                            "p-3 text-left rounded-lg border-2 border-blue-500 bg-gray-700"
+                        } else {
+                            "p-3 text-left rounded-lg border-2 border-gray-600 hover:border-gray-400"
+                        },
+                        onclick: move |_| keep_first.set(is_first),
+                        if let Some(url) = work.cover_image_local_uri.as_deref().and_then(|uri| app_state.read().media_src(uri)) {
+                            img {
+                                class: "w-full h-24 object-cover rounded mb-2",
+                                src: sized_image_url(&url, ImageKind::Poster, ImageSize::Thumbnail),
+                            }
+                        }
+                        p { class: "text-white text-sm font-medium", "{work.work_type.icon()} {work.title_en}" }
+                        p {
+                            class: "text-gray-400 text-xs",
+                            "{source_label(&work)}"
+                            if let Some(year) = work.release_year { " · {year}" }
+                        }
+                        p {
+                            class: "text-gray-500 text-xs",
+                            if mnemons == 1 { "1 memory" } else { "{mnemons} memories" }
+                        }
+                        if keep_first() == is_first {
+                            p { class: "text-blue-400 text-xs mt-1", "Keep this entry" }
+                        }
+                    }
+                }
+            }
+
+            if !differing.is_empty() {
+                div {
+                    class: "space-y-2",
+                    p { class: "text-gray-400 text-xs", "Pick the values to keep:" }
+                    for field in differing {
+                        {
+                            let picked_first = from_first()
+                                .iter()
+                                .find(|(f, _)| *f == field)
+                                .map(|(_, first)| *first)
+                                .unwrap_or(keep_first());
+                            let options = [
+                                (field_value(&first, field), true),
+                                (field_value(&second, field), false),
+                            ];
+                            rsx! {
+                                div {
+                                    key: "{field.label()}",
+                                    class: "flex items-center gap-2 text-sm",
+                                    span { class: "w-12 text-gray-400 text-xs", "{field.label()}" }
+                                    for (value, is_first) in options {
+                                        button {
+                                            class: if picked_first == is_first {
+                                                "flex-1 px-2 py-1 rounded bg-blue-600 text-white text-xs truncate"
+                                            } else {
+                                                "flex-1 px-2 py-1 rounded bg-gray-700 text-gray-300 text-xs truncate hover:bg-gray-600"
+                                            },
+                                            onclick: move |_| {
+                                                from_first.with_mut(|picks| {
+                                                    picks.retain(|(f, _)| *f != field);
+                                                    picks.push((field, is_first));
+                                                });
+                                            },
+                                            "{value}"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            p {
+                class: "text-gray-500 text-xs",
+                "Memories of both entries move to the kept one, and the other entry's titles stay searchable."
+            }
+
+            div {
+                class: "flex justify-end gap-3",
+                button {
+                    class: "px-4 py-2 text-gray-400 hover:text-white text-sm transition-colors",
+                    onclick: move |_| on_keep_both.call(()),
+                    "Not duplicates"
+                }
+                button {
+                    class: "px-4 py-2 bg-transparent border-2 border-white hover:bg-white/10 text-white rounded-lg text-sm font-medium transition-colors",
+                    onclick: merge,
+                    "Merge"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn DuplicatesModal(on_close: EventHandler<()>) -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+
+    let pairs = find_duplicates(&app_state.read().works.read());
+    let mnemon_count = move |work_id: Uuid| {
+        app_state
+            .read()
+            .mnemons
+            .read()
+            .iter()
+            .filter(|m| m.work_id == work_id)
+            .count()
+    };
+
+    rsx! {
+        div {
+            class: "fixed inset-0 z-50 flex items-center justify-center bg-black/80 backdrop-blur-sm",
+            onclick: move |_| on_close.call(()),
+
+            div {
+                class: "bg-gray-800 rounded-lg shadow-2xl w-full max-w-lg mx-2 sm:mx-4 md:mx-auto max-h-[90vh] overflow-y-auto",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "px-6 py-4 border-b border-gray-700 flex items-center justify-between",
+                    h2 {
+                        class: "text-xl font-semibold text-white",
+                        "Duplicate works"
+                    }
+                    button {
+                        class: "text-gray-400 hover:text-white transition-colors",
+                        onclick: move |_| on_close.call(()),
+                        "✕"
+                    }
+                }
+
+                div {
+                    class: "px-6 py-4",
+                    match pairs.first() {
+                        Some((first, second)) => rsx! {
+                            div {
+                                key: "{first.id}-{second.id}",
+                                p {
+                                    class: "text-gray-400 text-sm mb-4",
+                                    if pairs.len() == 1 {
+                                        "These entries look like the same work."
+                                    } else {
+                                        "These entries look like the same work (1 of {pairs.len()})."
+                                    }
+                                }
+                                DuplicatePair {
+                                    first: first.clone(),
+                                    second: second.clone(),
+                                    first_mnemons: mnemon_count(first.id),
+                                    second_mnemons: mnemon_count(second.id),
+                                    on_merge: move |(keep, drop, fields): (Uuid, Uuid, Vec<WorkField>)| {
+                                        app_state.write().merge_work_into(drop, keep, &fields);
+                                    },
+                                    on_keep_both: {
+                                        let (a, b) = (first.id, second.id);
+                                        move |_| app_state.write().mark_distinct(a, b)
+                                    },
+                                }
+                            }
+                        },
+                        None => rsx! {
+                            p { class: "text-gray-400 text-sm", "No duplicate works in your library." }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use dioxus::prelude::*;
 
-use crate::components::{FeelingsSelector, FinishedDateInput, NotesInput};
+use crate::components::{
+    CoverPicker, CoverUpload, FeelingsSelector, FinishedDateInput, NotesInput, ThemeMusicUpload,
+};
 use crate::forms::MnemonForm;
 
 #[component]
@@ -63,6 +65,12 @@ pub fn EditMnemonFlow(
                         }
                     }
 
+                    CoverPicker { form: form }
+
+                    CoverUpload { form: form }
+
+                    ThemeMusicUpload { form: form }
+
                     NotesInput { form: form }
 
                     FeelingsSelector { form: form }
@@ -28,3 +28,33 @@ pub fn EmptyState(on_click: EventHandler<()>) -> Element {
         }
     }
 }
+
+/// Shown when there are mnemons but the slideshow filter leaves none of them
+#[component]
+pub fn FilteredEmptyState(on_clear: EventHandler<()>) -> Element {
+    rsx! {
+        div {
+            class: "h-full w-full flex items-center justify-center",
+
+            div {
+                class: "text-center px-8 max-w-md",
+
+                h1 {
+                    class: "text-3xl font-semibold mb-4 text-white",
+                    "No memories match these filters"
+                }
+
+                p {
+                    class: "text-lg opacity-70 mb-8 text-white",
+                    "Loosen the filters to bring some back into the slideshow."
+                }
+
+                button {
+                    class: "px-6 py-2 bg-white/20 hover:bg-white/30 text-white rounded-lg transition-colors font-medium",
+                    onclick: move |_| on_clear.call(()),
+                    "Clear filters"
+                }
+            }
+        }
+    }
+}
@@ -5,12 +5,15 @@ use rand::seq::SliceRandom;
 use rand::thread_rng;
 use uuid::Uuid;
 
-use crate::app_state::MnemonWithWork;
+use crate::app_state::{AppState, MnemonWithWork};
 use crate::components::MemoryDetails;
 use crate::constants::*;
+use crate::data::anniversaries::featured_anniversaries;
 use crate::hooks::touch_gesture::{use_touch_gesture, SwipeDirection};
+use crate::models::WorkField;
+use crate::providers::images::{hero_srcset, sized_image_url, ImageKind, ImageSize};
 use crate::types::Direction;
-use crate::utils::calculate_reading_time;
+use crate::utils::{calculate_reading_time, today};
 
 #[component]
 pub fn Hero(
@@ -31,6 +34,8 @@ pub fn Hero(
     on_navigate_next: Option<EventHandler<()>>,
     #[props(default = false)]
     is_mobile: bool,
+    #[props(default = None)]
+    on_anniversaries: Option<EventHandler<()>>,
 ) -> Element {
     let work = mnemon_with_work().work;
     let mnemon = mnemon_with_work().mnemon;
@@ -124,6 +129,42 @@ pub fn Hero(
         notes.get(idx).cloned()
     });
 
+    // Covers that failed to load, so the next picked cover is shown instead (tried
+    // again when the work's covers change, e.g. after an edit or a refresh)
+    let mut failed_covers = use_signal(Vec::<String>::new);
+    let covers = use_memo(move || mnemon_with_work().work.covers().cloned().collect::<Vec<_>>());
+    use_effect(move || {
+        covers.read();
+        failed_covers.set(Vec::new());
+    });
+
+    // Cover art, or the backdrop when there is no cover
+    let app_state = use_context::<Signal<AppState>>();
+    let hero_art = work
+        .covers()
+        .filter_map(|uri| app_state.read().media_src(uri))
+        .find(|url| !failed_covers.read().contains(url))
+        .map(|url| (url, ImageKind::Poster))
+        .or_else(|| work.backdrop_url.clone().map(|url| (url, ImageKind::Backdrop)));
+    // Landscape art replaces the cover on wide screens, unless the user picked the cover
+    let wide_art_srcset = work
+        .backdrop_url
+        .clone()
+        .filter(|_| work.cover_image_local_uri.is_some() && !work.is_overridden(WorkField::Cover))
+        .map(|url| hero_srcset(&url, ImageKind::Backdrop).unwrap_or(url));
+
+    // Today's anniversaries, found again only when the mnemons or the date change
+    let mnemons = app_state.read().mnemons;
+    let today = today();
+    let featured = use_memo(use_reactive!(|today| featured_anniversaries(&mnemons.read(), today)));
+
+    // "On this day" headline when this mnemon is one of today's anniversaries
+    let anniversary_headline = featured
+        .read()
+        .iter()
+        .find(|a| a.mnemon_id == mnemon.id)
+        .map(|a| a.headline(&work.title_en));
+
     // Horizontal transition for slideshow with direction support
     let horizontal_transition = if is_transitioning {
         if is_exiting {
@@ -256,11 +297,34 @@ pub fn Hero(
                                 "absolute inset-0 ken-burns-{} bg-yellow-300",
                                 (mnemon.id.as_u128() % 6) + 1
                             ),
-                            style: if let Some(ref url) = work.cover_image_local_uri {
-                                format!("background-image: url('{}'); background-size: cover; background-position: center; background-repeat: no-repeat;", url)
-                            } else {
-                                "background-color: #1a1a2e;".to_string()
-                            },
+                            style: if hero_art.is_none() { "background-color: #1a1a2e;" } else { "" },
+
+                            if let Some((ref url, kind)) = hero_art {
+                                picture {
+                                    if let Some(ref srcset) = wide_art_srcset {
+                                        source {
+                                            "media": "(min-aspect-ratio: 4/3)",
+                                            "srcset": "{srcset}",
+                                            "sizes": "100vw",
+                                        }
+                                    }
+                                    img {
+                                        class: "w-full h-full object-cover",
+                                        src: sized_image_url(url, kind, ImageSize::Hero),
+                                        srcset: hero_srcset(url, kind),
+                                        sizes: "100vw",
+                                        alt: "",
+                                        onerror: {
+                                            let url = url.clone();
+                                            move |_| {
+                                                if kind == ImageKind::Poster {
+                                                    failed_covers.write().push(url.clone());
+                                                }
+                                            }
+                                        },
+                                    }
+                                }
+                            }
                         }
 
                         // Dark overlay for readability
@@ -284,6 +348,27 @@ pub fn Hero(
                         }
                     }
 
+                    // Anniversary banner - top center (only visible when details closed)
+                    if !details_open {
+                        if let Some(ref headline) = anniversary_headline {
+                            div {
+                                class: "absolute top-6 left-1/2 -translate-x-1/2 z-30 max-w-[70vw]",
+
+                                button {
+                                    class: "px-4 py-2 bg-black/50 backdrop-blur-sm text-white/90 text-sm rounded-full border border-white/20 hover:bg-black/70 transition-colors",
+                                    title: "Show all anniversaries",
+                                    onclick: move |e| {
+                                        e.stop_propagation();
+                                        if let Some(on_anniversaries) = on_anniversaries {
+                                            on_anniversaries.call(());
+                                        }
+                                    },
+                                    "🎉 {headline}"
+                                }
+                            }
+                        }
+                    }
+
                     // Content overlay - footnote style at bottom right (original layout)
                     div {
                         class: "absolute inset-0 z-10 px-8 pb-8 flex items-end justify-end pointer-events-none",
@@ -0,0 +1,137 @@
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use crate::app_state::AppState;
+use crate::data::refresh;
+use crate::data::SearchService;
+use crate::models::{SearchResult, Work};
+
+/// Review inbox for manual works the background matcher found candidates for,
+/// plus a summary of works it linked on its own
+#[component]
+pub fn MatchInbox(auto_linked: Vec<Work>, on_dismiss_linked: EventHandler<()>) -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+    let mut expanded = use_signal(|| false);
+
+    let pending: Vec<Work> = app_state
+        .read()
+        .works
+        .read()
+        .iter()
+        .filter(|w| w.provider_ref.is_none() && !w.match_candidates.is_empty())
+        .cloned()
+        .collect();
+    if pending.is_empty() && auto_linked.is_empty() {
+        return rsx! {};
+    }
+    // Works linked on their own are listed but have nothing left to review
+    let summary = match (pending.len(), auto_linked.len()) {
+        (1, _) => "1 online match to review".to_string(),
+        (0, 1) => "1 work linked online".to_string(),
+        (0, linked) => format!("{} works linked online", linked),
+        (pending, _) => format!("{} online matches to review", pending),
+    };
+
+    let mut link = move |work_id: Uuid, result: SearchResult| {
+        let linked = app_state.write().link_work_to_provider(work_id, &result);
+        let Some(linked) = linked else {
+            return;
+        };
+        // Fill in descriptive metadata for the newly linked work
+        spawn(async move {
+            match refresh::refresh_work(&SearchService::new(), linked).await {
+                Ok(refresh) => app_state.write().update_work(refresh.work),
+                Err(e) => tracing::info!("Could not fetch details for linked work: {}", e),
+            }
+        });
+    };
+
+    let mut dismiss = move |work: Work| {
+        let mut work = work;
+        work.match_candidates.clear();
+        work.match_dismissed = true;
+        app_state.write().update_work(work);
+    };
+
+    rsx! {
+        div {
+            class: "fixed bottom-8 left-8 z-40 max-w-sm",
+
+            div {
+                class: "bg-gray-800 border border-white/20 rounded-lg shadow-2xl overflow-hidden",
+
+                div {
+                    class: "px-4 py-3 flex items-center justify-between gap-4",
+
+                    button {
+                        class: "text-white/90 text-left hover:text-white",
+                        onclick: move |_| expanded.toggle(),
+                        "{summary}"
+                        span { class: "text-white/50 ml-2", if expanded() { "▾" } else { "▸" } }
+                    }
+
+                    if !auto_linked.is_empty() {
+                        button {
+                            class: "text-white/50 hover:text-white transition-colors",
+                            title: "Dismiss linked works",
+                            onclick: move |_| on_dismiss_linked.call(()),
+                            "✕"
+                        }
+                    }
+                }
+
+                if expanded() {
+                    div {
+                        class: "px-4 pb-3 max-h-96 overflow-y-auto space-y-4",
+
+                        for work in auto_linked.iter() {
+                            div {
+                                key: "linked-{work.id}",
+                                p { class: "text-white text-sm font-medium", "{work.title_en}" }
+                                p { class: "text-white/60 text-xs", "Linked automatically" }
+                            }
+                        }
+
+                        for work in pending.into_iter() {
+                            div {
+                                key: "{work.id}",
+                                class: "space-y-1",
+                                p { class: "text-white text-sm font-medium",
+                                    "{work.title_en}"
+                                    if let Some(year) = work.release_year {
+                                        span { class: "text-white/50 ml-1", "({year})" }
+                                    }
+                                }
+                                for candidate in work.match_candidates.clone().into_iter() {
+                                    {
+                                        let work_id = work.id;
+                                        let label = match candidate.year {
+                                            Some(year) => format!("{} ({})", candidate.title, year),
+                                            None => candidate.title.clone(),
+                                        };
+                                        rsx! {
+                                            button {
+                                                key: "{candidate.provider_ref.provider_id}",
+                                                class: "block w-full text-left px-2 py-1 rounded text-white/80 text-xs hover:bg-white/10",
+                                                onclick: move |_| link(work_id, candidate.clone()),
+                                                "🔗 {label}"
+                                            }
+                                        }
+                                    }
+                                }
+                                button {
+                                    class: "px-2 py-1 text-white/50 text-xs hover:text-white",
+                                    onclick: {
+                                        let work = work.clone();
+                                        move |_| dismiss(work.clone())
+                                    },
+                                    "None of these"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
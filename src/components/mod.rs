@@ -1,17 +1,35 @@
 pub mod add_mnemon;
+pub mod anniversaries;
+pub mod cover_picker;
+pub mod cover_upload;
 pub mod details;
+pub mod duplicates;
 pub mod edit_mnemon;
 pub mod empty_state;
 pub mod form_inputs;
 pub mod hero;
+pub mod match_inbox;
+pub mod radio_bar;
+pub mod refresh_toast;
 pub mod settings;
+pub mod slideshow_filter;
+pub mod theme_music_upload;
 pub mod undo_toast;
 
 pub use add_mnemon::AddMnemonFlow;
+pub use anniversaries::AnniversariesModal;
+pub use cover_picker::CoverPicker;
+pub use cover_upload::CoverUpload;
 pub use details::MemoryDetails;
+pub use duplicates::DuplicatesModal;
 pub use edit_mnemon::EditMnemonFlow;
-pub use empty_state::EmptyState;
+pub use empty_state::{EmptyState, FilteredEmptyState};
 pub use form_inputs::{EditIcon, FeelingsSelector, FinishedDateInput, NotesInput};
 pub use hero::Hero;
+pub use match_inbox::MatchInbox;
+pub use radio_bar::RadioBar;
+pub use refresh_toast::MetadataRefreshToast;
 pub use settings::SettingsModal;
+pub use slideshow_filter::SlideshowFilterBar;
+pub use theme_music_upload::ThemeMusicUpload;
 pub use undo_toast::{PendingDelete, UndoToast};
@@ -0,0 +1,173 @@
+use dioxus::prelude::*;
+
+use crate::app_state::AppState;
+use crate::audio::{AudioController, PlaybackStatus, ThemeTrack};
+use crate::constants::FEELINGS;
+use crate::models::WorkType;
+use crate::providers::images::{sized_image_url, ImageKind, ImageSize};
+use crate::radio::{radio_decades, Radio, RadioFilter};
+
+/// Theme music radio: a button that opens into a now-playing bar
+///
+/// Stays on screen over the hero, so the radio keeps going while the user browses
+/// or the hero is paused.
+#[component]
+pub fn RadioBar() -> Element {
+    let app_state = use_context::<Signal<AppState>>();
+    let audio = use_context::<AudioController>();
+    let mut radio = use_context::<Radio>();
+    let mut expanded = use_signal(|| false);
+    let mut nothing_to_play = use_signal(|| false);
+
+    // Filters offered: every feeling and type, and the decades that have music
+    let decades = {
+        let state = app_state.read();
+        let works = state.works.read();
+        radio_decades(&works, |w| ThemeTrack::for_work(w, &state).is_some())
+    };
+    let filters: Vec<RadioFilter> = std::iter::once(RadioFilter::All)
+        .chain(
+            FEELINGS
+                .iter()
+                .map(|(feeling, _)| RadioFilter::Feeling(feeling.to_string())),
+        )
+        .chain(WorkType::ALL.into_iter().map(RadioFilter::Type))
+        .chain(decades.into_iter().map(RadioFilter::Decade))
+        .collect();
+
+    let mut start = move |filter: RadioFilter| {
+        nothing_to_play.set(!radio.start(filter));
+    };
+
+    if !expanded() && !*radio.active.read() {
+        return rsx! {
+            button {
+                class: "fixed top-4 left-4 z-40 w-10 h-10 rounded-full bg-black/40 hover:bg-black/60 text-white flex items-center justify-center transition-colors",
+                title: "Theme music radio",
+                onclick: move |_| expanded.set(true),
+                "📻"
+            }
+        };
+    }
+
+    let now_playing = radio.current_work();
+    let cover = now_playing
+        .as_ref()
+        .and_then(|w| w.covers().next().cloned())
+        .and_then(|uri| app_state.read().media_src(&uri));
+    let is_playing = matches!(
+        *audio.status.read(),
+        PlaybackStatus::Playing | PlaybackStatus::Loading
+    );
+
+    rsx! {
+        div {
+            class: "fixed top-4 left-4 z-40 w-80 max-w-[calc(100vw-2rem)] bg-gray-800/95 border border-white/20 rounded-lg shadow-2xl p-3",
+
+            div {
+                class: "flex items-center gap-3",
+
+                if let Some(src) = cover {
+                    img {
+                        class: "w-12 h-12 rounded object-cover flex-shrink-0",
+                        src: sized_image_url(&src, ImageKind::Poster, ImageSize::Thumbnail),
+                        alt: "",
+                    }
+                } else {
+                    div {
+                        class: "w-12 h-12 rounded bg-gray-700 flex items-center justify-center flex-shrink-0 text-xl",
+                        "📻"
+                    }
+                }
+
+                div {
+                    class: "flex-1 min-w-0",
+                    if let Some(work) = now_playing.as_ref() {
+                        p { class: "text-white text-sm font-semibold truncate", "{work.title_en}" }
+                        p {
+                            class: "text-white/50 text-xs truncate",
+                            "{work.work_type.icon()} Radio · {radio.filter.read().label()}"
+                        }
+                    } else {
+                        p { class: "text-white text-sm font-semibold", "Theme music radio" }
+                        p {
+                            class: "text-white/50 text-xs",
+                            if nothing_to_play() { "No themes match this filter" } else { "Plays every theme in your library" }
+                        }
+                    }
+                }
+
+                button {
+                    class: "text-gray-400 hover:text-white transition-colors",
+                    title: if *radio.active.read() { "Turn off the radio" } else { "Close" },
+                    onclick: move |_| {
+                        radio.stop();
+                        expanded.set(false);
+                        nothing_to_play.set(false);
+                    },
+                    "✕"
+                }
+            }
+
+            if *radio.active.read() {
+                div {
+                    class: "flex items-center justify-center gap-6 mt-2 text-white text-xl",
+                    button {
+                        class: "hover:text-white/70 transition-colors",
+                        "aria-label": "Previous theme",
+                        onclick: move |_| radio.previous(),
+                        "⏮"
+                    }
+                    button {
+                        class: "hover:text-white/70 transition-colors",
+                        "aria-label": if is_playing { "Pause radio" } else { "Play radio" },
+                        onclick: move |_| radio.toggle(),
+                        if is_playing { "⏸" } else { "▶" }
+                    }
+                    button {
+                        class: "hover:text-white/70 transition-colors",
+                        "aria-label": "Next theme",
+                        onclick: move |_| radio.next(),
+                        "⏭"
+                    }
+                }
+            }
+
+            div {
+                class: "flex items-center gap-2 mt-2",
+                select {
+                    class: "flex-1 px-2 py-1 bg-gray-700 text-white rounded border border-gray-600 focus:border-blue-500 focus:outline-none text-sm",
+                    "aria-label": "Radio filter",
+                    value: "{radio.filter.read().key()}",
+                    onchange: move |e| {
+                        if let Some(filter) = RadioFilter::from_key(&e.value()) {
+                            if *radio.active.peek() {
+                                start(filter);
+                            } else {
+                                radio.filter.set(filter);
+                                nothing_to_play.set(false);
+                            }
+                        }
+                    },
+                    for filter in filters {
+                        option {
+                            value: filter.key(),
+                            selected: *radio.filter.read() == filter,
+                            "{filter.label()}"
+                        }
+                    }
+                }
+                if !*radio.active.read() {
+                    button {
+                        class: "px-3 py-1 bg-white/20 hover:bg-white/30 text-white rounded text-sm transition-colors",
+                        onclick: move |_| {
+                            let filter = radio.filter.peek().clone();
+                            start(filter);
+                        },
+                        "Play"
+                    }
+                }
+            }
+        }
+    }
+}
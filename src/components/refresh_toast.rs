@@ -0,0 +1,58 @@
+use dioxus::prelude::*;
+
+use crate::data::refresh::WorkRefresh;
+
+#[component]
+pub fn MetadataRefreshToast(refreshes: Vec<WorkRefresh>, on_dismiss: EventHandler<()>) -> Element {
+    let mut expanded = use_signal(|| false);
+    let count = refreshes.len();
+
+    rsx! {
+        div {
+            class: "fixed bottom-8 right-8 z-40 max-w-sm",
+
+            div {
+                class: "bg-gray-800 border border-white/20 rounded-lg shadow-2xl overflow-hidden",
+
+                div {
+                    class: "px-4 py-3 flex items-center justify-between gap-4",
+
+                    button {
+                        class: "text-white/90 text-left hover:text-white",
+                        onclick: move |_| expanded.toggle(),
+                        if count == 1 {
+                            "Updated metadata for 1 work"
+                        } else {
+                            "Updated metadata for {count} works"
+                        }
+                        span { class: "text-white/50 ml-2", if expanded() { "▾" } else { "▸" } }
+                    }
+
+                    button {
+                        class: "text-white/50 hover:text-white transition-colors",
+                        onclick: move |_| on_dismiss.call(()),
+                        "✕"
+                    }
+                }
+
+                if expanded() {
+                    div {
+                        class: "px-4 pb-3 max-h-64 overflow-y-auto space-y-3",
+                        for refresh in refreshes.iter() {
+                            div {
+                                key: "{refresh.work.id}",
+                                p { class: "text-white text-sm font-medium", "{refresh.work.title_en}" }
+                                for change in refresh.changes.iter() {
+                                    p {
+                                        class: "text-white/60 text-xs",
+                                        "{change.field.label()}: {change.old_label()} → {change.new_label()}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
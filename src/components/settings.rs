@@ -3,13 +3,175 @@
 use dioxus::prelude::*;
 use tracing::info;
 
-use crate::settings::ApiTokenSettings;
+use crate::audio::{AudioController, FadeCurve};
+use crate::constants::DISPLAY_LANGUAGES;
+use crate::providers::rawg::{RawgClient, RawgConfig, DEFAULT_RAWG_API_BASE};
+use crate::providers::tmdb::{
+    TmdbClient, TmdbConfig, DEFAULT_TMDB_API_BASE, DEFAULT_TMDB_IMAGE_BASE,
+};
+use crate::providers::ConnectionCheck;
+use crate::settings::{self, ApiTokenSettings, TokenVerification};
 
+/// "Test connection" button with the last verification result for a credential
 #[component]
-pub fn SettingsModal(paused: Signal<bool>, on_close: EventHandler<()>) -> Element {
+fn ConnectionTest(
+    verification: Option<TokenVerification>,
+    testing: bool,
+    disabled: bool,
+    on_test: EventHandler<()>,
+) -> Element {
+    rsx! {
+        div {
+            class: "flex items-start justify-between gap-3 mt-2",
+
+            div {
+                class: "flex-1 text-xs",
+                if testing {
+                    span { class: "text-gray-400", "Testing connection..." }
+                } else if let Some(verification) = verification {
+                    {
+                        let color = match verification.outcome {
+                            ConnectionCheck::Valid => "text-green-400",
+                            ConnectionCheck::Invalid(_) => "text-red-400",
+                            ConnectionCheck::NetworkError(_)
+                            | ConnectionCheck::RateLimited(_) => "text-yellow-400",
+                        };
+                        let checked_at = verification
+                            .checked_at
+                            .with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d %H:%M")
+                            .to_string();
+                        rsx! {
+                            p {
+                                class: "{color} font-medium",
+                                "{verification.outcome.label()}"
+                                span { class: "text-gray-500 font-normal", " · checked {checked_at}" }
+                            }
+                            if let Some(message) = verification.outcome.message() {
+                                p { class: "text-gray-500 break-words", "{message}" }
+                            }
+                        }
+                    }
+                } else {
+                    span { class: "text-gray-500", "Not verified yet" }
+                }
+            }
+
+            button {
+                class: "px-3 py-1 text-xs text-white border border-gray-500 hover:border-white rounded-lg transition-colors disabled:opacity-50 disabled:cursor-not-allowed",
+                disabled: disabled || testing,
+                onclick: move |_| on_test.call(()),
+                "Test connection"
+            }
+        }
+    }
+}
+
+/// Text input for a provider base URL
+#[component]
+fn EndpointInput(
+    label: &'static str,
+    placeholder: &'static str,
+    value: String,
+    on_input: EventHandler<String>,
+) -> Element {
+    rsx! {
+        div {
+            label {
+                class: "block text-white text-xs font-semibold mb-1",
+                "{label}"
+            }
+            input {
+                class: "w-full px-3 py-2 bg-gray-700 text-white rounded-lg border-2 border-gray-600 focus:border-blue-500 focus:outline-none font-mono text-xs",
+                r#type: "url",
+                placeholder: "{placeholder}",
+                value: "{value}",
+                oninput: move |e| on_input.call(e.value()),
+            }
+        }
+    }
+}
+
+/// Use a validated endpoint, or the default when it was left empty
+fn non_empty_or(value: String, default: &str) -> String {
+    if value.is_empty() {
+        default.to_string()
+    } else {
+        value
+    }
+}
+
+#[component]
+pub fn SettingsModal(
+    paused: Signal<bool>,
+    on_close: EventHandler<()>,
+    on_review_duplicates: EventHandler<()>,
+    on_show_anniversaries: EventHandler<()>,
+) -> Element {
+    let mut audio = use_context::<AudioController>();
+
     // Load current settings into local state
     let mut local_settings = use_signal(ApiTokenSettings::load);
-    let mut save_status = use_signal(|| Option::<bool>::None);
+    let mut save_status = use_signal(|| Option::<Result<(), String>>::None);
+    let mut show_endpoints = use_signal(|| {
+        let current = ApiTokenSettings::load();
+        !(current.tmdb_api_base.is_empty()
+            && current.tmdb_image_base.is_empty()
+            && current.rawg_api_base.is_empty())
+    });
+
+    // Last connection test for the credential currently typed in each field
+    let mut tmdb_check = use_signal(settings::load_tmdb_verification);
+    let mut rawg_check = use_signal(settings::load_rawg_verification);
+    let mut tmdb_testing = use_signal(|| false);
+    let mut rawg_testing = use_signal(|| false);
+
+    // Test the typed (possibly unsaved) credential against the provider
+    // (against the typed endpoint, so a proxy can be checked before saving)
+    let test_tmdb = move |_| {
+        let current = match local_settings().validated() {
+            Ok(current) => current,
+            Err(e) => {
+                save_status.set(Some(Err(e)));
+                return;
+            }
+        };
+        let token = current.tmdb_token.clone();
+        let client = TmdbClient::with_config(TmdbConfig {
+            token: Some(token.clone()),
+            api_base: non_empty_or(current.tmdb_api_base, DEFAULT_TMDB_API_BASE),
+            image_base: non_empty_or(current.tmdb_image_base, DEFAULT_TMDB_IMAGE_BASE),
+            language: current.display_language,
+        });
+        tmdb_testing.set(true);
+        spawn(async move {
+            let outcome = client.check_connection(&token).await;
+            info!("TMDB connection check: {}", outcome.label());
+            tmdb_check.set(Some(settings::save_tmdb_verification(&token, outcome)));
+            tmdb_testing.set(false);
+        });
+    };
+    let test_rawg = move |_| {
+        let current = match local_settings().validated() {
+            Ok(current) => current,
+            Err(e) => {
+                save_status.set(Some(Err(e)));
+                return;
+            }
+        };
+        let api_key = current.rawg_api_key.clone();
+        let client = RawgClient::with_config(RawgConfig {
+            api_key: Some(api_key.clone()),
+            api_base: non_empty_or(current.rawg_api_base, DEFAULT_RAWG_API_BASE),
+        });
+        rawg_testing.set(true);
+        spawn(async move {
+            let outcome = client.check_connection(&api_key).await;
+            info!("RAWG connection check: {}", outcome.label());
+            rawg_check.set(Some(settings::save_rawg_verification(&api_key, outcome)));
+            rawg_testing.set(false);
+        });
+    };
 
     // Check configuration status
     let tmdb_configured = local_settings().has_tmdb();
@@ -131,6 +293,75 @@ pub fn SettingsModal(paused: Signal<bool>, on_close: EventHandler<()>) -> Elemen
                         }
                     }
 
+                    // Theme music preferences (applied immediately)
+                    div {
+                        class: "mb-6 p-4 bg-gray-700/50 rounded-lg",
+
+                        div {
+                            class: "flex items-center justify-between gap-4 mb-4",
+
+                            div {
+                                class: "flex-1",
+                                label {
+                                    class: "block text-white text-sm font-semibold mb-1",
+                                    "Theme Music"
+                                }
+                                p {
+                                    class: "text-gray-400 text-xs",
+                                    if *audio.muted.read() { "Muted everywhere" } else { "Plays when you open a mnemon" }
+                                }
+                            }
+
+                            button {
+                                class: if !*audio.muted.read() {
+                                    "relative w-14 h-7 bg-green-600 hover:bg-green-700 rounded-full transition-all focus:outline-none focus:ring-2 focus:ring-green-500 focus:ring-offset-2 focus:ring-offset-gray-800"
+                                } else {
+                                    "relative w-14 h-7 bg-gray-600 hover:bg-gray-500 rounded-full transition-all focus:outline-none focus:ring-2 focus:ring-gray-500 focus:ring-offset-2 focus:ring-offset-gray-800"
+                                },
+                                onclick: move |_| {
+                                    let muted = *audio.muted.peek();
+                                    audio.set_muted(!muted);
+                                    info!("Theme music muted: {}", !muted);
+                                },
+                                "aria-label": if *audio.muted.read() { "Unmute theme music" } else { "Mute theme music" },
+                                "aria-pressed": if !*audio.muted.read() { "true" } else { "false" },
+
+                                div {
+                                    class: if !*audio.muted.read() {
+                                        "absolute left-8 top-1 w-5 h-5 bg-white rounded-full shadow-md transition-transform duration-200 ease-in-out"
+                                    } else {
+                                        "absolute left-1 top-1 w-5 h-5 bg-white rounded-full shadow-md transition-transform duration-200 ease-in-out"
+                                    }
+                                }
+                            }
+                        }
+
+                        label {
+                            class: "block text-white text-sm font-semibold mb-1",
+                            "Crossfade"
+                        }
+                        p {
+                            class: "text-gray-400 text-xs mb-2",
+                            "How one theme fades into the next as the mnemons change."
+                        }
+                        select {
+                            class: "w-full px-4 py-3 bg-gray-700 text-white rounded-lg border-2 border-gray-600 focus:border-blue-500 focus:outline-none text-sm",
+                            value: "{audio.fade_curve.read().key()}",
+                            onchange: move |e| {
+                                if let Some(curve) = FadeCurve::from_key(&e.value()) {
+                                    audio.set_fade_curve(curve);
+                                }
+                            },
+                            for curve in FadeCurve::ALL {
+                                option {
+                                    value: curve.key(),
+                                    selected: *audio.fade_curve.read() == curve,
+                                    "{curve.label()}"
+                                }
+                            }
+                        }
+                    }
+
                     // TMDB Token
                     div {
                         class: "mb-6",
@@ -172,10 +403,18 @@ pub fn SettingsModal(paused: Signal<bool>, on_close: EventHandler<()>) -> Elemen
                             placeholder: "eyJhbGciOiJIUzI1NiJ9...",
                             value: "{local_settings().tmdb_token}",
                             oninput: move |e| {
+                                tmdb_check.set(settings::load_tmdb_verification_for(&e.value()));
                                 local_settings.with_mut(|s| s.tmdb_token = e.value());
                                 save_status.set(None);
                             }
                         }
+
+                        ConnectionTest {
+                            verification: tmdb_check(),
+                            testing: tmdb_testing(),
+                            disabled: local_settings().tmdb_token.trim().is_empty(),
+                            on_test: test_tmdb,
+                        }
                     }
 
                     // RAWG API Key
@@ -219,24 +458,153 @@ pub fn SettingsModal(paused: Signal<bool>, on_close: EventHandler<()>) -> Elemen
                             placeholder: "abc123def456...",
                             value: "{local_settings().rawg_api_key}",
                             oninput: move |e| {
+                                rawg_check.set(settings::load_rawg_verification_for(&e.value()));
                                 local_settings.with_mut(|s| s.rawg_api_key = e.value());
                                 save_status.set(None);
                             }
                         }
+
+                        ConnectionTest {
+                            verification: rawg_check(),
+                            testing: rawg_testing(),
+                            disabled: local_settings().rawg_api_key.trim().is_empty(),
+                            on_test: test_rawg,
+                        }
+                    }
+
+                    // Display language passed to providers
+                    div {
+                        class: "mb-6",
+
+                        label {
+                            class: "block text-white text-sm font-semibold mb-2",
+                            "Display language"
+                        }
+                        p {
+                            class: "text-gray-500 text-xs mb-2",
+                            "Titles and overviews are fetched in this language when the provider has them."
+                        }
+                        select {
+                            class: "w-full px-4 py-3 bg-gray-700 text-white rounded-lg border-2 border-gray-600 focus:border-blue-500 focus:outline-none text-sm",
+                            value: "{local_settings().display_language}",
+                            onchange: move |e| {
+                                local_settings.with_mut(|s| s.display_language = e.value());
+                                save_status.set(None);
+                            },
+                            for (code, name) in DISPLAY_LANGUAGES.iter() {
+                                option {
+                                    value: *code,
+                                    selected: local_settings().display_language == *code,
+                                    "{name}"
+                                }
+                            }
+                        }
+                    }
+
+                    // Library maintenance
+                    div {
+                        class: "mb-6 flex items-center justify-between gap-4",
+
+                        div {
+                            label {
+                                class: "block text-white text-sm font-semibold mb-1",
+                                "Duplicate works"
+                            }
+                            p {
+                                class: "text-gray-500 text-xs",
+                                "Merge works that were added more than once, e.g. by hand and from TMDB."
+                            }
+                        }
+
+                        button {
+                            class: "px-3 py-1 text-xs text-white border border-gray-500 hover:border-white rounded-lg transition-colors whitespace-nowrap",
+                            onclick: move |_| on_review_duplicates.call(()),
+                            "Find duplicates"
+                        }
+                    }
+
+                    div {
+                        class: "mb-6 flex items-center justify-between gap-4",
+
+                        div {
+                            label {
+                                class: "block text-white text-sm font-semibold mb-1",
+                                "On this day"
+                            }
+                            p {
+                                class: "text-gray-500 text-xs",
+                                "Memories you finished around this date in earlier years."
+                            }
+                        }
+
+                        button {
+                            class: "px-3 py-1 text-xs text-white border border-gray-500 hover:border-white rounded-lg transition-colors whitespace-nowrap",
+                            onclick: move |_| on_show_anniversaries.call(()),
+                            "Show anniversaries"
+                        }
+                    }
+
+                    // Custom endpoints (CORS proxy, mirror, local stub server)
+                    div {
+                        class: "mb-6",
+
+                        button {
+                            class: "text-gray-400 hover:text-white text-sm transition-colors",
+                            onclick: move |_| show_endpoints.toggle(),
+                            if show_endpoints() { "▾ Custom endpoints" } else { "▸ Custom endpoints" }
+                        }
+
+                        if show_endpoints() {
+                            div {
+                                class: "mt-3 space-y-3",
+
+                                p {
+                                    class: "text-gray-500 text-xs",
+                                    "Route requests through a proxy or mirror. Leave empty to use the public APIs."
+                                }
+
+                                EndpointInput {
+                                    label: "TMDB API URL",
+                                    placeholder: DEFAULT_TMDB_API_BASE,
+                                    value: local_settings().tmdb_api_base,
+                                    on_input: move |value| {
+                                        local_settings.with_mut(|s| s.tmdb_api_base = value);
+                                        save_status.set(None);
+                                    },
+                                }
+                                EndpointInput {
+                                    label: "TMDB image URL",
+                                    placeholder: DEFAULT_TMDB_IMAGE_BASE,
+                                    value: local_settings().tmdb_image_base,
+                                    on_input: move |value| {
+                                        local_settings.with_mut(|s| s.tmdb_image_base = value);
+                                        save_status.set(None);
+                                    },
+                                }
+                                EndpointInput {
+                                    label: "RAWG API URL",
+                                    placeholder: DEFAULT_RAWG_API_BASE,
+                                    value: local_settings().rawg_api_base,
+                                    on_input: move |value| {
+                                        local_settings.with_mut(|s| s.rawg_api_base = value);
+                                        save_status.set(None);
+                                    },
+                                }
+                            }
+                        }
                     }
 
                     // Save status message
-                    if let Some(success) = save_status() {
+                    if let Some(status) = save_status() {
                         div {
-                            class: if success {
+                            class: if status.is_ok() {
                                 "mb-4 px-4 py-2 bg-green-500/20 text-green-400 rounded-lg text-sm"
                             } else {
                                 "mb-4 px-4 py-2 bg-red-500/20 text-red-400 rounded-lg text-sm"
                             },
-                            if success {
-                                "Settings saved!"
-                            } else {
-                                "Failed to save settings. Please try again."
+                            match &status {
+                                Ok(()) => "Settings saved!",
+                                Err(message) => message.as_str(),
                             }
                         }
                     }
@@ -255,10 +623,22 @@ pub fn SettingsModal(paused: Signal<bool>, on_close: EventHandler<()>) -> Elemen
                     button {
                         class: "px-6 py-2 bg-transparent border-2 border-white hover:bg-white/10 text-white rounded-lg font-medium transition-colors",
                         onclick: move |_| {
-                            let success = local_settings().save();
-                            save_status.set(Some(success));
+                            let validated = match local_settings().validated() {
+                                Ok(validated) => validated,
+                                Err(e) => {
+                                    save_status.set(Some(Err(e)));
+                                    return;
+                                }
+                            };
+                            let success = validated.save();
+                            local_settings.set(validated);
                             if success {
                                 info!("Settings saved successfully");
+                                save_status.set(Some(Ok(())));
+                            } else {
+                                save_status.set(Some(Err(
+                                    "Failed to save settings. Please try again.".to_string(),
+                                )));
                             }
                         },
                         "Save"
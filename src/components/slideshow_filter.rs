@@ -0,0 +1,224 @@
+use dioxus::prelude::*;
+
+use crate::app_state::AppState;
+use crate::constants::FEELINGS;
+use crate::data::slideshow::{toggle, SlideshowFilter};
+use crate::models::{release_decade, WorkType};
+
+#[component]
+fn FilterChip(label: String, selected: bool, on_toggle: EventHandler<()>) -> Element {
+    rsx! {
+        button {
+            class: if selected {
+                "px-3 py-1 bg-transparent text-white rounded-full border-2 border-white text-xs font-medium"
+            } else {
+                "px-3 py-1 bg-gray-700 text-gray-300 rounded-full border-2 border-gray-600 hover:border-gray-500 text-xs font-medium"
+            },
+            "aria-pressed": if selected { "true" } else { "false" },
+            onclick: move |_| on_toggle.call(()),
+            "{label}"
+        }
+    }
+}
+
+/// Slideshow filter: a button that opens a panel of feelings, types, release
+/// decades, genres, platforms and a finished-year range to narrow what the hero
+/// cycles through
+#[component]
+pub fn SlideshowFilterBar(on_change: EventHandler<()>) -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+    let mut open = use_signal(|| false);
+
+    let filter = app_state.read().slideshow_filter.read().clone();
+    let active_count = filter.active_count();
+
+    // Decades that have at least one work, oldest first
+    let decades = {
+        let state = app_state.read();
+        let mut decades: Vec<u16> = state
+            .works
+            .read()
+            .iter()
+            .filter_map(|w| w.release_year.map(release_decade))
+            .collect();
+        decades.sort_unstable();
+        decades.dedup();
+        decades
+    };
+
+    // Genres and platforms the works' metadata mentions, alphabetically
+    let (genres, platforms) = {
+        let state = app_state.read();
+        let works = state.works.read();
+        let distinct = |names: Vec<&String>| {
+            let mut names: Vec<String> = names.into_iter().cloned().collect();
+            names.sort_by_key(|n| n.to_lowercase());
+            names.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+            names
+        };
+        (
+            distinct(works.iter().flat_map(|w| &w.metadata.genres).collect()),
+            distinct(works.iter().flat_map(|w| &w.metadata.platforms).collect()),
+        )
+    };
+
+    let mut update = move |change: &dyn Fn(&mut SlideshowFilter)| {
+        let mut filter = app_state.peek().slideshow_filter.peek().clone();
+        change(&mut filter);
+        app_state.write().set_slideshow_filter(filter);
+        on_change.call(());
+    };
+
+    if !open() {
+        return rsx! {
+            button {
+                class: if active_count > 0 {
+                    "fixed top-4 right-4 z-40 h-10 px-4 rounded-full bg-white text-gray-900 text-sm font-medium transition-colors"
+                } else {
+                    "fixed top-4 right-4 z-40 h-10 px-4 rounded-full bg-black/40 hover:bg-black/60 text-white text-sm transition-colors"
+                },
+                title: "Filter the slideshow",
+                onclick: move |_| open.set(true),
+                if active_count > 0 { "Filters · {active_count}" } else { "Filters" }
+            }
+        };
+    }
+
+    rsx! {
+        div {
+            class: "fixed top-4 right-4 z-40 w-96 max-w-[calc(100vw-2rem)] max-h-[80vh] overflow-y-auto bg-gray-800/95 border border-white/20 rounded-lg shadow-2xl p-4 space-y-4",
+
+            div {
+                class: "flex items-center justify-between",
+                h2 { class: "text-white font-semibold", "Show only" }
+                div {
+                    class: "flex items-center gap-3",
+                    if active_count > 0 {
+                        button {
+                            class: "text-gray-400 hover:text-white text-sm transition-colors",
+                            onclick: move |_| update(&|f| *f = SlideshowFilter::default()),
+                            "Clear"
+                        }
+                    }
+                    button {
+                        class: "text-gray-400 hover:text-white transition-colors",
+                        title: "Hide filters",
+                        onclick: move |_| open.set(false),
+                        "✕"
+                    }
+                }
+            }
+
+            div {
+                p { class: "text-gray-400 text-xs mb-2", "Feelings" }
+                div {
+                    class: "flex flex-wrap gap-2",
+                    for (feeling, emoji) in FEELINGS.iter() {
+                        FilterChip {
+                            key: "{feeling}",
+                            label: format!("{} {}", emoji, feeling),
+                            selected: filter.feelings.iter().any(|f| f == feeling),
+                            on_toggle: move |_| update(&|f| toggle(&mut f.feelings, feeling.to_string())),
+                        }
+                    }
+                }
+            }
+
+            div {
+                p { class: "text-gray-400 text-xs mb-2", "Types" }
+                div {
+                    class: "flex flex-wrap gap-2",
+                    for work_type in WorkType::ALL {
+                        FilterChip {
+                            key: "{work_type.label()}",
+                            label: format!("{} {}", work_type.icon(), work_type.label()),
+                            selected: filter.work_types.contains(&work_type),
+                            on_toggle: move |_| update(&|f| toggle(&mut f.work_types, work_type.clone())),
+                        }
+                    }
+                }
+            }
+
+            if !decades.is_empty() {
+                div {
+                    p { class: "text-gray-400 text-xs mb-2", "Released in" }
+                    div {
+                        class: "flex flex-wrap gap-2",
+                        for decade in decades {
+                            FilterChip {
+                                key: "{decade}",
+                                label: format!("{}s", decade),
+                                selected: filter.decades.contains(&decade),
+                                on_toggle: move |_| update(&|f| toggle(&mut f.decades, decade)),
+                            }
+                        }
+                    }
+                }
+            }
+
+            if !genres.is_empty() {
+                div {
+                    p { class: "text-gray-400 text-xs mb-2", "Genres" }
+                    div {
+                        class: "flex flex-wrap gap-2",
+                        for genre in genres {
+                            FilterChip {
+                                key: "{genre}",
+                                label: genre.clone(),
+                                selected: filter.genres.iter().any(|g| g.eq_ignore_ascii_case(&genre)),
+                                on_toggle: move |_| update(&|f| toggle(&mut f.genres, genre.clone())),
+                            }
+                        }
+                    }
+                }
+            }
+
+            if !platforms.is_empty() {
+                div {
+                    p { class: "text-gray-400 text-xs mb-2", "Platforms" }
+                    div {
+                        class: "flex flex-wrap gap-2",
+                        for platform in platforms {
+                            FilterChip {
+                                key: "{platform}",
+                                label: platform.clone(),
+                                selected: filter.platforms.iter().any(|p| p.eq_ignore_ascii_case(&platform)),
+                                on_toggle: move |_| update(&|f| toggle(&mut f.platforms, platform.clone())),
+                            }
+                        }
+                    }
+                }
+            }
+
+            div {
+                p { class: "text-gray-400 text-xs mb-2", "Finished between" }
+                div {
+                    class: "flex items-center gap-2 text-sm text-gray-400",
+                    input {
+                        class: "w-24 px-2 py-1 bg-gray-700 text-white rounded border border-gray-600 focus:border-white focus:outline-none",
+                        r#type: "number",
+                        placeholder: "From",
+                        "aria-label": "Finished from year",
+                        value: filter.finished_from.map(|y| y.to_string()).unwrap_or_default(),
+                        oninput: move |e| {
+                            let year = e.value().trim().parse::<i32>().ok();
+                            update(&|f| f.finished_from = year);
+                        },
+                    }
+                    span { "and" }
+                    input {
+                        class: "w-24 px-2 py-1 bg-gray-700 text-white rounded border border-gray-600 focus:border-white focus:outline-none",
+                        r#type: "number",
+                        placeholder: "To",
+                        "aria-label": "Finished to year",
+                        value: filter.finished_to.map(|y| y.to_string()).unwrap_or_default(),
+                        oninput: move |e| {
+                            let year = e.value().trim().parse::<i32>().ok();
+                            update(&|f| f.finished_to = year);
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
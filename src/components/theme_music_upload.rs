@@ -0,0 +1,230 @@
+use dioxus::html::FileData;
+use dioxus::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::app_state::AppState;
+use crate::constants::{THEME_MUSIC_MAX_FILE_BYTES, THEME_MUSIC_TYPES};
+use crate::forms::MnemonForm;
+use crate::uploads::{prepare_theme_music, validate_trim};
+use crate::utils::{format_timestamp, parse_timestamp};
+
+const PREVIEW_ELEMENT_ID: &str = "theme-music-preview";
+
+#[derive(Clone, PartialEq)]
+enum UploadState {
+    Idle,
+    Processing,
+    Failed(String),
+}
+
+/// The preview player, for reading its length and position
+fn preview_element() -> Option<web_sys::HtmlMediaElement> {
+    web_sys::window()?
+        .document()?
+        .get_element_by_id(PREVIEW_ELEMENT_ID)?
+        .dyn_into()
+        .ok()
+}
+
+/// Theme music attached from a local audio file, with the segment to play
+#[component]
+pub fn ThemeMusicUpload(form: Signal<MnemonForm>) -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+    let mut state = use_signal(|| UploadState::Idle);
+    let mut duration = use_signal(|| Option::<f64>::None);
+
+    // Trim points as typed, checked against the track length as they change
+    let initial_trim = form.peek().theme_music_trim;
+    let mut start_text = use_signal(|| {
+        initial_trim
+            .map(|t| format_timestamp(t.start_secs))
+            .unwrap_or_default()
+    });
+    let mut end_text = use_signal(|| {
+        initial_trim
+            .and_then(|t| t.end_secs)
+            .map(format_timestamp)
+            .unwrap_or_default()
+    });
+    let mut trim_error = use_signal(|| Option::<String>::None);
+
+    let mut apply_trim = move || {
+        let start = match start_text().trim() {
+            "" => Some(0.0),
+            text => parse_timestamp(text),
+        };
+        let end = match end_text().trim() {
+            "" => Some(None),
+            text => parse_timestamp(text).map(Some),
+        };
+        let (Some(start), Some(end)) = (start, end) else {
+            trim_error.set(Some("Use seconds or m:ss".to_string()));
+            return;
+        };
+        match validate_trim(start, end, duration()) {
+            Ok(trim) => {
+                trim_error.set(None);
+                form.with_mut(|f| f.theme_music_trim = trim);
+            }
+            Err(e) => trim_error.set(Some(e.to_string())),
+        }
+    };
+
+    let mut upload = move |files: Vec<FileData>| {
+        let Some(file) = files.into_iter().next() else {
+            return;
+        };
+        state.set(UploadState::Processing);
+        spawn(async move {
+            match prepare_theme_music(&file).await {
+                Ok(asset) => {
+                    let uri = app_state.write().add_asset(asset);
+                    form.with_mut(|f| {
+                        f.theme_music_url = Some(uri);
+                        f.theme_music_trim = None;
+                    });
+                    duration.set(None);
+                    start_text.set(String::new());
+                    end_text.set(String::new());
+                    trim_error.set(None);
+                    state.set(UploadState::Idle);
+                }
+                Err(e) => state.set(UploadState::Failed(e.to_string())),
+            }
+        });
+    };
+
+    let preview_src = form()
+        .theme_music_url
+        .and_then(|uri| app_state.read().media_src(&uri));
+
+    rsx! {
+        div {
+            class: "mb-6",
+            label {
+                class: "block text-white text-sm font-semibold mb-2",
+                "Theme music"
+            }
+
+            if let Some(src) = preview_src {
+                div {
+                    class: "space-y-2 mb-2",
+                    div {
+                        class: "flex items-center gap-2",
+                        audio {
+                            id: PREVIEW_ELEMENT_ID,
+                            class: "flex-1",
+                            controls: true,
+                            preload: "metadata",
+                            src: "{src}",
+                            onloadedmetadata: move |_| {
+                                let length = preview_element()
+                                    .map(|audio| audio.duration())
+                                    .filter(|d| d.is_finite());
+                                duration.set(length);
+                                if length.is_some() {
+                                    apply_trim();
+                                }
+                            },
+                        }
+                        button {
+                            class: "text-gray-400 hover:text-white transition-colors",
+                            title: "Remove theme music",
+                            onclick: move |_| {
+                                form.with_mut(|f| {
+                                    f.theme_music_url = None;
+                                    f.theme_music_trim = None;
+                                });
+                                start_text.set(String::new());
+                                end_text.set(String::new());
+                                trim_error.set(None);
+                            },
+                            "✕"
+                        }
+                    }
+
+                    div {
+                        class: "flex flex-wrap items-center gap-2 text-sm text-gray-400",
+                        span { "Play from" }
+                        input {
+                            class: "w-20 px-2 py-1 bg-gray-700 text-white rounded border border-gray-600 focus:border-white focus:outline-none",
+                            r#type: "text",
+                            placeholder: "0:00",
+                            value: "{start_text}",
+                            oninput: move |e| {
+                                start_text.set(e.value());
+                                apply_trim();
+                            },
+                        }
+                        button {
+                            class: "px-2 py-1 bg-gray-700 hover:bg-gray-600 text-white rounded text-xs",
+                            title: "Use the preview's current position",
+                            onclick: move |_| {
+                                if let Some(audio) = preview_element() {
+                                    start_text.set(format_timestamp(audio.current_time()));
+                                    apply_trim();
+                                }
+                            },
+                            "Mark"
+                        }
+                        span { "to" }
+                        input {
+                            class: "w-20 px-2 py-1 bg-gray-700 text-white rounded border border-gray-600 focus:border-white focus:outline-none",
+                            r#type: "text",
+                            placeholder: duration().map(format_timestamp).unwrap_or_else(|| "end".to_string()),
+                            value: "{end_text}",
+                            oninput: move |e| {
+                                end_text.set(e.value());
+                                apply_trim();
+                            },
+                        }
+                        button {
+                            class: "px-2 py-1 bg-gray-700 hover:bg-gray-600 text-white rounded text-xs",
+                            title: "Use the preview's current position",
+                            onclick: move |_| {
+                                if let Some(audio) = preview_element() {
+                                    end_text.set(format_timestamp(audio.current_time()));
+                                    apply_trim();
+                                }
+                            },
+                            "Mark"
+                        }
+                    }
+                    if let Some(message) = trim_error() {
+                        p { class: "text-red-400 text-xs", "{message}" }
+                    }
+                }
+            }
+
+            match state() {
+                UploadState::Idle => rsx! {
+                    label {
+                        class: "inline-block px-4 py-2 bg-gray-700 hover:bg-gray-600 text-white rounded-lg text-sm cursor-pointer transition-colors",
+                        if form().theme_music_url.is_some() { "Replace audio file" } else { "Attach an audio file" }
+                        input {
+                            class: "hidden",
+                            r#type: "file",
+                            accept: THEME_MUSIC_TYPES.join(","),
+                            onchange: move |e| upload(e.files()),
+                        }
+                    }
+                    p {
+                        class: "text-gray-500 text-xs mt-1",
+                        "MP3, M4A, Ogg, WebM, WAV or FLAC, up to {THEME_MUSIC_MAX_FILE_BYTES / (1024 * 1024)} MB"
+                    }
+                },
+                UploadState::Processing => rsx! {
+                    p { class: "text-gray-400 text-sm", "Reading file..." }
+                },
+                UploadState::Failed(message) => rsx! {
+                    p { class: "text-red-400 text-sm", "{message}" }
+                    button {
+                        class: "mt-1 text-gray-300 hover:text-white text-sm",
+                        onclick: move |_| state.set(UploadState::Idle),
+                        "Try another file"
+                    }
+                },
+            }
+        }
+    }
+}
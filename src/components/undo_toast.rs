@@ -1,12 +1,14 @@
 use dioxus::prelude::*;
 
 use crate::constants::*;
-use crate::models::Mnemon;
+use crate::models::{Impression, Mnemon};
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct PendingDelete {
     pub mnemon: Mnemon,
     pub original_idx: usize,
+    /// When the mnemon was last shown, put back on undo
+    pub impression: Option<Impression>,
 }
 
 const UNDO_PROGRESS_INTERVAL_MS: u32 = 50;
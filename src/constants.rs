@@ -24,6 +24,105 @@ pub const SEARCH_DEBOUNCE_MS: u32 = 300;
 
 pub const SEARCH_PAGE_SIZE: usize = 10;
 
+pub const SEARCH_SCROLL_THRESHOLD_PX: f64 = 48.0;
+
+pub const LIBRARY_SEARCH_LIMIT: usize = 5;
+
+pub const SEARCH_CACHE_CAPACITY: usize = 64;
+
+pub const SEARCH_CACHE_TTL_HOURS: i64 = 24;
+
+pub const SEARCH_CACHE_MAX_AGE_DAYS: i64 = 30;
+
+pub const METADATA_REFRESH_INTERVAL_DAYS: i64 = 30;
+
+pub const METADATA_REFRESH_BATCH_SIZE: usize = 10;
+
+pub const AUTO_MATCH_INTERVAL_DAYS: i64 = 7;
+
+pub const AUTO_MATCH_BATCH_SIZE: usize = 10;
+
+pub const AUTO_MATCH_POLL_MS: u32 = 5 * 60 * 1000;
+
+pub const AUTO_MATCH_MIN_SCORE: f32 = 1.0;
+
+pub const AUTO_MATCH_MARGIN: f32 = 0.2;
+
+pub const MATCH_CANDIDATE_MIN_SCORE: f32 = 0.5;
+
+pub const MATCH_REVIEW_CANDIDATES: usize = 5;
+
+/// Days after being shown until a mnemon is back to full resurfacing weight
+pub const RESURFACE_RECOVERY_DAYS: f64 = 7.0;
+
+/// Weight kept by a mnemon that was just shown
+pub const RESURFACE_MIN_RECOVERY: f64 = 0.05;
+
+/// Extra weight of the oldest memories (finished `RESURFACE_AGE_MAX_YEARS` or more ago)
+pub const RESURFACE_AGE_BOOST: f64 = 1.0;
+
+pub const RESURFACE_AGE_MAX_YEARS: f64 = 10.0;
+
+/// Weight multiplier of favorite mnemons
+pub const RESURFACE_FAVORITE_BOOST: f64 = 2.5;
+
+/// Extra weight of a just-added mnemon, fading over `RESURFACE_NEW_DAYS`
+pub const RESURFACE_NEW_BOOST: f64 = 1.5;
+
+pub const RESURFACE_NEW_DAYS: f64 = 14.0;
+
+/// Days either side of today that count as an anniversary "this week"
+pub const ANNIVERSARY_WEEK_DAYS: i64 = 3;
+
+/// Width / height of uploaded covers, matching the hero on a widescreen display
+pub const CUSTOM_COVER_ASPECT_RATIO: f64 = 16.0 / 9.0;
+
+pub const CUSTOM_COVER_MAX_WIDTH: u32 = 1920;
+
+pub const CUSTOM_COVER_JPEG_QUALITY: f64 = 0.85;
+
+pub const CUSTOM_COVER_MAX_FILE_BYTES: u64 = 25 * 1024 * 1024;
+
+pub const THEME_MUSIC_MAX_FILE_BYTES: u64 = 15 * 1024 * 1024;
+
+/// Audio types accepted as theme music (MP3, AAC/M4A, Ogg, Opus/WebM, WAV, FLAC)
+pub const THEME_MUSIC_TYPES: &[&str] = &[
+    "audio/mpeg",
+    "audio/mp4",
+    "audio/x-m4a",
+    "audio/aac",
+    "audio/ogg",
+    "audio/webm",
+    "audio/wav",
+    "audio/x-wav",
+    "audio/flac",
+];
+
+/// How often playback progress is read from the audio element
+pub const AUDIO_POLL_MS: u32 = 250;
+
+/// Interval between volume steps of a theme crossfade
+pub const THEME_FADE_STEP_MS: u32 = 30;
+
+/// Shortest segment a theme can be trimmed to
+pub const THEME_MUSIC_MIN_SEGMENT_SECS: f64 = 1.0;
+
+pub const DEFAULT_DISPLAY_LANGUAGE: &str = "en-US";
+
+pub const DISPLAY_LANGUAGES: &[(&str, &str)] = &[
+    ("en-US", "English"),
+    ("ja-JP", "日本語"),
+    ("ko-KR", "한국어"),
+    ("zh-CN", "中文"),
+    ("fr-FR", "Français"),
+    ("de-DE", "Deutsch"),
+    ("es-ES", "Español"),
+    ("it-IT", "Italiano"),
+    ("pt-BR", "Português"),
+];
+
+pub const MAX_ALTERNATE_TITLES: usize = 20;
+
 pub const MAX_FEELINGS: usize = 5;
 
 pub const FEELINGS: &[(&str, &str)] = &[
@@ -0,0 +1,189 @@
+//! "On this day" anniversaries
+//!
+//! A mnemon has an anniversary when the day it was finished comes round again in a
+//! later year. Exact matches (same day and month) are featured first in the hero;
+//! when there are none, anniversaries within `ANNIVERSARY_WEEK_DAYS` of today are
+//! featured, then any in the current month. A finish on 29 February falls on the
+//! 28th in other years.
+
+use chrono::{Datelike, NaiveDate};
+use uuid::Uuid;
+
+use crate::constants::ANNIVERSARY_WEEK_DAYS;
+use crate::models::Mnemon;
+
+/// How close to today an anniversary falls (closest first)
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum AnniversaryMatch {
+    Day,
+    Week,
+    Month,
+}
+
+impl AnniversaryMatch {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AnniversaryMatch::Day => "Today",
+            AnniversaryMatch::Week => "This week",
+            AnniversaryMatch::Month => "This month",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Anniversary {
+    pub mnemon_id: Uuid,
+    pub finished_on: NaiveDate,
+    pub years_ago: i32,
+    pub matched: AnniversaryMatch,
+}
+
+impl Anniversary {
+    /// E.g. "5 years ago today you finished Persona 4"
+    pub fn headline(&self, title: &str) -> String {
+        let ago = match self.years_ago {
+            1 => "A year ago".to_string(),
+            years => format!("{} years ago", years),
+        };
+        let when = match self.matched {
+            AnniversaryMatch::Day => "today",
+            AnniversaryMatch::Week => "this week",
+            AnniversaryMatch::Month => "this month",
+        };
+        format!("{} {} you finished {}", ago, when, title)
+    }
+}
+
+/// The finish date's anniversary in a given year
+fn anniversary_in(finished: NaiveDate, year: i32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, finished.month(), finished.day())
+        .or_else(|| NaiveDate::from_ymd_opt(year, finished.month(), 28))
+}
+
+/// How close a finish date's nearest anniversary is to today, and how many years
+/// it marks (none for dates less than a year ago)
+pub fn anniversary_match(finished: NaiveDate, today: NaiveDate) -> Option<(AnniversaryMatch, i32)> {
+    // The nearest anniversary can be in the previous or next year around new year
+    let (date, years_ago) = (today.year() - 1..=today.year() + 1)
+        .filter_map(|year| Some((anniversary_in(finished, year)?, year - finished.year())))
+        .filter(|(_, years_ago)| *years_ago >= 1)
+        .min_by_key(|(date, _)| (*date - today).num_days().abs())?;
+
+    let days_away = (date - today).num_days().abs();
+    let matched = if days_away == 0 {
+        AnniversaryMatch::Day
+    } else if days_away <= ANNIVERSARY_WEEK_DAYS {
+        AnniversaryMatch::Week
+    } else if date.year() == today.year() && date.month() == today.month() {
+        AnniversaryMatch::Month
+    } else {
+        return None;
+    };
+    Some((matched, years_ago))
+}
+
+/// Every anniversary around today, closest match first, then oldest memories first
+pub fn anniversaries(mnemons: &[Mnemon], today: NaiveDate) -> Vec<Anniversary> {
+    let mut anniversaries: Vec<Anniversary> = mnemons
+        .iter()
+        .filter_map(|mnemon| {
+            let finished_on = mnemon.finished_on()?;
+            let (matched, years_ago) = anniversary_match(finished_on, today)?;
+            Some(Anniversary {
+                mnemon_id: mnemon.id,
+                finished_on,
+                years_ago,
+                matched,
+            })
+        })
+        .collect();
+    anniversaries.sort_by(|a, b| {
+        a.matched
+            .cmp(&b.matched)
+            .then(b.years_ago.cmp(&a.years_ago))
+    });
+    anniversaries
+}
+
+/// Anniversaries the hero puts first: the closest kind of match there is
+pub fn featured_anniversaries(mnemons: &[Mnemon], today: NaiveDate) -> Vec<Anniversary> {
+    let all = anniversaries(mnemons, today);
+    let Some(best) = all.first().map(|a| a.matched) else {
+        return Vec::new();
+    };
+    all.into_iter().filter(|a| a.matched == best).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    fn finished(text: &str) -> Mnemon {
+        Mnemon::new(
+            Uuid::new_v4(),
+            Some(text.to_string()),
+            Vec::new(),
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn test_anniversary_match() {
+        let today = date("2024-03-15");
+        assert_eq!(
+            anniversary_match(date("2019-03-15"), today),
+            Some((AnniversaryMatch::Day, 5))
+        );
+        assert_eq!(
+            anniversary_match(date("2020-03-13"), today),
+            Some((AnniversaryMatch::Week, 4))
+        );
+        assert_eq!(
+            anniversary_match(date("2010-03-30"), today),
+            Some((AnniversaryMatch::Month, 14))
+        );
+        assert_eq!(anniversary_match(date("2010-05-15"), today), None);
+        // Not a year yet
+        assert_eq!(anniversary_match(date("2024-03-15"), today), None);
+
+        // Across new year, and a leap day in a common year
+        assert_eq!(
+            anniversary_match(date("2015-12-30"), date("2024-01-02")),
+            Some((AnniversaryMatch::Week, 8))
+        );
+        assert_eq!(
+            anniversary_match(date("2020-02-29"), date("2023-02-28")),
+            Some((AnniversaryMatch::Day, 3))
+        );
+    }
+
+    #[test]
+    fn test_featured_anniversaries_fall_back_to_wider_matches() {
+        let today = date("2024-03-15");
+        let week = finished("2020-03-17");
+        let month = finished("2012-03-02");
+        let exact = finished("2019-03-15");
+        let none = finished("2019-07-01");
+
+        let mnemons = vec![week.clone(), month.clone(), none.clone()];
+        let featured = featured_anniversaries(&mnemons, today);
+        assert_eq!(featured.len(), 1);
+        assert_eq!(featured[0].mnemon_id, week.id);
+
+        let mnemons = vec![week, month.clone(), none, exact.clone()];
+        let featured = featured_anniversaries(&mnemons, today);
+        assert_eq!(featured.len(), 1);
+        assert_eq!(
+            featured[0].headline("Persona 4"),
+            "5 years ago today you finished Persona 4"
+        );
+
+        let all = anniversaries(&mnemons, today);
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[2].mnemon_id, month.id);
+    }
+}
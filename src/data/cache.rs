@@ -0,0 +1,251 @@
+//! Search result cache
+//!
+//! Provider search pages are cached per (provider, work type, display language,
+//! normalized query, page) in two tiers:
+//! - An in-memory LRU shared by every `SearchService`, so repeat queries are instant
+//! - IndexedDB, so results survive reloads and remain available offline
+//!
+//! Entries younger than `SEARCH_CACHE_TTL_HOURS` are served instead of calling the
+//! provider. Older entries are only used as a stale fallback when the provider
+//! cannot be reached.
+
+use crate::constants::{SEARCH_CACHE_CAPACITY, SEARCH_CACHE_MAX_AGE_DAYS, SEARCH_CACHE_TTL_HOURS};
+use crate::models::WorkType;
+use crate::storage::{self, StoredSearchPage};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use tracing::info;
+
+/// Whether expired entries have been pruned from IndexedDB this session
+static PRUNED: AtomicBool = AtomicBool::new(false);
+
+/// Normalize a query so trivially different spellings share a cache entry
+pub fn normalize_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Identifies one cached page of search results
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchCacheKey {
+    /// Provider source (e.g. "tmdb", "rawg")
+    pub provider: &'static str,
+    /// Work type that was searched
+    pub work_type: WorkType,
+    /// Display language the results were requested in
+    pub language: String,
+    /// Normalized query text
+    pub query: String,
+    /// Zero-indexed page
+    pub page: usize,
+}
+
+impl SearchCacheKey {
+    /// Create a key, normalizing the query
+    pub fn new(
+        provider: &'static str,
+        work_type: WorkType,
+        language: &str,
+        query: &str,
+        page: usize,
+    ) -> Self {
+        Self {
+            provider,
+            work_type,
+            language: language.to_string(),
+            query: normalize_query(query),
+            page,
+        }
+    }
+
+    /// Storage identifier for the key
+    pub fn id(&self) -> String {
+        format!(
+            "{}|{:?}|{}|{}|{}",
+            self.provider, self.work_type, self.language, self.query, self.page
+        )
+    }
+}
+
+/// Check if a cached page is still within its time-to-live
+pub fn is_fresh(entry: &StoredSearchPage, now: DateTime<Utc>) -> bool {
+    now - entry.cached_at < Duration::hours(SEARCH_CACHE_TTL_HOURS)
+}
+
+/// Bounded least-recently-used list of cached search pages
+///
+/// The most recently used entry is kept at the front.
+#[derive(Debug)]
+pub struct SearchLru {
+    capacity: usize,
+    entries: VecDeque<StoredSearchPage>,
+}
+
+impl SearchLru {
+    /// Create an empty LRU holding at most `capacity` pages
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Get an entry by id, marking it as most recently used
+    pub fn get(&mut self, id: &str) -> Option<StoredSearchPage> {
+        let position = self.entries.iter().position(|e| e.id == id)?;
+        let entry = self.entries.remove(position)?;
+        self.entries.push_front(entry.clone());
+        Some(entry)
+    }
+
+    /// Insert or replace an entry, evicting the least recently used if full
+    pub fn insert(&mut self, entry: StoredSearchPage) {
+        self.entries.retain(|e| e.id != entry.id);
+        self.entries.push_front(entry);
+        self.entries.truncate(self.capacity);
+    }
+
+    /// Number of cached pages
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Two-tier search cache (memory LRU backed by IndexedDB)
+#[derive(Clone)]
+pub struct SearchCache {
+    memory: Arc<Mutex<SearchLru>>,
+}
+
+impl SearchCache {
+    /// Get the cache shared across the whole app
+    pub fn shared() -> Self {
+        static SHARED: OnceLock<Arc<Mutex<SearchLru>>> = OnceLock::new();
+        let memory = SHARED
+            .get_or_init(|| Arc::new(Mutex::new(SearchLru::new(SEARCH_CACHE_CAPACITY))))
+            .clone();
+        Self { memory }
+    }
+
+    /// Look up an entry in memory, then IndexedDB (promoting it into memory)
+    async fn lookup(&self, key: &SearchCacheKey) -> Option<StoredSearchPage> {
+        let id = key.id();
+        let in_memory = self
+            .memory
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&id);
+        if in_memory.is_some() {
+            return in_memory;
+        }
+
+        match storage::load_search_page(&id).await {
+            Ok(Some(entry)) => {
+                self.memory
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .insert(entry.clone());
+                Some(entry)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                info!("Failed to read search cache: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Get a cached page that is still within its time-to-live
+    pub async fn get_fresh(&self, key: &SearchCacheKey) -> Option<StoredSearchPage> {
+        self.lookup(key)
+            .await
+            .filter(|entry| is_fresh(entry, Utc::now()))
+    }
+
+    /// Get a cached page regardless of age (offline fallback)
+    pub async fn get_stale(&self, key: &SearchCacheKey) -> Option<StoredSearchPage> {
+        self.lookup(key).await
+    }
+
+    /// Store a freshly fetched page in both tiers
+    pub async fn put(&self, entry: StoredSearchPage) {
+        self.memory
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(entry.clone());
+
+        if let Err(e) = storage::save_search_page(&entry).await {
+            info!("Failed to persist search cache entry: {}", e);
+        }
+
+        // Drop long-expired pages once per session
+        if !PRUNED.swap(true, Ordering::Relaxed) {
+            let cutoff = Utc::now() - Duration::days(SEARCH_CACHE_MAX_AGE_DAYS);
+            if let Err(e) = storage::prune_search_pages(cutoff).await {
+                info!("Failed to prune search cache: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, cached_at: DateTime<Utc>) -> StoredSearchPage {
+        StoredSearchPage {
+            id: id.to_string(),
+            results: Vec::new(),
+            total_count: 0,
+            page: 0,
+            has_next_page: false,
+            cached_at,
+        }
+    }
+
+    #[test]
+    fn test_key_normalizes_query() {
+        let a = SearchCacheKey::new("tmdb", WorkType::Movie, "en-US", "  Spirited   AWAY ", 0);
+        let b = SearchCacheKey::new("tmdb", WorkType::Movie, "en-US", "spirited away", 0);
+        assert_eq!(a.id(), b.id());
+
+        let other_page = SearchCacheKey::new("tmdb", WorkType::Movie, "en-US", "spirited away", 1);
+        assert_ne!(a.id(), other_page.id());
+
+        let other_language =
+            SearchCacheKey::new("tmdb", WorkType::Movie, "ja-JP", "spirited away", 0);
+        assert_ne!(a.id(), other_language.id());
+    }
+
+    #[test]
+    fn test_lru_evicts_least_recently_used() {
+        let now = Utc::now();
+        let mut lru = SearchLru::new(2);
+        lru.insert(entry("a", now));
+        lru.insert(entry("b", now));
+
+        // Touch "a" so "b" becomes the eviction candidate
+        assert!(lru.get("a").is_some());
+        lru.insert(entry("c", now));
+
+        assert_eq!(lru.len(), 2);
+        assert!(lru.get("b").is_none());
+        assert!(lru.get("a").is_some());
+        assert!(lru.get("c").is_some());
+    }
+
+    #[test]
+    fn test_freshness_respects_ttl() {
+        let now = Utc::now();
+        assert!(is_fresh(&entry("a", now - Duration::minutes(5)), now));
+        assert!(!is_fresh(
+            &entry("a", now - Duration::hours(SEARCH_CACHE_TTL_HOURS + 1)),
+            now
+        ));
+    }
+}
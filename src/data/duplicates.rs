@@ -0,0 +1,190 @@
+//! Duplicate work detection
+//!
+//! The same film can end up in the library more than once, e.g. typed in by hand
+//! while offline and later added again from TMDB. Two works are flagged as
+//! duplicates when they:
+//! - Have the same work type
+//! - Share a title (ignoring case, punctuation, accents and leading articles)
+//! - Were released at most a year apart (or one of them has no year)
+//!
+//! Works that share a provider or external id (e.g. the same IMDb id) are always
+//! flagged. Works linked to different entries of the same provider are otherwise never
+//! flagged, since the provider already tells them apart (e.g. a remake with the same
+//! title).
+
+use std::collections::HashMap;
+
+use crate::data::query::title_similarity;
+use crate::models::{Mnemon, Work};
+use uuid::Uuid;
+
+/// Check if two works look like the same work
+pub fn is_duplicate(a: &Work, b: &Work) -> bool {
+    if a.id == b.id
+        || a.work_type != b.work_type
+        || a.distinct_from.contains(&b.id)
+        || b.distinct_from.contains(&a.id)
+    {
+        return false;
+    }
+    if a.shares_id_with(b) {
+        return true;
+    }
+    if let (Some(a_ref), Some(b_ref)) = (&a.provider_ref, &b.provider_ref) {
+        if a_ref.provider_source == b_ref.provider_source {
+            return false;
+        }
+    }
+    if let (Some(a_year), Some(b_year)) = (a.release_year, b.release_year) {
+        if a_year.abs_diff(b_year) > 1 {
+            return false;
+        }
+    }
+    a.titles().iter().any(|a_title| {
+        b.titles()
+            .iter()
+            .any(|b_title| title_similarity(a_title, b_title) >= 1.0)
+    })
+}
+
+/// Order a pair so the suggested survivor comes first
+///
+/// Provider-backed works win over manual ones, then the older work wins.
+fn survivor_first(a: &Work, b: &Work) -> (Work, Work) {
+    let a_first = match (a.provider_ref.is_some(), b.provider_ref.is_some()) {
+        (true, false) => true,
+        (false, true) => false,
+        _ => a.created_at <= b.created_at,
+    };
+    if a_first {
+        (a.clone(), b.clone())
+    } else {
+        (b.clone(), a.clone())
+    }
+}
+
+/// Pairs of duplicate works in the library, each with the suggested survivor first
+pub fn find_duplicates(works: &[Work]) -> Vec<(Work, Work)> {
+    let mut pairs = Vec::new();
+    for (i, a) in works.iter().enumerate() {
+        for b in &works[i + 1..] {
+            if is_duplicate(a, b) {
+                pairs.push(survivor_first(a, b));
+            }
+        }
+    }
+    pairs
+}
+
+/// Fold loaded records that belong to merged works into their survivors
+///
+/// A merged work can come back from an older backup or a failed delete; it is dropped
+/// and its mnemons are pointed at the work it was merged into. Returns the ids of the
+/// dropped works and the mnemons that moved.
+pub fn reconcile_merged(works: &mut Vec<Work>, mnemons: &mut [Mnemon]) -> (Vec<Uuid>, Vec<Mnemon>) {
+    let survivors: HashMap<Uuid, Uuid> = works
+        .iter()
+        .flat_map(|w| w.merged_ids.iter().map(move |id| (*id, w.id)))
+        .collect();
+    if survivors.is_empty() {
+        return (Vec::new(), Vec::new());
+    }
+
+    let mut dropped = Vec::new();
+    works.retain(|w| {
+        let merged = survivors.contains_key(&w.id);
+        if merged {
+            dropped.push(w.id);
+        }
+        !merged
+    });
+
+    let mut moved = Vec::new();
+    for mnemon in mnemons.iter_mut() {
+        if let Some(survivor) = survivors.get(&mnemon.work_id) {
+            mnemon.work_id = *survivor;
+            moved.push(mnemon.clone());
+        }
+    }
+    (dropped, moved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_support::manual;
+    use crate::models::{ProviderRef, WorkType};
+
+    fn linked(source: &str, id: &str, title: &str, year: Option<u16>) -> Work {
+        Work::from_provider(
+            WorkType::Movie,
+            title.to_string(),
+            year,
+            None,
+            None,
+            ProviderRef::new(source, id),
+        )
+    }
+
+    #[test]
+    fn test_detects_duplicates_across_origins() {
+        let typed = manual("the thing", None);
+        let tmdb = linked("tmdb", "1091", "The Thing", Some(1982));
+        let remake = linked("tmdb", "60935", "The Thing", Some(2011));
+        let anilist = linked("anilist", "7", "Thing", Some(1983));
+
+        assert!(is_duplicate(&typed, &tmdb));
+        assert!(is_duplicate(&tmdb, &anilist));
+        // Different entries of one provider, or years far apart, are different works
+        assert!(!is_duplicate(&tmdb, &remake));
+        assert!(!is_duplicate(&anilist, &remake));
+
+        let mut game = manual("The Thing", Some(1982));
+        game.work_type = WorkType::Game;
+        assert!(!is_duplicate(&game, &tmdb));
+
+        // A shared IMDb id wins over differing titles
+        let mut imdb = linked("anilist", "1", "Yuusei kara no Buttai X", None);
+        imdb.add_external_id(ProviderRef::new("imdb", "tt0084787"));
+        let mut tmdb_with_imdb = tmdb.clone();
+        tmdb_with_imdb.add_external_id(ProviderRef::new("imdb", "tt0084787"));
+        assert!(is_duplicate(&tmdb_with_imdb, &imdb));
+
+        let mut kept_apart = typed.clone();
+        kept_apart.distinct_from.push(tmdb.id);
+        assert!(!is_duplicate(&tmdb, &kept_apart));
+    }
+
+    #[test]
+    fn test_find_duplicates_suggests_provider_work_as_survivor() {
+        let typed = manual("Spirited Away", Some(2001));
+        let mut tmdb = linked("tmdb", "129", "Sen to Chihiro", Some(2001));
+        tmdb.add_alternate_title("Spirited Away".to_string());
+        let other = manual("Totoro", None);
+
+        let pairs = find_duplicates(&[typed.clone(), other, tmdb.clone()]);
+
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].0.id, tmdb.id);
+        assert_eq!(pairs[0].1.id, typed.id);
+    }
+
+    #[test]
+    fn test_reconcile_folds_merged_records_into_survivor() {
+        let stale = manual("Dune", Some(1984));
+        let mut survivor = linked("tmdb", "841", "Dune", Some(1984));
+        survivor.absorb(&stale, &[]);
+        let mut works = vec![stale.clone(), survivor.clone()];
+        let mut mnemons = vec![
+            Mnemon::new(stale.id, None, Vec::new(), Vec::new()),
+            Mnemon::new(survivor.id, None, Vec::new(), Vec::new()),
+        ];
+
+        let (dropped, moved) = reconcile_merged(&mut works, &mut mnemons);
+
+        assert_eq!(dropped, vec![stale.id]);
+        assert_eq!(works.len(), 1);
+        assert_eq!(moved.len(), 1);
+        assert!(mnemons.iter().all(|m| m.work_id == survivor.id));
+    }
+}
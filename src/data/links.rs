@@ -0,0 +1,133 @@
+//! Provider page links
+//!
+//! Recognizes TMDB, RAWG and IMDb page URLs pasted into the title field so the
+//! work can be looked up directly instead of searched for:
+//! - `themoviedb.org/movie/129-spirited-away` and `themoviedb.org/tv/1396`
+//! - `rawg.io/games/the-witcher-3-wild-hunt`
+//! - `imdb.com/title/tt0245429` (resolved through TMDB's external id lookup)
+
+use crate::models::WorkType;
+
+/// A work identified by a provider page URL
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProviderLink {
+    /// TMDB movie or TV id
+    Tmdb { work_type: WorkType, id: String },
+    /// RAWG game slug or id
+    Rawg { slug: String },
+    /// IMDb title id (e.g. "tt0245429")
+    Imdb { id: String },
+}
+
+impl ProviderLink {
+    /// Provider name for status messages
+    pub fn provider_name(&self) -> &'static str {
+        match self {
+            ProviderLink::Tmdb { .. } => "TMDB",
+            ProviderLink::Rawg { .. } => "RAWG",
+            ProviderLink::Imdb { .. } => "IMDb",
+        }
+    }
+}
+
+/// Parse a pasted provider page URL
+///
+/// The scheme and `www.`/`m.` prefixes are optional; query strings, fragments and
+/// trailing path segments (e.g. `/season/1`) are ignored.
+pub fn parse_provider_url(input: &str) -> Option<ProviderLink> {
+    let trimmed = input.trim();
+    if trimmed.contains(char::is_whitespace) {
+        return None;
+    }
+    let without_scheme = trimmed
+        .strip_prefix("https://")
+        .or_else(|| trimmed.strip_prefix("http://"))
+        .unwrap_or(trimmed);
+    let (host, path) = without_scheme.split_once('/')?;
+    let host = host.to_lowercase();
+    let host = host
+        .strip_prefix("www.")
+        .or_else(|| host.strip_prefix("m."))
+        .unwrap_or(&host);
+
+    // Drop query strings and fragments, then split the path
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    match (host, segments.as_slice()) {
+        ("themoviedb.org", [kind, slug, ..]) => {
+            let work_type = match *kind {
+                "movie" => WorkType::Movie,
+                "tv" => WorkType::TvAnime,
+                _ => return None,
+            };
+            // "129-spirited-away" -> "129"
+            let id: String = slug.chars().take_while(|c| c.is_ascii_digit()).collect();
+            (!id.is_empty()).then_some(ProviderLink::Tmdb { work_type, id })
+        }
+        ("rawg.io", ["games", slug, ..]) => Some(ProviderLink::Rawg {
+            slug: slug.to_string(),
+        }),
+        ("imdb.com", ["title", id, ..]) => {
+            let valid =
+                id.len() > 2 && id.starts_with("tt") && id[2..].chars().all(|c| c.is_ascii_digit());
+            valid.then(|| ProviderLink::Imdb { id: id.to_string() })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_provider_urls() {
+        assert_eq!(
+            parse_provider_url("https://www.themoviedb.org/movie/129-spirited-away?language=ja"),
+            Some(ProviderLink::Tmdb {
+                work_type: WorkType::Movie,
+                id: "129".to_string()
+            })
+        );
+        assert_eq!(
+            parse_provider_url("themoviedb.org/tv/1396/season/1"),
+            Some(ProviderLink::Tmdb {
+                work_type: WorkType::TvAnime,
+                id: "1396".to_string()
+            })
+        );
+        assert_eq!(
+            parse_provider_url(" https://rawg.io/games/the-witcher-3-wild-hunt "),
+            Some(ProviderLink::Rawg {
+                slug: "the-witcher-3-wild-hunt".to_string()
+            })
+        );
+        assert_eq!(
+            parse_provider_url("https://m.imdb.com/title/tt0245429/?ref_=nv_sr_1"),
+            Some(ProviderLink::Imdb {
+                id: "tt0245429".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_ignores_titles_and_other_pages() {
+        assert_eq!(parse_provider_url("Spirited Away"), None);
+        assert_eq!(parse_provider_url("Fate/Zero"), None);
+        assert_eq!(
+            parse_provider_url("https://www.themoviedb.org/person/1"),
+            None
+        );
+        assert_eq!(
+            parse_provider_url("https://www.themoviedb.org/movie/"),
+            None
+        );
+        assert_eq!(parse_provider_url("https://rawg.io/creators/1"), None);
+        assert_eq!(
+            parse_provider_url("https://www.imdb.com/name/nm0000001"),
+            None
+        );
+        assert_eq!(parse_provider_url("https://example.com/movie/129"), None);
+    }
+}
@@ -0,0 +1,206 @@
+//! Background matching of manual works to provider entries
+//!
+//! Works added by hand (typically because the provider was offline or not yet
+//! configured) are searched for again once providers are reachable:
+//! - A single clear match (same title, same year give or take one) is linked
+//!   automatically
+//! - Several plausible matches are stored on the work as candidates for the user
+//!   to review
+//! - Nothing plausible leaves the work alone until `AUTO_MATCH_INTERVAL_DAYS` pass
+//!
+//! Works the user dismissed from the review inbox are never auto-matched again;
+//! "Find online match" in the details view still works for them.
+
+use crate::constants::{
+    AUTO_MATCH_BATCH_SIZE, AUTO_MATCH_INTERVAL_DAYS, AUTO_MATCH_MARGIN, AUTO_MATCH_MIN_SCORE,
+    MATCH_CANDIDATE_MIN_SCORE, MATCH_REVIEW_CANDIDATES,
+};
+use crate::data::query::{rank_results, relevance, ParsedQuery};
+use crate::data::{SearchService, SearchStatus};
+use crate::models::{SearchResult, Work, WorkType};
+use chrono::{DateTime, Duration, Utc};
+use tracing::info;
+use uuid::Uuid;
+
+/// What a provider search turned up for a manual work
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchOutcome {
+    /// One clear match, safe to link without asking
+    Confident(SearchResult),
+    /// Several plausible matches for the user to choose from
+    Ambiguous(Vec<SearchResult>),
+    /// Nothing plausible
+    NoMatch,
+}
+
+/// Query used to rank candidates for a work
+fn work_query(work: &Work) -> ParsedQuery {
+    ParsedQuery {
+        text: work.title_en.clone(),
+        year: work.release_year,
+        ..Default::default()
+    }
+}
+
+/// Decide how confidently search results match a manual work
+pub fn classify_matches(work: &Work, results: Vec<SearchResult>) -> MatchOutcome {
+    let query = work_query(work);
+    let candidates: Vec<(f32, SearchResult)> = rank_results(results, &query)
+        .into_iter()
+        .filter(|r| r.work_type == work.work_type)
+        .map(|r| (relevance(&r, &query), r))
+        .filter(|(score, _)| *score >= MATCH_CANDIDATE_MIN_SCORE)
+        .take(MATCH_REVIEW_CANDIDATES)
+        .collect();
+
+    let Some((top_score, top)) = candidates.first() else {
+        return MatchOutcome::NoMatch;
+    };
+    let runner_up = candidates.get(1).map(|(score, _)| *score).unwrap_or(0.0);
+    // Release dates differ by region, so a year off by one still agrees
+    let year_agrees = match work.release_year {
+        Some(year) => top.year.is_some_and(|y| y.abs_diff(year) <= 1),
+        None => true,
+    };
+
+    if *top_score >= AUTO_MATCH_MIN_SCORE
+        && top_score - runner_up >= AUTO_MATCH_MARGIN
+        && year_agrees
+    {
+        MatchOutcome::Confident(top.clone())
+    } else {
+        MatchOutcome::Ambiguous(candidates.into_iter().map(|(_, r)| r).collect())
+    }
+}
+
+/// Check if a manual work should be searched for in the background
+pub fn needs_matching(work: &Work, now: DateTime<Utc>) -> bool {
+    if work.provider_ref.is_some() || work.match_dismissed || !work.match_candidates.is_empty() {
+        return false;
+    }
+    match work.match_checked_at {
+        Some(last) => now - last >= Duration::days(AUTO_MATCH_INTERVAL_DAYS),
+        None => true,
+    }
+}
+
+/// Store a non-confident outcome on the work (candidates to review, or just the check time)
+pub fn record_outcome(work: &mut Work, outcome: &MatchOutcome, now: DateTime<Utc>) {
+    work.match_checked_at = Some(now);
+    if let MatchOutcome::Ambiguous(candidates) = outcome {
+        work.match_candidates = candidates.clone();
+    }
+}
+
+/// Search providers for a batch of manual works that are due
+///
+/// Works whose provider isn't configured are left for later; a network error or
+/// rate limit stops the batch so the remaining works are retried on the next pass.
+pub async fn auto_match_manual_works(
+    service: &SearchService,
+    works: Vec<Work>,
+) -> Vec<(Uuid, MatchOutcome)> {
+    let now = Utc::now();
+    let due: Vec<Work> = works
+        .into_iter()
+        .filter(|w| needs_matching(w, now))
+        .filter(|w| match w.work_type {
+            WorkType::Movie | WorkType::TvAnime => service.is_tmdb_configured(),
+            WorkType::Game => service.is_rawg_configured(),
+        })
+        .take(AUTO_MATCH_BATCH_SIZE)
+        .collect();
+
+    let mut outcomes = Vec::new();
+    for work in due {
+        let response = service
+            .search(&work.title_en, work.work_type.clone(), 0)
+            .await;
+        match response.status {
+            SearchStatus::Success => {
+                let outcome = classify_matches(&work, response.results);
+                info!("Auto-match for {}: {:?}", work.title_en, outcome);
+                outcomes.push((work.id, outcome));
+            }
+            SearchStatus::NetworkError(_)
+            | SearchStatus::RateLimited { .. }
+            | SearchStatus::StaleCache { .. } => {
+                info!("Stopping auto-match: {:?}", response.status);
+                break;
+            }
+            status => info!("Skipping auto-match for {}: {:?}", work.title_en, status),
+        }
+    }
+
+    outcomes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_support::{manual, result};
+
+    #[test]
+    fn test_clear_match_is_confident() {
+        let outcome = classify_matches(
+            &manual("The Thing", Some(1982)),
+            vec![
+                result("2", "The Thing", Some(2011)),
+                result("1", "The Thing", Some(1982)),
+                result("3", "The Thing Below", Some(2023)),
+            ],
+        );
+        assert_eq!(
+            outcome,
+            MatchOutcome::Confident(result("1", "The Thing", Some(1982)))
+        );
+    }
+
+    #[test]
+    fn test_remakes_without_a_year_need_review() {
+        let outcome = classify_matches(
+            &manual("The Thing", None),
+            vec![
+                result("1", "The Thing", Some(1982)),
+                result("2", "The Thing", Some(2011)),
+                result("4", "Unrelated", Some(1999)),
+            ],
+        );
+        let MatchOutcome::Ambiguous(candidates) = outcome else {
+            panic!("expected candidates to review");
+        };
+        assert_eq!(candidates.len(), 2);
+    }
+
+    #[test]
+    fn test_wrong_year_or_no_results_is_not_confident() {
+        let wrong_year = classify_matches(
+            &manual("Dune", Some(1984)),
+            vec![result("1", "Dune", Some(2021))],
+        );
+        assert!(matches!(wrong_year, MatchOutcome::Ambiguous(_)));
+
+        let nothing = classify_matches(&manual("Dune", None), vec![result("9", "Tetris", None)]);
+        assert_eq!(nothing, MatchOutcome::NoMatch);
+    }
+
+    #[test]
+    fn test_needs_matching_respects_dismissal_and_interval() {
+        let now = Utc::now();
+        let mut work = manual("Dune", None);
+        assert!(needs_matching(&work, now));
+
+        record_outcome(&mut work, &MatchOutcome::NoMatch, now);
+        assert!(!needs_matching(&work, now + Duration::days(1)));
+        assert!(needs_matching(
+            &work,
+            now + Duration::days(AUTO_MATCH_INTERVAL_DAYS)
+        ));
+
+        work.match_dismissed = true;
+        assert!(!needs_matching(
+            &work,
+            now + Duration::days(AUTO_MATCH_INTERVAL_DAYS)
+        ));
+    }
+}
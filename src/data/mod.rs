@@ -5,16 +5,55 @@
 //! - Movies: TMDB API
 //! - TV/Anime: TMDB API
 //! - Games: RAWG API
+//!
+//! Searches can also fan out across every work type at once, producing one
+//! `SearchGroup` per type so the UI can render each group as it arrives.
+//!
+//! Queries are parsed for year and type hints, and each provider page is re-ranked
+//! by relevance before it is shown (see `query`).
+//!
+//! Provider pages are cached (see `cache`) and reused on repeat queries, or as a
+//! stale fallback when the provider cannot be reached.
+//!
+//! Saved works can also be re-fetched from their provider to pick up corrected
+//! metadata (see `refresh`), and works added by hand are matched to a provider
+//! entry in the background once providers are reachable (see `matching`).
+//! Works that ended up in the library twice can be found and merged (see
+//! `duplicates`). The hero's cycling order is weighted rather than uniformly
+//! shuffled (see `resurfacing`), with "on this day" anniversaries put first (see
+//! `anniversaries`), and can be narrowed by the user (see `slideshow`).
 
 #![allow(dead_code)]
 
+pub mod anniversaries;
+pub mod cache;
+pub mod duplicates;
 pub mod fixtures;
+pub mod links;
+pub mod matching;
+pub mod query;
+pub mod refresh;
+pub mod resurfacing;
+pub mod slideshow;
+#[cfg(test)]
+pub mod test_support;
+
+use cache::{SearchCache, SearchCacheKey};
+use links::ProviderLink;
+use query::{parse_query, rank_results, ParsedQuery};
 
 use crate::constants::SEARCH_PAGE_SIZE;
-use crate::models::{SearchResult, SearchResultsPage, WorkType};
+use crate::models::{
+    ProviderImage, ProviderRef, SearchResult, SearchResultsPage, WorkDetails, WorkType,
+};
 use crate::providers::rawg::RawgClient;
 use crate::providers::tmdb::TmdbClient;
-use crate::providers::{ProviderError, ProviderStatus};
+use crate::providers::{ProviderError, ProviderResult, ProviderStatus};
+use crate::settings;
+use crate::storage::StoredSearchPage;
+use chrono::Datelike;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
 use tracing::info;
 
@@ -27,10 +66,37 @@ pub enum SearchStatus {
     ProviderNotConfigured,
     /// Network error, using fallback or manual entry
     NetworkError(String),
-    /// API error (invalid key, rate limit, etc.)
+    /// API error (invalid key, bad request, etc.)
     ApiError { status: u16, message: String },
+    /// Provider is rate limiting requests
+    RateLimited { retry_after_secs: u64 },
     /// Using fixture data (for Games)
     UsingFixtures,
+    /// Provider unreachable; showing previously cached (possibly outdated) results
+    StaleCache {
+        message: String,
+        cached_at: chrono::DateTime<chrono::Utc>,
+    },
+}
+
+impl SearchStatus {
+    /// Why the provider could not be searched, if it couldn't
+    pub fn problem(&self) -> Option<String> {
+        match self {
+            SearchStatus::Success
+            | SearchStatus::UsingFixtures
+            | SearchStatus::StaleCache { .. } => None,
+            SearchStatus::ProviderNotConfigured => {
+                Some("Provider not configured. Add your API key in Settings.".to_string())
+            }
+            SearchStatus::NetworkError(message) => Some(format!("Network error: {}", message)),
+            SearchStatus::ApiError { status, .. } => Some(format!("API error ({})", status)),
+            SearchStatus::RateLimited { retry_after_secs } => Some(format!(
+                "Rate limit exceeded. Try again in {}s.",
+                retry_after_secs
+            )),
+        }
+    }
 }
 
 /// Result of a search operation
@@ -42,15 +108,58 @@ pub struct SearchResponse {
     pub status: SearchStatus,
     /// Total count (if known from API)
     pub total_count: Option<usize>,
+    /// Zero-indexed page these results belong to
+    pub page: usize,
+    /// Whether the provider has more results after this page
+    pub has_next_page: bool,
 }
 
 impl SearchResponse {
-    /// Create a successful response
-    pub fn success(results: Vec<SearchResult>, total_count: Option<usize>) -> Self {
+    /// Create a successful response from a provider results page
+    pub fn success(page: SearchResultsPage) -> Self {
+        Self {
+            status: SearchStatus::Success,
+            total_count: Some(page.total_count),
+            has_next_page: page.has_next_page(),
+            page: page.page,
+            results: page.results,
+        }
+    }
+
+    /// Create a response from a cached page that is still fresh
+    pub fn from_cache(entry: StoredSearchPage) -> Self {
         Self {
-            results,
             status: SearchStatus::Success,
-            total_count,
+            total_count: Some(entry.total_count),
+            has_next_page: entry.has_next_page,
+            page: entry.page,
+            results: entry.results,
+        }
+    }
+
+    /// Create a response serving an outdated cached page after a network error
+    pub fn stale(entry: StoredSearchPage, message: String) -> Self {
+        Self {
+            status: SearchStatus::StaleCache {
+                message,
+                cached_at: entry.cached_at,
+            },
+            total_count: Some(entry.total_count),
+            has_next_page: entry.has_next_page,
+            page: entry.page,
+            results: entry.results,
+        }
+    }
+
+    /// Convert a successful response into a cache entry
+    fn to_cache_entry(&self, key: &SearchCacheKey) -> StoredSearchPage {
+        StoredSearchPage {
+            id: key.id(),
+            results: self.results.clone(),
+            total_count: self.total_count.unwrap_or(self.results.len()),
+            page: self.page,
+            has_next_page: self.has_next_page,
+            cached_at: chrono::Utc::now(),
         }
     }
 
@@ -60,6 +169,8 @@ impl SearchResponse {
             results: Vec::new(),
             status: SearchStatus::ProviderNotConfigured,
             total_count: None,
+            page: 0,
+            has_next_page: false,
         }
     }
 
@@ -69,6 +180,8 @@ impl SearchResponse {
             results: Vec::new(),
             status: SearchStatus::NetworkError(message),
             total_count: None,
+            page: 0,
+            has_next_page: false,
         }
     }
 
@@ -78,6 +191,19 @@ impl SearchResponse {
             results: Vec::new(),
             status: SearchStatus::ApiError { status, message },
             total_count: None,
+            page: 0,
+            has_next_page: false,
+        }
+    }
+
+    /// Create a response for a rate-limited provider
+    pub fn rate_limited(retry_after_secs: u64) -> Self {
+        Self {
+            results: Vec::new(),
+            status: SearchStatus::RateLimited { retry_after_secs },
+            total_count: None,
+            page: 0,
+            has_next_page: false,
         }
     }
 
@@ -87,15 +213,121 @@ impl SearchResponse {
             results,
             status: SearchStatus::UsingFixtures,
             total_count: Some(total_count),
+            page: 0,
+            has_next_page: false,
         }
     }
 }
 
+/// Results for a single work type within a fan-out search
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchGroup {
+    /// The work type this group holds results for
+    pub work_type: WorkType,
+    /// Deduplicated results from the group's provider
+    pub results: Vec<SearchResult>,
+    /// Status of the group's search (`None` while the request is in flight)
+    pub status: Option<SearchStatus>,
+    /// Zero-indexed page of the most recently loaded results
+    pub page: usize,
+    /// Whether the provider has more pages to load
+    pub has_next_page: bool,
+    /// Whether a further page is currently being fetched
+    pub loading_more: bool,
+}
+
+impl SearchGroup {
+    /// Create a group whose search has not completed yet
+    pub fn pending(work_type: WorkType) -> Self {
+        Self {
+            work_type,
+            results: Vec::new(),
+            status: None,
+            page: 0,
+            has_next_page: false,
+            loading_more: false,
+        }
+    }
+
+    /// Build a completed group from a search response
+    pub fn from_response(work_type: WorkType, response: SearchResponse) -> Self {
+        Self {
+            work_type,
+            has_next_page: response.has_next_page,
+            page: response.page,
+            results: response.results,
+            status: Some(response.status),
+            loading_more: false,
+        }
+    }
+
+    /// Check if another page can be requested for this group
+    pub fn can_load_more(&self) -> bool {
+        self.has_next_page && !self.loading_more && !self.is_pending()
+    }
+
+    /// Append a further page, skipping results already shown in the group
+    ///
+    /// A failed page keeps the results loaded so far and stops pagination.
+    pub fn append_page(&mut self, response: SearchResponse) {
+        self.loading_more = false;
+        match response.status {
+            SearchStatus::Success
+            | SearchStatus::UsingFixtures
+            | SearchStatus::StaleCache { .. } => {
+                self.has_next_page = response.has_next_page;
+                self.page = response.page;
+                let mut results = std::mem::take(&mut self.results);
+                results.extend(response.results);
+                self.results = dedupe_results(results);
+            }
+            status => {
+                self.has_next_page = false;
+                self.status = Some(status);
+            }
+        }
+    }
+
+    /// Check if the group's search is still in flight
+    pub fn is_pending(&self) -> bool {
+        self.status.is_none()
+    }
+
+    /// Check if the group's search failed or could not run
+    pub fn has_error(&self) -> bool {
+        matches!(
+            self.status,
+            Some(SearchStatus::NetworkError(_))
+                | Some(SearchStatus::ApiError { .. })
+                | Some(SearchStatus::RateLimited { .. })
+                | Some(SearchStatus::ProviderNotConfigured)
+        )
+    }
+}
+
+/// Future resolving to one group of a fan-out search
+pub type GroupSearchFuture = Pin<Box<dyn Future<Output = SearchGroup>>>;
+
+/// Collapse results sharing the same provider identifier, keeping the first
+pub fn dedupe_results(results: Vec<SearchResult>) -> Vec<SearchResult> {
+    let mut unique: Vec<SearchResult> = Vec::with_capacity(results.len());
+    for result in results {
+        if !unique
+            .iter()
+            .any(|r| r.provider_ref.matches(&result.provider_ref))
+        {
+            unique.push(result);
+        }
+    }
+    unique
+}
+
 /// Unified search service that routes to appropriate providers
 #[derive(Clone)]
 pub struct SearchService {
     tmdb: Arc<TmdbClient>,
     rawg: Arc<RawgClient>,
+    cache: SearchCache,
 }
 
 impl SearchService {
@@ -104,17 +336,71 @@ impl SearchService {
         Self {
             tmdb: Arc::new(TmdbClient::new()),
             rawg: Arc::new(RawgClient::new()),
+            cache: SearchCache::shared(),
         }
     }
 
+    /// Provider source that serves a work type
+    fn provider_for(work_type: &WorkType) -> &'static str {
+        match work_type {
+            WorkType::Movie | WorkType::TvAnime => "tmdb",
+            WorkType::Game => "rawg",
+        }
+    }
+
+    /// Display language a provider's titles come back in (only TMDB localizes them)
+    pub fn title_language(provider_source: &str) -> Option<String> {
+        (provider_source == "tmdb").then(settings::display_language)
+    }
+
     /// Check if TMDB is configured
     pub fn is_tmdb_configured(&self) -> bool {
-        self.tmdb.status() == ProviderStatus::Available
+        self.tmdb.status() != ProviderStatus::NotConfigured
     }
 
     /// Check if RAWG is configured
     pub fn is_rawg_configured(&self) -> bool {
-        self.rawg.status() == ProviderStatus::Available
+        self.rawg.status() != ProviderStatus::NotConfigured
+    }
+
+    /// Fetch current metadata for a provider-backed work
+    ///
+    /// Details are never cached: a refresh is only useful if it reaches the provider.
+    pub async fn details(
+        &self,
+        provider_ref: &ProviderRef,
+        work_type: WorkType,
+    ) -> ProviderResult<WorkDetails> {
+        match provider_ref.provider_source.as_str() {
+            "tmdb" => {
+                self.tmdb
+                    .details(&provider_ref.provider_id, work_type)
+                    .await
+            }
+            "rawg" => self.rawg.details(&provider_ref.provider_id).await,
+            source => Err(ProviderError::Unavailable(format!(
+                "No details provider for {}",
+                source
+            ))),
+        }
+    }
+
+    /// Fetch the alternative images a provider offers for a work
+    ///
+    /// TMDB posters and backdrops, or RAWG screenshots.
+    pub async fn images(
+        &self,
+        provider_ref: &ProviderRef,
+        work_type: WorkType,
+    ) -> ProviderResult<Vec<ProviderImage>> {
+        match provider_ref.provider_source.as_str() {
+            "tmdb" => self.tmdb.images(&provider_ref.provider_id, work_type).await,
+            "rawg" => self.rawg.screenshots(&provider_ref.provider_id).await,
+            source => Err(ProviderError::Unavailable(format!(
+                "No images provider for {}",
+                source
+            ))),
+        }
     }
 
     /// Search for works by query and type
@@ -122,15 +408,127 @@ impl SearchService {
     /// Routes to the appropriate provider:
     /// - Movies/TV: TMDB API (if configured)
     /// - Games: RAWG API (if configured)
+    ///
+    /// Fresh cached pages are returned without calling the provider; on a network
+    /// error a cached page of any age is returned flagged as stale.
     pub async fn search(&self, query: &str, work_type: WorkType, page: usize) -> SearchResponse {
+        let provider = Self::provider_for(&work_type);
+        // RAWG pages are shared across languages
+        let language = Self::title_language(provider).unwrap_or_default();
+        let key = SearchCacheKey::new(provider, work_type.clone(), &language, query, page);
+        let parsed = parse_query(query, chrono::Utc::now().year());
+        let cacheable = !parsed.text.is_empty();
+
+        if cacheable {
+            if let Some(entry) = self.cache.get_fresh(&key).await {
+                info!("Serving '{}' from search cache", key.id());
+                return SearchResponse::from_cache(entry);
+            }
+        }
+
+        let mut response = self.fetch(&parsed, work_type.clone(), page).await;
+        let mut ranked_by = parsed.clone();
+
+        // A bare trailing year may be part of the title ("Wonder Woman 1984")
+        if response.results.is_empty() && response.status == SearchStatus::Success {
+            if let Some(literal) = parsed.literal() {
+                info!(
+                    "No results in {:?}, retrying '{}'",
+                    parsed.year, literal.text
+                );
+                response = self.fetch(&literal, work_type, page).await;
+                ranked_by = literal;
+            }
+        }
+        response.results = rank_results(dedupe_results(response.results), &ranked_by);
+
+        if cacheable {
+            match response.status {
+                SearchStatus::Success => self.cache.put(response.to_cache_entry(&key)).await,
+                SearchStatus::NetworkError(ref message) => {
+                    if let Some(entry) = self.cache.get_stale(&key).await {
+                        info!("Network error, serving stale cache for '{}'", key.id());
+                        return SearchResponse::stale(entry, message.clone());
+                    }
+                }
+                SearchStatus::RateLimited { retry_after_secs } => {
+                    if let Some(entry) = self.cache.get_stale(&key).await {
+                        info!("Rate limited, serving stale cache for '{}'", key.id());
+                        let message = format!("rate limited for {}s", retry_after_secs);
+                        return SearchResponse::stale(entry, message);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        response
+    }
+
+    /// Look up the single work a pasted provider link points to
+    pub async fn resolve_link(&self, link: &ProviderLink) -> ProviderResult<SearchResult> {
+        match link {
+            ProviderLink::Tmdb { work_type, id } => self
+                .tmdb
+                .details(id, work_type.clone())
+                .await
+                .map(SearchResult::from),
+            ProviderLink::Rawg { slug } => self.rawg.details(slug).await.map(SearchResult::from),
+            ProviderLink::Imdb { id } => self.tmdb.find_by_imdb_id(id).await,
+        }
+    }
+
+    /// Query the provider for a work type with the parsed text and year
+    async fn fetch(&self, query: &ParsedQuery, work_type: WorkType, page: usize) -> SearchResponse {
         match work_type {
-            WorkType::Movie | WorkType::TvAnime => self.search_tmdb(query, work_type, page).await,
-            WorkType::Game => self.search_rawg(query, page).await,
+            WorkType::Movie | WorkType::TvAnime => {
+                self.search_tmdb(&query.text, query.year, work_type, page)
+                    .await
+            }
+            WorkType::Game => self.search_rawg(&query.text, query.year, page).await,
         }
     }
 
+    /// Search a single work type and wrap the response as a group
+    pub async fn search_group(&self, query: &str, work_type: WorkType, page: usize) -> SearchGroup {
+        let response = self.search(query, work_type.clone(), page).await;
+        SearchGroup::from_response(work_type, response)
+    }
+
+    /// Fan out a search across the given work types
+    ///
+    /// Returns one future per type so callers can drive them concurrently and
+    /// show each group as soon as its provider answers. A failing provider only
+    /// affects its own group's status.
+    pub fn search_all(
+        &self,
+        query: &str,
+        work_types: &[WorkType],
+        page: usize,
+    ) -> Vec<(WorkType, GroupSearchFuture)> {
+        work_types
+            .iter()
+            .map(|work_type| {
+                let service = self.clone();
+                let query = query.to_string();
+                let work_type = work_type.clone();
+                let future: GroupSearchFuture = Box::pin({
+                    let work_type = work_type.clone();
+                    async move { service.search_group(&query, work_type, page).await }
+                });
+                (work_type, future)
+            })
+            .collect()
+    }
+
     /// Search TMDB for movies or TV shows
-    async fn search_tmdb(&self, query: &str, work_type: WorkType, page: usize) -> SearchResponse {
+    async fn search_tmdb(
+        &self,
+        query: &str,
+        year: Option<u16>,
+        work_type: WorkType,
+        page: usize,
+    ) -> SearchResponse {
         // Check if TMDB is configured
         if !self.is_tmdb_configured() {
             info!("TMDB not configured, returning empty results");
@@ -139,16 +537,21 @@ impl SearchService {
 
         // Don't search with empty query
         if query.trim().is_empty() {
-            return SearchResponse::success(Vec::new(), Some(0));
+            return SearchResponse::success(SearchResultsPage::empty(page));
         }
 
         info!("TMDB is configured, performing search for '{}'", query);
 
         // Perform the search
-        match self.tmdb.search(query, work_type, page).await {
-            Ok(results) => {
-                info!("TMDB search returned {} results", results.len());
-                SearchResponse::success(results, None)
+        match self.tmdb.search(query, year, work_type, page).await {
+            Ok(results_page) => {
+                info!(
+                    "TMDB search returned {} results (page {}/{})",
+                    results_page.results.len(),
+                    results_page.page + 1,
+                    results_page.total_pages
+                );
+                SearchResponse::success(results_page)
             }
             Err(e) => {
                 info!(
@@ -169,6 +572,10 @@ impl SearchService {
                         info!("Returning ApiError response for TMDB ({})", status);
                         SearchResponse::api_error(status, message)
                     }
+                    ProviderError::RateLimited { retry_after_secs } => {
+                        info!("Returning RateLimited response for TMDB");
+                        SearchResponse::rate_limited(retry_after_secs)
+                    }
                     _ => {
                         info!("Returning NetworkError (fallback) response for TMDB");
                         SearchResponse::network_error(e.to_string())
@@ -179,7 +586,7 @@ impl SearchService {
     }
 
     /// Search games using RAWG API
-    async fn search_rawg(&self, query: &str, page: usize) -> SearchResponse {
+    async fn search_rawg(&self, query: &str, year: Option<u16>, page: usize) -> SearchResponse {
         // Check if RAWG is configured
         if !self.is_rawg_configured() {
             info!("RAWG not configured, returning empty results");
@@ -188,16 +595,21 @@ impl SearchService {
 
         // Don't search with empty query
         if query.trim().is_empty() {
-            return SearchResponse::success(Vec::new(), Some(0));
+            return SearchResponse::success(SearchResultsPage::empty(page));
         }
 
         info!("RAWG is configured, performing search for '{}'", query);
 
         // Perform the search
-        match self.rawg.search(query, page).await {
-            Ok(results) => {
-                info!("RAWG search returned {} results", results.len());
-                SearchResponse::success(results, None)
+        match self.rawg.search(query, year, page).await {
+            Ok(results_page) => {
+                info!(
+                    "RAWG search returned {} results (page {}/{})",
+                    results_page.results.len(),
+                    results_page.page + 1,
+                    results_page.total_pages
+                );
+                SearchResponse::success(results_page)
             }
             Err(e) => {
                 info!(
@@ -218,6 +630,10 @@ impl SearchService {
                         info!("Returning ApiError response for RAWG ({})", status);
                         SearchResponse::api_error(status, message)
                     }
+                    ProviderError::RateLimited { retry_after_secs } => {
+                        info!("Returning RateLimited response for RAWG");
+                        SearchResponse::rate_limited(retry_after_secs)
+                    }
                     _ => {
                         info!("Returning NetworkError (fallback) response for RAWG");
                         SearchResponse::network_error(e.to_string())
@@ -255,7 +671,11 @@ pub fn search_works(query: &str, work_type: WorkType, page: usize) -> SearchResu
             if query.is_empty() {
                 true
             } else {
-                r.title.to_lowercase().contains(&query.to_lowercase())
+                let query = query.to_lowercase();
+                r.title.to_lowercase().contains(&query)
+                    || r.original_title
+                        .as_ref()
+                        .is_some_and(|t| t.to_lowercase().contains(&query))
             }
         })
         .collect();
@@ -273,3 +693,62 @@ pub fn search_works(query: &str, work_type: WorkType, page: usize) -> SearchResu
 
     SearchResultsPage::new(results, total_count, page, total_pages)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ProviderRef;
+
+    fn result(source: &str, id: &str, title: &str) -> SearchResult {
+        SearchResult {
+            provider_ref: ProviderRef::new(source, id),
+            title: title.to_string(),
+            original_title: None,
+            year: None,
+            work_type: WorkType::Movie,
+            cover_url: None,
+            backdrop_url: None,
+            theme_music_url: None,
+        }
+    }
+
+    #[test]
+    fn test_dedupe_collapses_same_provider_id() {
+        let results = vec![
+            result("tmdb", "129", "Spirited Away"),
+            result("tmdb", "550", "Fight Club"),
+            result("tmdb", "129", "Spirited Away (duplicate)"),
+        ];
+
+        let deduped = dedupe_results(results);
+        assert_eq!(deduped.len(), 2);
+        assert_eq!(deduped[0].title, "Spirited Away");
+        assert_eq!(deduped[1].title, "Fight Club");
+    }
+
+    #[test]
+    fn test_append_page_skips_results_already_shown() {
+        let first = SearchResultsPage::new(vec![result("tmdb", "1", "A")], 3, 0, 2);
+        let mut group = SearchGroup::from_response(WorkType::Movie, SearchResponse::success(first));
+        assert!(group.can_load_more());
+
+        let second = SearchResultsPage::new(
+            vec![result("tmdb", "1", "A"), result("tmdb", "2", "B")],
+            3,
+            1,
+            2,
+        );
+        group.append_page(SearchResponse::success(second));
+
+        assert_eq!(group.results.len(), 2);
+        assert_eq!(group.page, 1);
+        assert!(!group.can_load_more());
+    }
+
+    #[test]
+    fn test_dedupe_keeps_same_id_from_different_providers() {
+        let results = vec![result("tmdb", "129", "A"), result("rawg", "129", "B")];
+
+        assert_eq!(dedupe_results(results).len(), 2);
+    }
+}
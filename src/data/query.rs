@@ -0,0 +1,326 @@
+//! Search query parsing and result ranking
+//!
+//! Typed queries can carry hints the providers understand better as parameters:
+//! - A trailing year: `Dune 1984` or `Dune (1984)`
+//! - An explicit year: `y:1984` / `year:1984`
+//! - A work type: `t:movie`, `type:tv`, `t:anime`, `t:game`
+//!
+//! Provider pages come back in the provider's own order, so each page is re-ranked
+//! by how closely titles match the typed text and how near the release year is to
+//! the requested one.
+
+use crate::models::{normalize_title, SearchResult, WorkType};
+
+/// Earliest year treated as a release year rather than part of a title
+const MIN_YEAR: u16 = 1870;
+
+/// How far into the future a trailing number may be and still count as a year
+const MAX_YEARS_AHEAD: u16 = 5;
+
+/// A search query split into free text and provider hints
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ParsedQuery {
+    /// Text to send to the provider, with hints removed
+    pub text: String,
+    /// Requested release year
+    pub year: Option<u16>,
+    /// Requested work type
+    pub work_type: Option<WorkType>,
+    /// Whether the year was a bare trailing number that may belong to the title
+    pub year_inferred: bool,
+}
+
+impl ParsedQuery {
+    /// The query with an inferred year put back into the title text
+    ///
+    /// Used to retry a search like "Wonder Woman 1984" when the year filter found nothing.
+    pub fn literal(&self) -> Option<ParsedQuery> {
+        if !self.year_inferred {
+            return None;
+        }
+        let year = self.year?;
+        Some(ParsedQuery {
+            text: format!("{} {}", self.text, year),
+            year: None,
+            work_type: self.work_type.clone(),
+            year_inferred: false,
+        })
+    }
+}
+
+/// Parse a year, rejecting numbers outside the plausible release range
+fn parse_year(value: &str, current_year: i32) -> Option<u16> {
+    if value.len() != 4 || !value.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let year: u16 = value.parse().ok()?;
+    let latest = u16::try_from(current_year)
+        .ok()?
+        .saturating_add(MAX_YEARS_AHEAD);
+    (MIN_YEAR..=latest).contains(&year).then_some(year)
+}
+
+/// Parse a type hint value
+fn parse_work_type(value: &str) -> Option<WorkType> {
+    match value.to_lowercase().as_str() {
+        "movie" | "movies" | "film" => Some(WorkType::Movie),
+        "tv" | "show" | "series" | "anime" => Some(WorkType::TvAnime),
+        "game" | "games" => Some(WorkType::Game),
+        _ => None,
+    }
+}
+
+/// Split a raw query into free text, year and type hints
+///
+/// A bare trailing year is only extracted when other words remain, so a query
+/// like "1984" still searches for the title. Years more than a few years past
+/// `current_year` are treated as part of the title.
+pub fn parse_query(raw: &str, current_year: i32) -> ParsedQuery {
+    let mut parsed = ParsedQuery::default();
+    let mut words: Vec<&str> = Vec::new();
+
+    for word in raw.split_whitespace() {
+        let hint =
+            word.split_once(':')
+                .and_then(|(key, value)| match key.to_lowercase().as_str() {
+                    "y" | "year" => {
+                        parse_year(value, current_year).map(|year| parsed.year = Some(year))
+                    }
+                    "t" | "type" => parse_work_type(value).map(|wt| parsed.work_type = Some(wt)),
+                    _ => None,
+                });
+        if hint.is_none() {
+            words.push(word);
+        }
+    }
+
+    if parsed.year.is_none() && words.len() > 1 {
+        let last = words[words.len() - 1];
+        let parenthesized = last
+            .strip_prefix('(')
+            .and_then(|w| w.strip_suffix(')'))
+            .and_then(|w| parse_year(w, current_year));
+        if let Some(year) = parenthesized {
+            parsed.year = Some(year);
+            words.pop();
+        } else if let Some(year) = parse_year(last, current_year) {
+            parsed.year = Some(year);
+            parsed.year_inferred = true;
+            words.pop();
+        }
+    }
+
+    parsed.text = words.join(" ");
+    parsed
+}
+
+/// Fold common Latin diacritics so "Pokémon" and "Pokemon" compare equal
+fn fold_diacritics(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' => 'a',
+            'ç' | 'č' => 'c',
+            'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ę' => 'e',
+            'ì' | 'í' | 'î' | 'ï' | 'ī' => 'i',
+            'ñ' | 'ń' => 'n',
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' => 'o',
+            'ù' | 'ú' | 'û' | 'ü' | 'ū' => 'u',
+            'ý' | 'ÿ' => 'y',
+            'ś' | 'š' => 's',
+            'ź' | 'ż' | 'ž' => 'z',
+            'ł' => 'l',
+            _ => c,
+        })
+        .collect()
+}
+
+/// Normalize a title for comparison: lowercase, folded, no punctuation or leading article
+fn comparable(title: &str) -> String {
+    let normalized = fold_diacritics(&normalize_title(title));
+    for article in ["the ", "a ", "an "] {
+        if let Some(rest) = normalized.strip_prefix(article) {
+            if !rest.is_empty() {
+                return rest.to_string();
+            }
+        }
+    }
+    normalized
+}
+
+/// Character bigrams of a string, ignoring spaces
+fn bigrams(text: &str) -> Vec<(char, char)> {
+    let chars: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    chars.windows(2).map(|w| (w[0], w[1])).collect()
+}
+
+/// Similarity of two titles between 0.0 and 1.0
+///
+/// Exact matches score 1.0 and a title starting with the query scores at least 0.9;
+/// anything else uses the Dice coefficient over character bigrams.
+pub fn title_similarity(query: &str, title: &str) -> f32 {
+    let query = comparable(query);
+    let title = comparable(title);
+    if query.is_empty() || title.is_empty() {
+        return 0.0;
+    }
+    // Spacing differences alone ("M*A*S*H" vs "mash") still count as exact
+    if query.replace(' ', "") == title.replace(' ', "") {
+        return 1.0;
+    }
+
+    let query_bigrams = bigrams(&query);
+    let mut title_bigrams = bigrams(&title);
+    let dice = if query_bigrams.is_empty() || title_bigrams.is_empty() {
+        0.0
+    } else {
+        let total = query_bigrams.len() + title_bigrams.len();
+        let mut shared = 0;
+        for bigram in &query_bigrams {
+            if let Some(pos) = title_bigrams.iter().position(|b| b == bigram) {
+                title_bigrams.swap_remove(pos);
+                shared += 1;
+            }
+        }
+        2.0 * shared as f32 / total as f32
+    };
+
+    let starts_with_query = title
+        .strip_prefix(&query)
+        .is_some_and(|rest| rest.starts_with(' '));
+    if starts_with_query {
+        dice.max(0.9)
+    } else {
+        dice
+    }
+}
+
+/// Bonus for a release year close to the requested one (0.0 to 0.3)
+///
+/// Off-by-one years still score well, since festival and regional releases differ.
+pub fn year_proximity(wanted: Option<u16>, actual: Option<u16>) -> f32 {
+    match (wanted, actual) {
+        (Some(wanted), Some(actual)) => {
+            let diff = wanted.abs_diff(actual) as f32;
+            (0.3 - 0.1 * diff).max(0.0)
+        }
+        _ => 0.0,
+    }
+}
+
+/// Relevance of a result to the query (higher is better)
+pub fn relevance(result: &SearchResult, query: &ParsedQuery) -> f32 {
+    let title_score = result
+        .original_title
+        .iter()
+        .map(|original| title_similarity(&query.text, original))
+        .fold(title_similarity(&query.text, &result.title), f32::max);
+    title_score + year_proximity(query.year, result.year)
+}
+
+/// Re-rank results by relevance, keeping provider order for ties
+pub fn rank_results(mut results: Vec<SearchResult>, query: &ParsedQuery) -> Vec<SearchResult> {
+    if query.text.is_empty() && query.year.is_none() {
+        return results;
+    }
+    // Compare rounded scores so near-identical matches keep the provider's popularity order
+    let key = |r: &SearchResult| (relevance(r, query) * 100.0).round() as i32;
+    results.sort_by_key(|r| std::cmp::Reverse(key(r)));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_support::result;
+
+    /// Fixed so the "too far ahead to be a year" cutoff doesn't move with the clock
+    const THIS_YEAR: i32 = 2025;
+
+    fn parse(raw: &str) -> ParsedQuery {
+        parse_query(raw, THIS_YEAR)
+    }
+
+    #[test]
+    fn test_parse_trailing_and_explicit_years() {
+        let dune = parse("Dune 1984");
+        assert_eq!(dune.text, "Dune");
+        assert_eq!(dune.year, Some(1984));
+        assert!(dune.year_inferred);
+        assert_eq!(dune.literal().unwrap().text, "Dune 1984");
+
+        let parenthesized = parse("Dune (2021)");
+        assert_eq!(
+            (parenthesized.text.as_str(), parenthesized.year),
+            ("Dune", Some(2021))
+        );
+        assert!(parenthesized.literal().is_none());
+
+        let explicit = parse("y:1984 dune t:movie");
+        assert_eq!(explicit.text, "dune");
+        assert_eq!(explicit.year, Some(1984));
+        assert_eq!(explicit.work_type, Some(WorkType::Movie));
+        assert!(!explicit.year_inferred);
+    }
+
+    #[test]
+    fn test_parse_keeps_numbers_that_belong_to_titles() {
+        // A lone number is the title itself
+        assert_eq!(parse("1984").text, "1984");
+        assert_eq!(parse("1984").year, None);
+
+        // Numbers that cannot be release years stay in the text
+        assert_eq!(parse("Blade Runner 2049").text, "Blade Runner 2049");
+        assert_eq!(parse_query("Blade Runner 2049", 2045).year, Some(2049));
+        assert_eq!(parse("Apollo 13").text, "Apollo 13");
+        assert_eq!(parse("Ocean's 11").year, None);
+
+        // Unknown hints are treated as text
+        assert_eq!(parse("Re:Zero").text, "Re:Zero");
+        assert_eq!(parse("t:podcast serial").text, "t:podcast serial");
+    }
+
+    #[test]
+    fn test_title_similarity_on_tricky_titles() {
+        assert_eq!(title_similarity("the thing", "Thing"), 1.0);
+        assert_eq!(title_similarity("pokemon", "Pokémon"), 1.0);
+        assert_eq!(title_similarity("se7en", "Se7en"), 1.0);
+        assert_eq!(title_similarity("mash", "M*A*S*H"), 1.0);
+        assert!(title_similarity("千と千尋", "千と千尋の神隠し") > 0.5);
+        assert!(title_similarity("alien", "Alien: Romulus") >= 0.9);
+        assert!(title_similarity("alien", "Aliens") > title_similarity("alien", "Predator"));
+        assert_eq!(title_similarity("", "Alien"), 0.0);
+        assert_eq!(title_similarity("!!!", "Alien"), 0.0);
+    }
+
+    #[test]
+    fn test_rank_prefers_close_titles_and_years() {
+        let results = vec![
+            result("1", "Dune: Part Two", Some(2024)),
+            result("2", "Dune", Some(2021)),
+            result("3", "Dune", Some(1984)),
+            result("4", "Jodorowsky's Dune", Some(2013)),
+        ];
+
+        let ranked = rank_results(results.clone(), &parse("Dune 1984"));
+        let ids: Vec<&str> = ranked
+            .iter()
+            .map(|r| r.provider_ref.provider_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["3", "2", "1", "4"]);
+
+        // Without a year, equally good titles keep the provider order
+        let ranked = rank_results(results, &parse("dune"));
+        assert_eq!(ranked[0].provider_ref.provider_id, "2");
+        assert_eq!(ranked[1].provider_ref.provider_id, "3");
+    }
+
+    #[test]
+    fn test_rank_uses_original_titles() {
+        let mut spirited = result("129", "Spirited Away", Some(2001));
+        spirited.original_title = Some("千と千尋の神隠し".to_string());
+        let results = vec![result("1", "Sen", Some(2010)), spirited];
+
+        let ranked = rank_results(results, &parse("千と千尋の神隠し"));
+        assert_eq!(ranked[0].provider_ref.provider_id, "129");
+    }
+}
@@ -0,0 +1,387 @@
+//! Metadata refresh for provider-backed works
+//!
+//! A work keeps the title, year and cover it was saved with. Refreshing fetches the
+//! provider's current details, diffs them against the work and applies the accepted
+//! changes. Fields in `Work::overridden_fields` are never diffed, and a value the
+//! provider no longer reports never erases the one we have. Descriptive metadata
+//! (synopsis, genres, creators, ...) is provider-owned and replaced on every refresh.
+//!
+//! Providers localize titles in the display language, so a title that comes back in
+//! another language than the saved one is not a correction: it is kept searchable as
+//! an alternate title instead of being offered as a change.
+//!
+//! A background pass refreshes works whose metadata is older than
+//! `METADATA_REFRESH_INTERVAL_DAYS`, a batch at a time so it stays within rate limits.
+
+use crate::constants::{
+    MAX_ALTERNATE_TITLES, METADATA_REFRESH_BATCH_SIZE, METADATA_REFRESH_INTERVAL_DAYS,
+};
+use crate::data::SearchService;
+use crate::models::{Work, WorkDetails, WorkField};
+use crate::providers::{ProviderError, ProviderResult};
+use chrono::{DateTime, Duration, Utc};
+use tracing::info;
+
+/// A field whose provider value differs from the saved one
+#[derive(Clone, PartialEq, Debug)]
+pub struct FieldChange {
+    pub field: WorkField,
+    /// Saved value (for display)
+    pub old: Option<String>,
+    /// Provider value (for display)
+    pub new: Option<String>,
+}
+
+impl FieldChange {
+    /// Saved value for display
+    pub fn old_label(&self) -> String {
+        self.label_for(&self.old)
+    }
+
+    /// Provider value for display
+    pub fn new_label(&self) -> String {
+        self.label_for(&self.new)
+    }
+
+    fn label_for(&self, value: &Option<String>) -> String {
+        match (self.field, value) {
+            (_, None) => "—".to_string(),
+            (WorkField::Cover, Some(_)) => "image".to_string(),
+            (_, Some(value)) => value.clone(),
+        }
+    }
+}
+
+/// A refreshed work and the changes that were applied to it
+#[derive(Clone, PartialEq, Debug)]
+pub struct WorkRefresh {
+    pub work: Work,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Whether the provider localized the title in another language than the saved one
+///
+/// Only when both languages are known: a manual title, or one saved before languages
+/// were recorded, can still be corrected.
+fn title_language_differs(work: &Work, details: &WorkDetails) -> bool {
+    matches!(
+        (&work.title_language, &details.language),
+        (Some(saved), Some(fetched)) if saved != fetched
+    )
+}
+
+/// Compare a work against its provider details
+///
+/// Skips overridden fields, fields the provider left empty, and a title localized in
+/// another display language.
+pub fn diff_work(work: &Work, details: &WorkDetails) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    let language_differs = title_language_differs(work, details);
+    let mut compare = |field: WorkField, old: Option<String>, new: Option<String>| {
+        let localized_title = field == WorkField::Title && language_differs;
+        if work.is_overridden(field) || localized_title || new.is_none() || old == new {
+            return;
+        }
+        changes.push(FieldChange { field, old, new });
+    };
+
+    let title = details.title.trim();
+    compare(
+        WorkField::Title,
+        Some(work.title_en.clone()),
+        (!title.is_empty()).then(|| title.to_string()),
+    );
+    compare(
+        WorkField::ReleaseYear,
+        work.release_year.map(|y| y.to_string()),
+        details.year.map(|y| y.to_string()),
+    );
+    compare(
+        WorkField::Cover,
+        work.cover_image_local_uri.clone(),
+        details.cover_url.clone(),
+    );
+
+    changes
+}
+
+/// Copy the given fields and the descriptive metadata from the provider details,
+/// and stamp the refresh time
+///
+/// Overridden fields are left untouched even if listed.
+pub fn apply_details(
+    work: &mut Work,
+    details: &WorkDetails,
+    fields: &[WorkField],
+    now: DateTime<Utc>,
+) {
+    for field in fields {
+        if work.is_overridden(*field) {
+            continue;
+        }
+        match field {
+            WorkField::Title if !details.title.trim().is_empty() => {
+                // Keep the previous title searchable (e.g. a misspelling)
+                let previous =
+                    std::mem::replace(&mut work.title_en, details.title.trim().to_string());
+                if previous != work.title_en && !work.alternate_titles.contains(&previous) {
+                    work.alternate_titles.push(previous);
+                }
+            }
+            WorkField::ReleaseYear if details.year.is_some() => {
+                work.release_year = details.year;
+            }
+            WorkField::Cover if details.cover_url.is_some() => {
+                work.cover_image_local_uri = details.cover_url.clone();
+            }
+            _ => {}
+        }
+    }
+    // The saved title is in the language the provider reports it in
+    if work.title_en == details.title.trim() {
+        work.title_language = details.language.clone();
+    }
+    if details.original_title.is_some() {
+        work.original_title = details.original_title.clone();
+    }
+    let provider_title = details.title.trim().to_string();
+    for title in std::iter::once(&provider_title).chain(&details.alternate_titles) {
+        if !title.is_empty() && *title != work.title_en && !work.alternate_titles.contains(title) {
+            work.alternate_titles.push(title.clone());
+        }
+    }
+    work.alternate_titles.truncate(MAX_ALTERNATE_TITLES);
+    if details.backdrop_url.is_some() {
+        work.backdrop_url = details.backdrop_url.clone();
+    }
+    for external_id in &details.external_ids {
+        work.add_external_id(external_id.clone());
+    }
+    if !details.metadata.is_empty() {
+        work.metadata = details.metadata.clone();
+    }
+    work.metadata_refreshed_at = Some(now);
+}
+
+/// Check if a provider-backed work is due for a background refresh
+///
+/// Works whose details were never fetched are always due.
+pub fn needs_refresh(work: &Work, now: DateTime<Utc>) -> bool {
+    if work.provider_ref.is_none() {
+        return false;
+    }
+    match work.metadata_refreshed_at {
+        Some(last) => now - last >= Duration::days(METADATA_REFRESH_INTERVAL_DAYS),
+        None => true,
+    }
+}
+
+/// Fetch a work's details and apply every non-overridden change
+pub async fn refresh_work(service: &SearchService, mut work: Work) -> ProviderResult<WorkRefresh> {
+    let Some(provider_ref) = work.provider_ref.clone() else {
+        return Err(ProviderError::Unavailable(format!(
+            "{} has no provider",
+            work.title_en
+        )));
+    };
+
+    let details = service
+        .details(&provider_ref, work.work_type.clone())
+        .await?;
+    let changes = diff_work(&work, &details);
+    let fields: Vec<WorkField> = changes.iter().map(|c| c.field).collect();
+    apply_details(&mut work, &details, &fields, Utc::now());
+    Ok(WorkRefresh { work, changes })
+}
+
+/// Refresh a batch of stale works, applying every non-overridden change
+///
+/// Returns each work that was checked (so its refresh time can be saved), with the
+/// changes that were applied. Stops early when the provider is unreachable.
+pub async fn refresh_stale_works(service: &SearchService, works: Vec<Work>) -> Vec<WorkRefresh> {
+    let now = Utc::now();
+    let stale: Vec<Work> = works
+        .into_iter()
+        .filter(|w| needs_refresh(w, now))
+        .take(METADATA_REFRESH_BATCH_SIZE)
+        .collect();
+
+    let mut refreshed = Vec::new();
+    for mut work in stale {
+        match refresh_work(service, work.clone()).await {
+            Ok(refresh) => refreshed.push(refresh),
+            Err(ProviderError::ApiError { status: 404, .. }) => {
+                // Gone from the provider; keep what we have and stop asking
+                info!("{} no longer exists on its provider", work.title_en);
+                work.metadata_refreshed_at = Some(now);
+                refreshed.push(WorkRefresh {
+                    work,
+                    changes: Vec::new(),
+                });
+            }
+            Err(
+                e @ (ProviderError::NetworkError(_)
+                | ProviderError::RateLimited { .. }
+                | ProviderError::Unavailable(_)),
+            ) => {
+                info!("Stopping metadata refresh: {}", e);
+                break;
+            }
+            Err(e) => {
+                info!("Skipping metadata refresh for {}: {}", work.title_en, e);
+            }
+        }
+    }
+
+    refreshed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ProviderRef, WorkMetadata, WorkType};
+
+    fn work() -> Work {
+        Work::from_provider(
+            WorkType::Movie,
+            "Spirited Away".to_string(),
+            Some(2002),
+            Some("https://image.tmdb.org/t/p/w500/old.jpg".to_string()),
+            None,
+            ProviderRef::new("tmdb", "129"),
+        )
+    }
+
+    fn details() -> WorkDetails {
+        WorkDetails {
+            provider_ref: ProviderRef::new("tmdb", "129"),
+            work_type: WorkType::Movie,
+            title: "Spirited Away".to_string(),
+            original_title: Some("千と千尋の神隠し".to_string()),
+            alternate_titles: vec!["Sen to Chihiro no Kamikakushi".to_string()],
+            year: Some(2001),
+            cover_url: Some("https://image.tmdb.org/t/p/w500/new.jpg".to_string()),
+            backdrop_url: Some("https://image.tmdb.org/t/p/w1280/wide.jpg".to_string()),
+            metadata: WorkMetadata {
+                genres: vec!["Animation".to_string()],
+                ..Default::default()
+            },
+            external_ids: vec![ProviderRef::new("imdb", "tt0245429")],
+            language: Some("en-US".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_diff_skips_overridden_and_missing_fields() {
+        let mut work = work();
+        let changes = diff_work(&work, &details());
+        let fields: Vec<WorkField> = changes.iter().map(|c| c.field).collect();
+        assert_eq!(fields, vec![WorkField::ReleaseYear, WorkField::Cover]);
+
+        work.set_overridden(WorkField::Cover);
+        let missing_year = WorkDetails {
+            year: None,
+            ..details()
+        };
+        assert!(diff_work(&work, &missing_year).is_empty());
+    }
+
+    #[test]
+    fn test_apply_never_touches_overridden_fields() {
+        let mut work = work();
+        work.set_overridden(WorkField::Cover);
+        let now = Utc::now();
+
+        apply_details(
+            &mut work,
+            &details(),
+            &[WorkField::ReleaseYear, WorkField::Cover],
+            now,
+        );
+
+        assert_eq!(work.release_year, Some(2001));
+        assert_eq!(
+            work.cover_image_local_uri.as_deref(),
+            Some("https://image.tmdb.org/t/p/w500/old.jpg")
+        );
+        assert_eq!(work.metadata_refreshed_at, Some(now));
+        assert!(work.metadata.has_genre("animation"));
+        assert_eq!(work.original_title.as_deref(), Some("千と千尋の神隠し"));
+        assert!(work.has_provider_ref(&ProviderRef::new("imdb", "tt0245429")));
+    }
+
+    #[test]
+    fn test_apply_keeps_previous_title_searchable() {
+        let mut work = work();
+        let localized = WorkDetails {
+            title: "千と千尋の神隠し".to_string(),
+            ..details()
+        };
+
+        apply_details(&mut work, &localized, &[WorkField::Title], Utc::now());
+
+        assert_eq!(work.title_en, "千と千尋の神隠し");
+        assert_eq!(
+            work.alternate_titles,
+            vec!["Spirited Away", "Sen to Chihiro no Kamikakushi"]
+        );
+        assert_eq!(work.matching_title("spirited"), Some("Spirited Away"));
+    }
+
+    #[test]
+    fn test_localized_title_is_not_a_correction() {
+        let mut work = work();
+        let localized = WorkDetails {
+            title: "千と千尋の神隠し".to_string(),
+            year: Some(2002),
+            cover_url: work.cover_image_local_uri.clone(),
+            language: Some("ja-JP".to_string()),
+            ..details()
+        };
+
+        // Unchanged in English, so the saved title is known to be English
+        apply_details(&mut work, &details(), &[], Utc::now());
+        assert_eq!(work.title_language.as_deref(), Some("en-US"));
+
+        // After switching the display language the title stays, but is searchable
+        assert!(diff_work(&work, &localized).is_empty());
+        apply_details(&mut work, &localized, &[], Utc::now());
+        assert_eq!(work.title_en, "Spirited Away");
+        assert_eq!(work.title_language.as_deref(), Some("en-US"));
+        assert!(work
+            .alternate_titles
+            .contains(&"千と千尋の神隠し".to_string()));
+
+        // A different title in the same language is still a correction
+        let corrected = WorkDetails {
+            title: "Spirited Away (2001)".to_string(),
+            ..details()
+        };
+        let changes = diff_work(&work, &corrected);
+        assert_eq!(changes[0].field, WorkField::Title);
+
+        // So is a title whose language was never recorded
+        work.title_language = None;
+        let changes = diff_work(&work, &corrected);
+        assert_eq!(changes[0].field, WorkField::Title);
+        apply_details(&mut work, &corrected, &[WorkField::Title], Utc::now());
+        assert_eq!(work.title_en, "Spirited Away (2001)");
+        assert_eq!(work.title_language.as_deref(), Some("en-US"));
+    }
+
+    #[test]
+    fn test_needs_refresh_after_interval() {
+        let mut work = work();
+        let now = Utc::now();
+        assert!(needs_refresh(&work, now));
+
+        work.metadata_refreshed_at = Some(now - Duration::days(1));
+        assert!(!needs_refresh(&work, now));
+
+        work.metadata_refreshed_at = Some(now - Duration::days(METADATA_REFRESH_INTERVAL_DAYS));
+        assert!(needs_refresh(&work, now));
+
+        let manual = Work::from_manual(WorkType::Game, "Tetris".to_string(), None);
+        assert!(!needs_refresh(&manual, now + Duration::days(365)));
+    }
+}
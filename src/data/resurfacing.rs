@@ -0,0 +1,174 @@
+//! Resurfacing order of the hero
+//!
+//! Instead of a uniform shuffle, each mnemon gets a weight and the cycling order is a
+//! weighted random permutation (each mnemon draws `u^(1/weight)` and the highest draws
+//! go first), so heavier mnemons tend to come up sooner without lighter ones ever
+//! being left out. The weight multiplies:
+//! - Time since it was last shown: just-seen mnemons are held back, recovering over
+//!   `RESURFACE_RECOVERY_DAYS`; never-shown ones are fully recovered
+//! - Age of the memory: the longer ago it was finished, the stronger the nostalgia
+//! - Favorites
+//! - Recency of addition: new mnemons get a boost that fades over `RESURFACE_NEW_DAYS`
+//!
+//! The order is deterministic for a given seed, so it can be tested; the app draws a
+//! fresh seed per load.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use uuid::Uuid;
+
+use crate::constants::{
+    RESURFACE_AGE_BOOST, RESURFACE_AGE_MAX_YEARS, RESURFACE_FAVORITE_BOOST, RESURFACE_MIN_RECOVERY,
+    RESURFACE_NEW_BOOST, RESURFACE_NEW_DAYS, RESURFACE_RECOVERY_DAYS,
+};
+use crate::models::{Impression, Mnemon};
+
+/// Days between two instants, as a fraction (never negative)
+fn days_between(earlier: DateTime<Utc>, later: DateTime<Utc>) -> f64 {
+    ((later - earlier).num_seconds() as f64 / 86_400.0).max(0.0)
+}
+
+/// How likely a mnemon is to come up early (always positive)
+pub fn resurfacing_weight(
+    mnemon: &Mnemon,
+    impression: Option<&Impression>,
+    now: DateTime<Utc>,
+) -> f64 {
+    let recovery = match impression {
+        Some(impression) => {
+            let days = days_between(impression.last_shown_at, now);
+            (days / RESURFACE_RECOVERY_DAYS).clamp(RESURFACE_MIN_RECOVERY, 1.0)
+        }
+        None => 1.0,
+    };
+
+    let age = match mnemon.finished_on() {
+        Some(finished) => {
+            let finished_at = finished.and_time(chrono::NaiveTime::MIN).and_utc();
+            let years = days_between(finished_at, now) / 365.25;
+            1.0 + RESURFACE_AGE_BOOST * (years / RESURFACE_AGE_MAX_YEARS).min(1.0)
+        }
+        None => 1.0,
+    };
+
+    let favorite = if mnemon.favorite {
+        RESURFACE_FAVORITE_BOOST
+    } else {
+        1.0
+    };
+
+    let added_days = days_between(mnemon.created_at, now);
+    let novelty = 1.0 + RESURFACE_NEW_BOOST * (1.0 - added_days / RESURFACE_NEW_DAYS).max(0.0);
+
+    recovery * age * favorite * novelty
+}
+
+/// Order in which to cycle through mnemons, as indices into `mnemons`
+pub fn resurfacing_order(
+    mnemons: &[Mnemon],
+    impressions: &HashMap<Uuid, Impression>,
+    now: DateTime<Utc>,
+    seed: u64,
+) -> Vec<usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut keyed: Vec<(f64, usize)> = mnemons
+        .iter()
+        .enumerate()
+        .map(|(index, mnemon)| {
+            let weight = resurfacing_weight(mnemon, impressions.get(&mnemon.id), now);
+            // Drawn in (0, 1] so a zero draw can't tie every weight at 0
+            let draw: f64 = 1.0 - rng.gen::<f64>();
+            (draw.powf(1.0 / weight), index)
+        })
+        .collect();
+    keyed.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+    keyed.into_iter().map(|(_, index)| index).collect()
+}
+
+/// Impression after showing a mnemon (again)
+pub fn record_impression(
+    mnemon_id: Uuid,
+    previous: Option<&Impression>,
+    now: DateTime<Utc>,
+) -> Impression {
+    Impression {
+        id: mnemon_id,
+        last_shown_at: now,
+        times_shown: previous.map_or(0, |i| i.times_shown).saturating_add(1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn mnemon(created_days_ago: i64, now: DateTime<Utc>) -> Mnemon {
+        let mut mnemon = Mnemon::new(Uuid::new_v4(), None, Vec::new(), Vec::new());
+        mnemon.created_at = now - Duration::days(created_days_ago);
+        mnemon
+    }
+
+    #[test]
+    fn test_resurfacing_weight_factors() {
+        let now = Utc::now();
+        let base = mnemon(365, now);
+        let base_weight = resurfacing_weight(&base, None, now);
+        assert_eq!(base_weight, 1.0);
+
+        let mut favorite = base.clone();
+        favorite.favorite = true;
+        assert!(resurfacing_weight(&favorite, None, now) > base_weight);
+
+        let mut old_memory = base.clone();
+        old_memory.finished_date = Some("2005-06-01".to_string());
+        assert!(resurfacing_weight(&old_memory, None, now) > base_weight);
+
+        let new = mnemon(1, now);
+        assert!(resurfacing_weight(&new, None, now) > base_weight);
+
+        let just_shown = record_impression(base.id, None, now);
+        let shown_weight = resurfacing_weight(&base, Some(&just_shown), now);
+        assert!(shown_weight < base_weight && shown_weight > 0.0);
+        let later = now + Duration::days(RESURFACE_RECOVERY_DAYS as i64);
+        assert_eq!(resurfacing_weight(&base, Some(&just_shown), later), 1.0);
+    }
+
+    #[test]
+    fn test_resurfacing_order_is_seeded_and_weighted() {
+        let now = Utc::now();
+        let mnemons: Vec<Mnemon> = (0..10).map(|_| mnemon(365, now)).collect();
+        let impressions = HashMap::new();
+
+        let order = resurfacing_order(&mnemons, &impressions, now, 42);
+        assert_eq!(order, resurfacing_order(&mnemons, &impressions, now, 42));
+        let mut sorted = order.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..10).collect::<Vec<_>>());
+
+        // Everything but the first mnemon was just shown: it should lead most orders
+        // (a uniform shuffle would put it first 1 time in 10)
+        let impressions: HashMap<Uuid, Impression> = mnemons[1..]
+            .iter()
+            .map(|m| (m.id, record_impression(m.id, None, now)))
+            .collect();
+        let leads = (0..50)
+            .filter(|seed| resurfacing_order(&mnemons, &impressions, now, *seed)[0] == 0)
+            .count();
+        assert!(leads > 20, "led {} of 50 orders", leads);
+    }
+
+    #[test]
+    fn test_record_impression_counts_showings() {
+        let now = Utc::now();
+        let id = Uuid::new_v4();
+        let first = record_impression(id, None, now);
+        let second = record_impression(id, Some(&first), now + Duration::minutes(5));
+        assert_eq!(first.times_shown, 1);
+        assert_eq!(second.times_shown, 2);
+        assert_eq!(second.last_shown_at, now + Duration::minutes(5));
+    }
+}
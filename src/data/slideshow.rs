@@ -0,0 +1,233 @@
+//! Slideshow filter
+//!
+//! Narrows which mnemons the hero cycles through, by feeling, type, release decade,
+//! genre, platform or finished year. The filter only applies to the cycling order
+//! (`AppState::shuffled_indices`); the library itself is untouched, so clearing the
+//! filter brings everything back in the same order.
+//!
+//! Choices within a group widen the filter (Cozy or Nostalgic), groups narrow it
+//! (Cozy games from the 1990s). An empty group doesn't restrict anything.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::{release_decade, Mnemon, Work, WorkType};
+
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct SlideshowFilter {
+    #[serde(default)]
+    pub feelings: Vec<String>,
+
+    #[serde(default)]
+    pub work_types: Vec<WorkType>,
+
+    /// Release decades, as their first year (e.g. 1990)
+    #[serde(default)]
+    pub decades: Vec<u16>,
+
+    /// Genres from the works' provider metadata
+    #[serde(default)]
+    pub genres: Vec<String>,
+
+    /// Platforms (games) from the works' provider metadata
+    #[serde(default)]
+    pub platforms: Vec<String>,
+
+    /// First year of the finished-date range (inclusive)
+    #[serde(default)]
+    pub finished_from: Option<i32>,
+
+    /// Last year of the finished-date range (inclusive)
+    #[serde(default)]
+    pub finished_to: Option<i32>,
+}
+
+/// Add a value to a list, or take it out if it's already there
+pub fn toggle<T: PartialEq>(values: &mut Vec<T>, value: T) {
+    if let Some(index) = values.iter().position(|v| *v == value) {
+        values.remove(index);
+    } else {
+        values.push(value);
+    }
+}
+
+impl SlideshowFilter {
+    /// Whether the filter lets everything through
+    pub fn is_empty(&self) -> bool {
+        self.feelings.is_empty()
+            && self.work_types.is_empty()
+            && self.decades.is_empty()
+            && self.genres.is_empty()
+            && self.platforms.is_empty()
+            && self.finished_from.is_none()
+            && self.finished_to.is_none()
+    }
+
+    /// Number of restrictions set (a year range counts once)
+    pub fn active_count(&self) -> usize {
+        let range = usize::from(self.finished_from.is_some() || self.finished_to.is_some());
+        self.feelings.len()
+            + self.work_types.len()
+            + self.decades.len()
+            + self.genres.len()
+            + self.platforms.len()
+            + range
+    }
+
+    /// Whether a mnemon (and its work) passes the filter
+    ///
+    /// With a finished-year range set, mnemons without a finished date are left out.
+    pub fn matches(&self, mnemon: &Mnemon, work: &Work) -> bool {
+        if !self.feelings.is_empty() && !mnemon.feelings.iter().any(|f| self.feelings.contains(f)) {
+            return false;
+        }
+        if !self.work_types.is_empty() && !self.work_types.contains(&work.work_type) {
+            return false;
+        }
+        if !self.decades.is_empty() {
+            let decade = work.release_year.map(release_decade);
+            if !decade.is_some_and(|d| self.decades.contains(&d)) {
+                return false;
+            }
+        }
+        if !self.genres.is_empty() && !self.genres.iter().any(|g| work.metadata.has_genre(g)) {
+            return false;
+        }
+        if !self.platforms.is_empty()
+            && !self.platforms.iter().any(|p| work.metadata.has_platform(p))
+        {
+            return false;
+        }
+        if self.finished_from.is_some() || self.finished_to.is_some() {
+            let Some(year) = mnemon.finished_on().map(|d| chrono::Datelike::year(&d)) else {
+                return false;
+            };
+            if self.finished_from.is_some_and(|from| year < from)
+                || self.finished_to.is_some_and(|to| year > to)
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// The mnemons of a cycling order (indices into `mnemons`) that pass the filter
+pub fn cycle_order(
+    order: &[usize],
+    mnemons: &[Mnemon],
+    works: &[Work],
+    filter: &SlideshowFilter,
+) -> Vec<usize> {
+    if filter.is_empty() {
+        return order.to_vec();
+    }
+    let works: HashMap<Uuid, &Work> = works.iter().map(|w| (w.id, w)).collect();
+    order
+        .iter()
+        .copied()
+        .filter(|&i| {
+            mnemons.get(i).is_some_and(|mnemon| {
+                works
+                    .get(&mnemon.work_id)
+                    .is_some_and(|work| filter.matches(mnemon, work))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(
+        work_type: WorkType,
+        year: u16,
+        finished: Option<&str>,
+        feelings: &[&str],
+    ) -> (Mnemon, Work) {
+        let work = Work::from_manual(work_type, "Title".to_string(), Some(year));
+        let mnemon = Mnemon::new(
+            work.id,
+            finished.map(str::to_string),
+            feelings.iter().map(|f| f.to_string()).collect(),
+            Vec::new(),
+        );
+        (mnemon, work)
+    }
+
+    #[test]
+    fn test_groups_widen_inside_and_narrow_across() {
+        let cozy_game = entry(WorkType::Game, 1995, Some("2001-04-02"), &["Cozy"]);
+        let nostalgic_movie = entry(WorkType::Movie, 1999, None, &["Nostalgic"]);
+        let epic_game = entry(WorkType::Game, 2011, Some("2012-10-20"), &["Epic"]);
+        let all = [&cozy_game, &nostalgic_movie, &epic_game];
+        let passing =
+            |filter: &SlideshowFilter| all.iter().filter(|(m, w)| filter.matches(m, w)).count();
+
+        let mut filter = SlideshowFilter::default();
+        assert!(filter.is_empty());
+        assert_eq!(passing(&filter), 3);
+
+        toggle(&mut filter.feelings, "Cozy".to_string());
+        toggle(&mut filter.feelings, "Nostalgic".to_string());
+        assert_eq!(passing(&filter), 2);
+
+        filter.decades.push(1990);
+        filter.work_types.push(WorkType::Game);
+        assert_eq!(passing(&filter), 1);
+        assert_eq!(filter.active_count(), 4);
+
+        // A year range leaves out mnemons without a finished date
+        let range = SlideshowFilter {
+            finished_from: Some(2000),
+            finished_to: Some(2010),
+            ..Default::default()
+        };
+        assert!(range.matches(&cozy_game.0, &cozy_game.1));
+        assert!(!range.matches(&nostalgic_movie.0, &nostalgic_movie.1));
+        assert!(!range.matches(&epic_game.0, &epic_game.1));
+
+        toggle(&mut filter.feelings, "Cozy".to_string());
+        assert_eq!(filter.feelings, vec!["Nostalgic".to_string()]);
+
+        // Genres and platforms come from provider metadata, in any case
+        let mut platformer = entry(WorkType::Game, 1995, None, &[]);
+        platformer.1.metadata.genres = vec!["Platformer".to_string()];
+        platformer.1.metadata.platforms = vec!["SNES".to_string()];
+        let genre = SlideshowFilter {
+            genres: vec!["platformer".to_string()],
+            ..Default::default()
+        };
+        assert!(genre.matches(&platformer.0, &platformer.1));
+        assert!(!genre.matches(&cozy_game.0, &cozy_game.1));
+        let platform = SlideshowFilter {
+            platforms: vec!["Game Boy".to_string()],
+            ..Default::default()
+        };
+        assert!(!platform.matches(&platformer.0, &platformer.1));
+    }
+
+    #[test]
+    fn test_cycle_order_keeps_the_order_of_passing_mnemons() {
+        let entries = [
+            entry(WorkType::Game, 1995, None, &["Cozy"]),
+            entry(WorkType::Movie, 1999, None, &["Epic"]),
+            entry(WorkType::Game, 2011, None, &["Cozy"]),
+        ];
+        let mnemons: Vec<Mnemon> = entries.iter().map(|(m, _)| m.clone()).collect();
+        let works: Vec<Work> = entries.iter().map(|(_, w)| w.clone()).collect();
+        let order = [2, 1, 0];
+
+        let everything = cycle_order(&order, &mnemons, &works, &SlideshowFilter::default());
+        assert_eq!(everything, vec![2, 1, 0]);
+
+        let cozy = SlideshowFilter {
+            feelings: vec!["Cozy".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(cycle_order(&order, &mnemons, &works, &cozy), vec![2, 0]);
+    }
+}
@@ -0,0 +1,22 @@
+//! Factories shared by the data layer's tests
+
+use crate::models::{ProviderRef, SearchResult, Work, WorkType};
+
+/// A TMDB movie result
+pub fn result(id: &str, title: &str, year: Option<u16>) -> SearchResult {
+    SearchResult {
+        provider_ref: ProviderRef::new("tmdb", id),
+        title: title.to_string(),
+        original_title: None,
+        year,
+        work_type: WorkType::Movie,
+        cover_url: None,
+        backdrop_url: None,
+        theme_music_url: None,
+    }
+}
+
+/// A movie added by hand
+pub fn manual(title: &str, year: Option<u16>) -> Work {
+    Work::from_manual(WorkType::Movie, title.to_string(), year)
+}
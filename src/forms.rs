@@ -1,4 +1,6 @@
 use crate::models::*;
+use crate::storage;
+use uuid::Uuid;
 
 #[derive(Clone, PartialEq, Debug, Default)]
 pub struct MnemonForm {
@@ -7,8 +9,16 @@ pub struct MnemonForm {
     pub year: String,
 
     pub provider_ref: Option<ProviderRef>,
+    /// Original-language title reported by the provider
+    pub original_title: Option<String>,
+    /// Work already in the library that the mnemon should be attached to
+    pub existing_work_id: Option<Uuid>,
     pub cover_url: Option<String>,
+    pub backdrop_url: Option<String>,
+    /// Further picked covers, shown when the cover fails to load
+    pub cover_fallbacks: Vec<String>,
     pub theme_music_url: Option<String>,
+    pub theme_music_trim: Option<ThemeTrim>,
 
     pub finished_date: String,
     pub feelings: Vec<String>,
@@ -26,14 +36,49 @@ impl MnemonForm {
             title: work.title_en.clone(),
             year: work.release_year.map(|y| y.to_string()).unwrap_or_default(),
             provider_ref: work.provider_ref.clone(),
+            original_title: work.original_title.clone(),
+            existing_work_id: Some(work.id),
             cover_url: work.cover_image_local_uri.clone(),
+            backdrop_url: work.backdrop_url.clone(),
+            cover_fallbacks: work.cover_fallbacks.clone(),
             theme_music_url: work.theme_music_local_uri.clone(),
+            theme_music_trim: work.theme_music_trim,
             finished_date: mnemon.finished_date.clone().unwrap_or_default(),
             feelings: mnemon.feelings.clone(),
             notes: mnemon.notes.join("\n"),
         }
     }
 
+    /// The picked cover followed by its fallbacks
+    pub fn covers(&self) -> Vec<String> {
+        self.cover_url
+            .iter()
+            .chain(self.cover_fallbacks.iter())
+            .cloned()
+            .collect()
+    }
+
+    /// Use picked covers, the first as the cover and the rest as fallbacks
+    pub fn set_covers(&mut self, covers: Vec<String>) {
+        let mut covers = covers.into_iter();
+        self.cover_url = covers.next();
+        self.cover_fallbacks = covers.collect();
+    }
+
+    /// Whether the cover is an image the user uploaded
+    pub fn has_uploaded_cover(&self) -> bool {
+        self.cover_url
+            .as_deref()
+            .is_some_and(|uri| storage::asset_id(uri).is_some())
+    }
+
+    /// Whether the theme music is a file the user attached
+    pub fn has_uploaded_theme_music(&self) -> bool {
+        self.theme_music_url
+            .as_deref()
+            .is_some_and(|uri| storage::asset_id(uri).is_some())
+    }
+
     pub fn parse_notes(&self) -> Vec<String> {
         self.notes
             .lines()
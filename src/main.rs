@@ -5,6 +5,7 @@ use tracing::info;
 use uuid::Uuid;
 
 mod app_state;
+mod audio;
 mod components;
 mod constants;
 mod data;
@@ -12,14 +13,19 @@ mod forms;
 mod hooks;
 mod models;
 mod providers;
+mod radio;
 mod settings;
 mod storage;
 mod types;
+mod uploads;
 mod utils;
 
 use app_state::AppState;
 use components::*;
 use constants::*;
+use data::matching::MatchOutcome;
+use data::refresh::WorkRefresh;
+use data::SearchService;
 use forms::MnemonForm;
 use models::*;
 use types::Direction;
@@ -50,6 +56,12 @@ fn App() -> Element {
     // Provide state to child components
     use_context_provider(|| app_state);
 
+    // Shared theme music player
+    let mut audio = audio::use_audio_controller_provider();
+
+    // Theme music radio (takes over the audio from the hero while on)
+    let radio = radio::use_radio_provider();
+
     // Load data from IndexedDB on mount
     use_effect(move || {
         let is_loaded = app_state.peek().is_loaded();
@@ -60,6 +72,104 @@ fn App() -> Element {
         }
     });
 
+    // Works updated by the background metadata refresh (shown until dismissed)
+    let mut refreshed_works = use_signal(Vec::<WorkRefresh>::new);
+    let mut metadata_refresh_started = use_signal(|| false);
+
+    // Refresh stale provider metadata once data has loaded
+    use_effect(move || {
+        if !app_state.read().is_loaded() || *metadata_refresh_started.peek() {
+            return;
+        }
+        metadata_refresh_started.set(true);
+
+        spawn(async move {
+            let works = app_state.peek().works.peek().clone();
+            let refreshes = data::refresh::refresh_stale_works(&SearchService::new(), works).await;
+            if refreshes.is_empty() {
+                return;
+            }
+
+            let changed: Vec<WorkRefresh> = refreshes
+                .iter()
+                .filter(|r| !r.changes.is_empty())
+                .cloned()
+                .collect();
+            info!(
+                "Metadata refresh checked {} works, {} changed",
+                refreshes.len(),
+                changed.len()
+            );
+            for refresh in refreshes {
+                app_state.write().update_work(refresh.work);
+            }
+            refreshed_works.set(changed);
+        });
+    });
+
+    // Manual works linked to a provider in the background (shown until dismissed)
+    let mut auto_linked_works = use_signal(Vec::<Work>::new);
+    let mut auto_match_started = use_signal(|| false);
+
+    // Periodically match manual works once providers are reachable
+    use_effect(move || {
+        if !app_state.read().is_loaded() || *auto_match_started.peek() {
+            return;
+        }
+        auto_match_started.set(true);
+
+        spawn(async move {
+            loop {
+                if utils::is_online() {
+                    // Created per pass so newly configured API keys are picked up
+                    let service = SearchService::new();
+                    let works = app_state.peek().works.peek().clone();
+                    let outcomes =
+                        data::matching::auto_match_manual_works(&service, works.clone()).await;
+                    let find_work = move |work_id: Uuid| {
+                        let state = app_state.peek();
+                        let work = state.works.peek().iter().find(|w| w.id == work_id).cloned();
+                        work
+                    };
+                    for (work_id, outcome) in outcomes {
+                        // The user may have linked, edited or deleted the work while the
+                        // providers were searched: their change wins
+                        let before = works.iter().find(|w| w.id == work_id);
+                        let Some(mut work) = find_work(work_id).filter(|w| Some(w) == before)
+                        else {
+                            continue;
+                        };
+                        if let MatchOutcome::Confident(result) = outcome {
+                            let Some(linked) =
+                                app_state.write().link_work_to_provider(work_id, &result)
+                            else {
+                                continue;
+                            };
+                            let work = match data::refresh::refresh_work(&service, linked.clone())
+                                .await
+                            {
+                                Ok(refresh) => refresh.work,
+                                Err(e) => {
+                                    info!("Could not fetch details for linked work: {}", e);
+                                    linked.clone()
+                                }
+                            };
+                            // Same again for the details fetch
+                            if find_work(work.id).as_ref() == Some(&linked) {
+                                app_state.write().update_work(work.clone());
+                            }
+                            auto_linked_works.write().push(work);
+                        } else {
+                            data::matching::record_outcome(&mut work, &outcome, chrono::Utc::now());
+                            app_state.write().update_work(work);
+                        }
+                    }
+                }
+                gloo_timers::future::TimeoutFuture::new(AUTO_MATCH_POLL_MS).await;
+            }
+        });
+    });
+
     // Current mnemon index for hero display
     let mut current_index = use_signal(|| 0usize);
     let mut is_transitioning = use_signal(|| false);
@@ -90,6 +200,21 @@ fn App() -> Element {
     // Settings modal state
     let mut show_settings = use_signal(|| false);
 
+    // Duplicate works review state
+    let mut show_duplicates = use_signal(|| false);
+
+    // "On this day" anniversaries list state
+    let mut show_anniversaries = use_signal(|| false);
+
+    // Uploads the flows didn't keep (cancelled, or replaced before saving) are
+    // deleted once they close, and earlier sessions' leftovers once loading is done
+    let loaded = app_state.peek().loaded;
+    use_effect(move || {
+        if loaded() && !show_add_flow() && edit_mnemon_id().is_none() {
+            app_state.peek().collect_unused_assets();
+        }
+    });
+
     // Pending delete state for undo functionality
     let mut pending_delete: Signal<Option<PendingDelete>> = use_signal(|| None);
 
@@ -111,6 +236,59 @@ fn App() -> Element {
 
     let next_mnemon_with_work = use_memo(move || next_index().and_then(get_mnemon_by_index));
 
+    // Record each mnemon the hero lands on, so the next load holds it back for a while
+    let mut last_impression = use_signal(|| Option::<Uuid>::None);
+    use_effect(move || {
+        let Some(current) = current_mnemon_with_work() else {
+            return;
+        };
+        let mnemon_id = current.mnemon.id;
+        if *last_impression.peek() != Some(mnemon_id) {
+            last_impression.set(Some(mnemon_id));
+            app_state.peek().record_impression(mnemon_id);
+        }
+    });
+
+    // While a theme plays, the hero's slide to the next mnemon crossfades into that
+    // work's theme. Works without music are skipped over: the theme already playing
+    // carries on through them
+    use_effect(move || {
+        let Some(next) = next_mnemon_with_work() else {
+            return;
+        };
+        if *radio.active.peek() || *audio.status.peek() != audio::PlaybackStatus::Playing {
+            return;
+        }
+        if let Some(track) = audio::ThemeTrack::for_work(&next.work, &app_state.peek()) {
+            audio.crossfade_to(track, HERO_TRANSITION_MS);
+        }
+    });
+
+    // When the hero lands on another mnemon without a transition (or the transition's
+    // fade was skipped), catch up the same way; a paused theme is unloaded
+    use_effect(move || {
+        let current = current_mnemon_with_work();
+        if *radio.active.peek() {
+            return;
+        }
+        let Some(playing_work_id) = audio.track.peek().as_ref().map(|t| t.work_id) else {
+            return;
+        };
+        let current_work = current.as_ref().map(|mw| &mw.work);
+        if current_work.is_some_and(|w| w.id == playing_work_id) {
+            return;
+        }
+        if *audio.status.peek() != audio::PlaybackStatus::Playing {
+            audio.stop();
+            return;
+        }
+        if let Some(track) =
+            current_work.and_then(|w| audio::ThemeTrack::for_work(w, &app_state.peek()))
+        {
+            audio.crossfade_to(track, HERO_TRANSITION_MS);
+        }
+    });
+
     // Manual navigation function
     let mut navigate = move |direction: Direction| {
         if is_transitioning() {
@@ -226,6 +404,7 @@ fn App() -> Element {
     });
 
     let has_mnemons = !mnemons_with_works().is_empty();
+    let has_matches = app_state.read().mnemons_count() > 0;
     let is_loaded = app_state.peek().is_loaded();
 
     rsx! {
@@ -237,7 +416,7 @@ fn App() -> Element {
             class: "h-screen w-screen overflow-hidden bg-gray-900",
 
             // Show loading state until IndexedDB data is loaded
-            if is_loaded && has_mnemons {
+            if is_loaded && has_mnemons && has_matches {
                 // Positioning container for overlapping Heroes during transitions
                 div {
                     class: "relative h-full w-full overflow-hidden",
@@ -304,8 +483,12 @@ fn App() -> Element {
                         on_delete: move |mnemon_id: Uuid| {
                             // Remove from memory and store for potential undo
                             let removed = app_state.write().remove_mnemon(mnemon_id);
-                            if let Some((mnemon, original_idx)) = removed {
-                                pending_delete.set(Some(PendingDelete { mnemon, original_idx }));
+                            if let Some((mnemon, original_idx, impression)) = removed {
+                                pending_delete.set(Some(PendingDelete {
+                                    mnemon,
+                                    original_idx,
+                                    impression,
+                                }));
                                 // Close details view
                                 details_open.set(false);
                                 // Adjust current index if needed
@@ -321,9 +504,19 @@ fn App() -> Element {
                         on_navigate_next: move |_| {
                             navigate(Direction::Forward);
                         },
+                        on_anniversaries: move |_| {
+                            show_anniversaries.set(true);
+                        },
                     }
                 }
                 }
+            } else if is_loaded && has_mnemons {
+                FilteredEmptyState {
+                    on_clear: move |_| {
+                        app_state.write().set_slideshow_filter(Default::default());
+                        current_index.set(0);
+                    }
+                }
             } else if is_loaded {
                 EmptyState {
                     on_click: move |_| {
@@ -342,7 +535,10 @@ fn App() -> Element {
                         let year = form.year.trim().parse::<u16>().ok();
 
                         // First, do reads without holding a write lock
-                        let existing_work_id = if let Some(ref provider_ref) = form.provider_ref {
+                        let existing_work_id = if let Some(id) = form.existing_work_id {
+                            info!("Attaching to library work: {}", id);
+                            Some(id)
+                        } else if let Some(ref provider_ref) = form.provider_ref {
                             app_state.peek().find_work_by_provider_ref(provider_ref).map(|w| {
                                 info!("Reusing existing work: {}", w.title_en);
                                 w.id
@@ -353,10 +549,20 @@ fn App() -> Element {
 
                         // Now do writes
                         let work_id = if let Some(id) = existing_work_id {
+                            if form.has_uploaded_cover() {
+                                app_state.write().set_work_covers(id, form.covers());
+                            }
+                            if form.has_uploaded_theme_music() {
+                                app_state.write().set_work_theme_music(
+                                    id,
+                                    form.theme_music_url.clone(),
+                                    form.theme_music_trim,
+                                );
+                            }
                             id
                         } else if let Some(ref provider_ref) = form.provider_ref {
                             // Create new work from provider
-                            let work = Work::from_provider(
+                            let mut work = Work::from_provider(
                                 form.work_type.clone().unwrap(),
                                 form.title.clone(),
                                 year,
@@ -364,15 +570,38 @@ fn App() -> Element {
                                 form.theme_music_url.clone(),
                                 provider_ref.clone(),
                             );
+                            work.original_title = form.original_title.clone();
+                            work.title_language =
+                                SearchService::title_language(&provider_ref.provider_source);
+                            work.backdrop_url = form.backdrop_url.clone();
+                            work.theme_music_trim = form.theme_music_trim;
+                            if form.has_uploaded_cover() {
+                                work.set_covers(form.covers());
+                            }
                             info!("Created new work from provider: {}", work.title_en);
-                            app_state.write().add_work(work)
+                            let new_work = work.clone();
+                            let id = app_state.write().add_work(work);
+
+                            // Fill in descriptive metadata from the provider's details
+                            spawn(async move {
+                                match data::refresh::refresh_work(&SearchService::new(), new_work).await {
+                                    Ok(refresh) => app_state.write().update_work(refresh.work),
+                                    Err(e) => info!("Could not fetch details for new work: {}", e),
+                                }
+                            });
+                            id
                         } else {
                             // Create manual work
-                            let work = Work::from_manual(
+                            let mut work = Work::from_manual(
                                 form.work_type.clone().unwrap(),
                                 form.title.clone(),
                                 year,
                             );
+                            if form.has_uploaded_cover() {
+                                work.set_covers(form.covers());
+                            }
+                            work.theme_music_local_uri = form.theme_music_url.clone();
+                            work.theme_music_trim = form.theme_music_trim;
                             info!("Created manual work: {}", work.title_en);
                             app_state.write().add_work(work)
                         };
@@ -386,7 +615,9 @@ fn App() -> Element {
                         let shuffled_position = app_state.write().add_mnemon(mnemon);
 
                         // Set current index to the shuffled position of the new mnemon
-                        current_index.set(shuffled_position);
+                        if let Some(shuffled_position) = shuffled_position {
+                            current_index.set(shuffled_position);
+                        }
 
                         show_add_flow.set(false);
                     },
@@ -403,6 +634,9 @@ fn App() -> Element {
                     let all_mnemons_with_works = mnemons_with_works();
                     if let Some(mnemon_with_work) = all_mnemons_with_works.iter().find(|mw| mw.mnemon.id == editing_id) {
                         let initial_form = MnemonForm::from_mnemon_for_edit(&mnemon_with_work.mnemon, &mnemon_with_work.work);
+                        let work_id = mnemon_with_work.work.id;
+                        let initial_covers = initial_form.covers();
+                        let initial_theme_music = (initial_form.theme_music_url.clone(), initial_form.theme_music_trim);
                         rsx! {
                             EditMnemonFlow {
                                 initial_form: initial_form,
@@ -419,6 +653,18 @@ fn App() -> Element {
                                         notes,
                                     );
 
+                                    // Covers picked from the provider's images
+                                    let covers = form.covers();
+                                    if covers != initial_covers {
+                                        app_state.write().set_work_covers(work_id, covers);
+                                    }
+
+                                    // Theme music attached or trimmed
+                                    let theme_music = (form.theme_music_url.clone(), form.theme_music_trim);
+                                    if theme_music != initial_theme_music {
+                                        app_state.write().set_work_theme_music(work_id, theme_music.0, theme_music.1);
+                                    }
+
                                     info!("Updated mnemon: {}", editing_id);
                                     edit_mnemon_id.set(None);
                                 },
@@ -439,9 +685,16 @@ fn App() -> Element {
                     message: "Memory deleted".to_string(),
                     on_undo: move |_| {
                         if let Some(pending) = pending_delete.take() {
-                            app_state.write().restore_mnemon(pending.mnemon, pending.original_idx);
-                            // Set index to 0 to show restored mnemon
-                            current_index.set(0);
+                            let mnemon_id = pending.mnemon.id;
+                            app_state.write().restore_mnemon(
+                                pending.mnemon,
+                                pending.original_idx,
+                                pending.impression,
+                            );
+                            // Show the restored mnemon (first in the cycle, once the
+                            // slideshow filter lets it through)
+                            let position = app_state.write().reveal_mnemon(mnemon_id);
+                            current_index.set(position.unwrap_or(0));
                         }
                     },
                     on_timeout: move |_| {
@@ -453,12 +706,80 @@ fn App() -> Element {
                 }
             }
 
+            // Background metadata refresh summary
+            if !refreshed_works().is_empty() {
+                MetadataRefreshToast {
+                    refreshes: refreshed_works(),
+                    on_dismiss: move |_| {
+                        refreshed_works.set(Vec::new());
+                    }
+                }
+            }
+
+            // Review inbox for background match suggestions
+            MatchInbox {
+                auto_linked: auto_linked_works(),
+                on_dismiss_linked: move |_| {
+                    auto_linked_works.set(Vec::new());
+                }
+            }
+
+            // Theme music radio (only once there is a library to play)
+            if is_loaded && has_mnemons {
+                RadioBar {}
+            }
+
+            // Slideshow filter (hidden behind a button until opened)
+            if is_loaded && has_mnemons {
+                SlideshowFilterBar {
+                    // A new cycling order: start it over
+                    on_change: move |_| {
+                        current_index.set(0);
+                        details_open.set(false);
+                    }
+                }
+            }
+
             // Settings modal
             if show_settings() {
                 SettingsModal {
                     paused: paused,
                     on_close: move |_| {
                         show_settings.set(false);
+                    },
+                    on_review_duplicates: move |_| {
+                        show_settings.set(false);
+                        show_duplicates.set(true);
+                    },
+                    on_show_anniversaries: move |_| {
+                        show_settings.set(false);
+                        show_anniversaries.set(true);
+                    }
+                }
+            }
+
+            // "On this day" anniversaries
+            if show_anniversaries() {
+                AnniversariesModal {
+                    on_select: move |mnemon_id: Uuid| {
+                        let position = app_state.write().reveal_mnemon(mnemon_id);
+                        if let Some(position) = position {
+                            current_index.set(position);
+                            details_open.set(false);
+                        }
+                        show_anniversaries.set(false);
+                    },
+                    on_close: move |_| {
+                        show_anniversaries.set(false);
+                    }
+                }
+            }
+
+            // Duplicate works review
+            if show_duplicates() {
+                DuplicatesModal {
+                    on_close: move |_| {
+                        show_duplicates.set(false);
                     }
                 }
             }
@@ -0,0 +1,81 @@
+use super::{ProviderRef, SearchResult, WorkMetadata, WorkType};
+
+/// Current metadata for a work, as reported by its provider
+#[derive(Clone, PartialEq, Debug)]
+pub struct WorkDetails {
+    pub provider_ref: ProviderRef,
+
+    pub work_type: WorkType,
+
+    pub title: String,
+
+    pub original_title: Option<String>,
+
+    /// Other known titles (translations, romanizations, working titles)
+    pub alternate_titles: Vec<String>,
+
+    pub year: Option<u16>,
+
+    pub cover_url: Option<String>,
+
+    /// Wide landscape art (TMDB backdrop, RAWG background)
+    pub backdrop_url: Option<String>,
+
+    pub metadata: WorkMetadata,
+
+    /// Ids of the same work in other databases (imdb and wikidata, from TMDB)
+    pub external_ids: Vec<ProviderRef>,
+
+    /// Display language the title was localized in (none when the provider doesn't
+    /// localize titles)
+    pub language: Option<String>,
+}
+
+/// An image a provider offers for a work (poster, backdrop or screenshot)
+#[derive(Clone, PartialEq, Debug)]
+pub struct ProviderImage {
+    pub url: String,
+
+    pub width: u32,
+
+    pub height: u32,
+}
+
+impl ProviderImage {
+    pub fn is_landscape(&self) -> bool {
+        self.width > self.height
+    }
+}
+
+impl From<SearchResult> for WorkDetails {
+    fn from(result: SearchResult) -> Self {
+        Self {
+            provider_ref: result.provider_ref,
+            work_type: result.work_type,
+            title: result.title,
+            original_title: result.original_title,
+            alternate_titles: Vec::new(),
+            year: result.year,
+            cover_url: result.cover_url,
+            backdrop_url: result.backdrop_url,
+            metadata: WorkMetadata::default(),
+            external_ids: Vec::new(),
+            language: None,
+        }
+    }
+}
+
+impl From<WorkDetails> for SearchResult {
+    fn from(details: WorkDetails) -> Self {
+        Self {
+            provider_ref: details.provider_ref,
+            title: details.title,
+            original_title: details.original_title,
+            year: details.year,
+            work_type: details.work_type,
+            cover_url: details.cover_url,
+            backdrop_url: details.backdrop_url,
+            theme_music_url: None,
+        }
+    }
+}
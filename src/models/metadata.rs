@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+/// Descriptive metadata for a work, filled from provider detail endpoints
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkMetadata {
+    pub synopsis: Option<String>,
+
+    pub genres: Vec<String>,
+
+    /// Directors (movies), creators (TV) or developers (games)
+    pub creators: Vec<String>,
+
+    /// Production companies, networks or publishers
+    pub studios: Vec<String>,
+
+    pub runtime_minutes: Option<u32>,
+
+    pub episode_count: Option<u32>,
+
+    /// Platforms a game was released on
+    pub platforms: Vec<String>,
+}
+
+impl WorkMetadata {
+    pub fn is_empty(&self) -> bool {
+        *self == WorkMetadata::default()
+    }
+
+    /// Case-insensitive genre match (for filtering)
+    pub fn has_genre(&self, genre: &str) -> bool {
+        self.genres.iter().any(|g| g.eq_ignore_ascii_case(genre))
+    }
+
+    /// Case-insensitive platform match (for filtering)
+    pub fn has_platform(&self, platform: &str) -> bool {
+        self.platforms
+            .iter()
+            .any(|p| p.eq_ignore_ascii_case(platform))
+    }
+
+    /// Human readable length, e.g. "2h 5m" or "62 episodes"
+    pub fn length_label(&self) -> Option<String> {
+        if let Some(minutes) = self.runtime_minutes.filter(|m| *m > 0) {
+            return Some(match (minutes / 60, minutes % 60) {
+                (0, m) => format!("{}m", m),
+                (h, 0) => format!("{}h", h),
+                (h, m) => format!("{}h {}m", h, m),
+            });
+        }
+        match self.episode_count {
+            Some(1) => Some("1 episode".to_string()),
+            Some(count) if count > 1 => Some(format!("{} episodes", count)),
+            _ => None,
+        }
+    }
+}
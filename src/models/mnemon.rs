@@ -14,6 +14,21 @@ pub struct Mnemon {
     pub notes: Vec<String>,
 
     pub created_at: chrono::DateTime<chrono::Utc>,
+
+    /// Marked by the user to resurface more often
+    #[serde(default)]
+    pub favorite: bool,
+}
+
+/// How often, and when last, a mnemon was shown in the hero
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Impression {
+    /// ID of the mnemon
+    pub id: Uuid,
+
+    pub last_shown_at: chrono::DateTime<chrono::Utc>,
+
+    pub times_shown: u32,
 }
 
 impl Mnemon {
@@ -30,6 +45,13 @@ impl Mnemon {
             feelings,
             notes,
             created_at: chrono::Utc::now(),
+            favorite: false,
         }
     }
+
+    /// Finished date as a calendar date (as entered in the date picker, YYYY-MM-DD)
+    pub fn finished_on(&self) -> Option<chrono::NaiveDate> {
+        let date = self.finished_date.as_deref()?;
+        chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok()
+    }
 }
@@ -1,9 +1,15 @@
+mod details;
+mod metadata;
 mod mnemon;
 mod provider;
 mod search;
 mod work;
 
-pub use mnemon::Mnemon;
+pub use details::{ProviderImage, WorkDetails};
+pub use metadata::WorkMetadata;
+pub use mnemon::{Impression, Mnemon};
 pub use provider::ProviderRef;
 pub use search::{SearchResult, SearchResultsPage};
-pub use work::{Work, WorkType};
+pub use work::{
+    normalize_title, release_decade, ThemeTrim, Work, WorkField, WorkType, WORK_SCHEMA_VERSION,
+};
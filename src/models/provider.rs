@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct ProviderRef {
+    /// Provider or id database: "tmdb", "rawg", "anilist" or "igdb" (fixtures), or
+    /// "imdb" or "wikidata" for external ids
     pub provider_source: String,
 
     pub provider_id: String,
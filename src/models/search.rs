@@ -1,22 +1,30 @@
 use super::{ProviderRef, WorkType};
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct SearchResult {
     pub provider_ref: ProviderRef,
 
     pub title: String,
 
+    /// Title in the work's original language, when it differs
+    #[serde(default)]
+    pub original_title: Option<String>,
+
     pub year: Option<u16>,
 
     pub work_type: WorkType,
 
     pub cover_url: Option<String>,
 
+    /// Wide landscape art (TMDB backdrop, RAWG background)
+    #[serde(default)]
+    pub backdrop_url: Option<String>,
+
     pub theme_music_url: Option<String>,
 }
 
 #[derive(Clone, Debug)]
-#[allow(dead_code)]
 pub struct SearchResultsPage {
     pub results: Vec<SearchResult>,
 
@@ -27,7 +35,6 @@ pub struct SearchResultsPage {
     pub total_pages: usize,
 }
 
-#[allow(dead_code)]
 impl SearchResultsPage {
     pub fn new(
         results: Vec<SearchResult>,
@@ -47,11 +54,18 @@ impl SearchResultsPage {
         self.page + 1 < self.total_pages
     }
 
+    #[allow(dead_code)]
     pub fn has_previous_page(&self) -> bool {
         self.page > 0
     }
 
+    #[allow(dead_code)]
     pub fn is_empty(&self) -> bool {
         self.results.is_empty()
     }
+
+    /// An empty first page (used when there is nothing to search for)
+    pub fn empty(page: usize) -> Self {
+        Self::new(Vec::new(), 0, page, 0)
+    }
 }
@@ -1,4 +1,4 @@
-use super::ProviderRef;
+use super::{ProviderRef, SearchResult, WorkMetadata};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -10,6 +10,9 @@ pub enum WorkType {
 }
 
 impl WorkType {
+    /// All work types, in the order search groups are displayed
+    pub const ALL: [WorkType; 3] = [WorkType::Movie, WorkType::TvAnime, WorkType::Game];
+
     pub fn icon(&self) -> &'static str {
         match self {
             WorkType::Movie => "🎬",
@@ -33,25 +36,144 @@ pub enum WorkOrigin {
     Manual,
 }
 
+/// First year of the decade a year falls in (e.g. 1990 for 1995)
+pub fn release_decade(year: u16) -> u16 {
+    year - year % 10
+}
+
+/// Lowercase a title and reduce punctuation and whitespace to single spaces
+///
+/// Letters in any script are kept, so original titles (e.g. Japanese) still match.
+pub fn normalize_title(title: &str) -> String {
+    title
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Current shape of stored works; older records are migrated on load
+///
+/// Bump it when stored records need rewriting. Fields added with `#[serde(default)]`
+/// load fine from older records and don't need a bump on their own.
+pub const WORK_SCHEMA_VERSION: u32 = 2;
+
+/// Provider-backed fields of a work that the user can override
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum WorkField {
+    Title,
+    ReleaseYear,
+    Cover,
+}
+
+impl WorkField {
+    pub fn label(&self) -> &'static str {
+        match self {
+            WorkField::Title => "Title",
+            WorkField::ReleaseYear => "Year",
+            WorkField::Cover => "Cover",
+        }
+    }
+}
+
+/// Segment of a theme music track to play, in seconds from the start of the file
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ThemeTrim {
+    pub start_secs: f64,
+    /// End of the segment (the end of the track when unset)
+    pub end_secs: Option<f64>,
+}
+
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct Work {
     pub id: Uuid,
 
     pub work_type: WorkType,
 
+    /// Display title, in the display language chosen when the work was added
     pub title_en: String,
 
+    /// Title in the work's original language, when it differs
+    #[serde(default)]
+    pub original_title: Option<String>,
+
+    /// Display language the provider localized the title in (none for manual titles,
+    /// providers that don't localize, and works saved before it was recorded)
+    #[serde(default)]
+    pub title_language: Option<String>,
+
+    /// Other known titles (translations, romanizations, working titles)
+    #[serde(default)]
+    pub alternate_titles: Vec<String>,
+
     pub release_year: Option<u16>,
 
     pub cover_image_local_uri: Option<String>,
 
+    /// Wide landscape art, preferred by the hero on wide screens
+    #[serde(default)]
+    pub backdrop_url: Option<String>,
+
+    /// Further covers the user picked, in order, shown when the cover fails to load
+    #[serde(default)]
+    pub cover_fallbacks: Vec<String>,
+
     pub theme_music_local_uri: Option<String>,
 
+    /// Part of the theme music to play (the whole track when unset)
+    #[serde(default)]
+    pub theme_music_trim: Option<ThemeTrim>,
+
     pub provider_ref: Option<ProviderRef>,
 
     pub origin: WorkOrigin,
 
     pub created_at: chrono::DateTime<chrono::Utc>,
+
+    /// Fields the user chose to keep, which metadata refreshes leave untouched
+    #[serde(default)]
+    pub overridden_fields: Vec<WorkField>,
+
+    /// Last time metadata was refreshed from the provider
+    #[serde(default)]
+    pub metadata_refreshed_at: Option<chrono::DateTime<chrono::Utc>>,
+
+    #[serde(default)]
+    pub metadata: WorkMetadata,
+
+    /// Last time a manual work was searched for in the background
+    #[serde(default)]
+    pub match_checked_at: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// Possible provider matches waiting for the user's review
+    #[serde(default)]
+    pub match_candidates: Vec<SearchResult>,
+
+    /// The user rejected the suggested matches; don't auto-match again
+    #[serde(default)]
+    pub match_dismissed: bool,
+
+    /// Works merged into this one, so stale copies are folded in rather than recreated
+    #[serde(default)]
+    pub merged_ids: Vec<Uuid>,
+
+    /// Other ids of the same work: IMDb and Wikidata ids from TMDB's external ids,
+    /// and the provider entries of merged duplicates (RAWG has no cross-references)
+    ///
+    /// Saved as `merged_provider_refs` before it held external ids too.
+    #[serde(default, alias = "merged_provider_refs")]
+    pub external_ids: Vec<ProviderRef>,
+
+    /// Works the user marked as not being duplicates of this one
+    #[serde(default)]
+    pub distinct_from: Vec<Uuid>,
+
+    /// Schema version the record was written with (0 for records that predate it)
+    #[serde(default)]
+    pub schema_version: u32,
 }
 
 impl Work {
@@ -67,12 +189,28 @@ impl Work {
             id: Uuid::new_v4(),
             work_type,
             title_en: title,
+            original_title: None,
+            title_language: None,
+            alternate_titles: Vec::new(),
             release_year,
             cover_image_local_uri: cover_url,
+            backdrop_url: None,
+            cover_fallbacks: Vec::new(),
             theme_music_local_uri: theme_music_url,
+            theme_music_trim: None,
             provider_ref: Some(provider_ref),
             origin: WorkOrigin::Provider,
             created_at: chrono::Utc::now(),
+            overridden_fields: Vec::new(),
+            metadata_refreshed_at: None,
+            metadata: WorkMetadata::default(),
+            match_checked_at: None,
+            match_candidates: Vec::new(),
+            match_dismissed: false,
+            merged_ids: Vec::new(),
+            external_ids: Vec::new(),
+            distinct_from: Vec::new(),
+            schema_version: WORK_SCHEMA_VERSION,
         }
     }
 
@@ -81,12 +219,376 @@ impl Work {
             id: Uuid::new_v4(),
             work_type,
             title_en: title,
+            original_title: None,
+            title_language: None,
+            alternate_titles: Vec::new(),
             release_year,
             cover_image_local_uri: None,
+            backdrop_url: None,
+            cover_fallbacks: Vec::new(),
             theme_music_local_uri: None,
+            theme_music_trim: None,
             provider_ref: None,
             origin: WorkOrigin::Manual,
             created_at: chrono::Utc::now(),
+            overridden_fields: Vec::new(),
+            metadata_refreshed_at: None,
+            metadata: WorkMetadata::default(),
+            match_checked_at: None,
+            match_candidates: Vec::new(),
+            match_dismissed: false,
+            merged_ids: Vec::new(),
+            external_ids: Vec::new(),
+            distinct_from: Vec::new(),
+            schema_version: WORK_SCHEMA_VERSION,
+        }
+    }
+
+    /// Display, original and alternate titles, without duplicates
+    pub fn titles(&self) -> Vec<&str> {
+        let mut titles: Vec<&str> = vec![self.title_en.as_str()];
+        for title in self
+            .original_title
+            .iter()
+            .chain(self.alternate_titles.iter())
+        {
+            if !titles
+                .iter()
+                .any(|t| normalize_title(t) == normalize_title(title))
+            {
+                titles.push(title);
+            }
+        }
+        titles
+    }
+
+    /// First title containing the query (ignoring case, punctuation and spacing)
+    pub fn matching_title(&self, query: &str) -> Option<&str> {
+        let query = normalize_title(query);
+        if query.is_empty() {
+            return None;
+        }
+        self.titles()
+            .into_iter()
+            .find(|title| normalize_title(title).contains(&query))
+    }
+
+    /// Link a manual work to the provider entry the user picked
+    ///
+    /// Provider fields replace the typed ones (the typed title stays searchable as an
+    /// alternate title), except a cover the user picked, and the work becomes due for a
+    /// metadata refresh.
+    pub fn link_to_provider(&mut self, result: &SearchResult) {
+        if !result.title.trim().is_empty() && result.title != self.title_en {
+            let typed = std::mem::replace(&mut self.title_en, result.title.clone());
+            self.add_alternate_title(typed);
+        }
+        if result.original_title.is_some() {
+            self.original_title = result.original_title.clone();
+        }
+        if result.year.is_some() {
+            self.release_year = result.year;
+        }
+        if result.cover_url.is_some() && !self.is_overridden(WorkField::Cover) {
+            self.cover_image_local_uri = result.cover_url.clone();
+        }
+        if result.backdrop_url.is_some() {
+            self.backdrop_url = result.backdrop_url.clone();
+        }
+        self.provider_ref = Some(result.provider_ref.clone());
+        self.origin = WorkOrigin::Provider;
+        self.metadata_refreshed_at = None;
+        self.match_candidates.clear();
+    }
+
+    /// Whether the work is known by the given id, as its provider entry or an external id
+    pub fn has_provider_ref(&self, provider_ref: &ProviderRef) -> bool {
+        self.provider_ref
+            .iter()
+            .chain(self.external_ids.iter())
+            .any(|r| r.matches(provider_ref))
+    }
+
+    /// Whether two works share any provider or external id
+    pub fn shares_id_with(&self, other: &Work) -> bool {
+        other
+            .provider_ref
+            .iter()
+            .chain(other.external_ids.iter())
+            .any(|r| self.has_provider_ref(r))
+    }
+
+    /// Record another id for the work unless it is already known
+    pub fn add_external_id(&mut self, external_id: ProviderRef) {
+        if !external_id.provider_id.is_empty() && !self.has_provider_ref(&external_id) {
+            self.external_ids.push(external_id);
+        }
+    }
+
+    /// The cover followed by the picked fallbacks
+    pub fn covers(&self) -> impl Iterator<Item = &String> {
+        self.cover_image_local_uri
+            .iter()
+            .chain(self.cover_fallbacks.iter())
+    }
+
+    /// Use the user's picked covers, the first as the cover and the rest as fallbacks
+    ///
+    /// The pick is kept across metadata refreshes. Removing every cover hands the
+    /// cover back to the provider, on the next refresh.
+    pub fn set_covers(&mut self, covers: Vec<String>) {
+        let mut covers = covers.into_iter();
+        self.cover_image_local_uri = covers.next();
+        self.cover_fallbacks = covers.collect();
+        if self.cover_image_local_uri.is_some() {
+            self.set_overridden(WorkField::Cover);
+        } else {
+            self.overridden_fields.retain(|f| *f != WorkField::Cover);
+            if self.provider_ref.is_some() {
+                self.metadata_refreshed_at = None;
+            }
+        }
+    }
+
+    /// Whether another work has a different value for a field
+    pub fn field_differs(&self, other: &Work, field: WorkField) -> bool {
+        match field {
+            WorkField::Title => self.title_en != other.title_en,
+            WorkField::ReleaseYear => self.release_year != other.release_year,
+            WorkField::Cover => self.cover_image_local_uri != other.cover_image_local_uri,
+        }
+    }
+
+    /// Fold a duplicate of this work into it
+    ///
+    /// `fields` are taken from the duplicate and everything else is kept. A manual
+    /// work adopts the duplicate's provider entry; values that didn't come from the
+    /// surviving provider entry are kept across refreshes. The duplicate's titles stay
+    /// searchable and its ids are remembered.
+    pub fn absorb(&mut self, duplicate: &Work, fields: &[WorkField]) {
+        let adopts_provider = self.provider_ref.is_none() && duplicate.provider_ref.is_some();
+        let previous_title = self.title_en.clone();
+
+        for field in [WorkField::Title, WorkField::ReleaseYear, WorkField::Cover] {
+            let from_duplicate = fields.contains(&field);
+            if !self.field_differs(duplicate, field) {
+                continue;
+            }
+            if from_duplicate {
+                match field {
+                    WorkField::Title => self.title_en = duplicate.title_en.clone(),
+                    WorkField::ReleaseYear => self.release_year = duplicate.release_year,
+                    WorkField::Cover => {
+                        self.cover_image_local_uri = duplicate.cover_image_local_uri.clone();
+                        self.cover_fallbacks = duplicate.cover_fallbacks.clone();
+                    }
+                }
+            }
+            let from_provider = if adopts_provider {
+                from_duplicate && !duplicate.is_overridden(field)
+            } else {
+                !from_duplicate && !self.is_overridden(field)
+            };
+            if (adopts_provider || self.provider_ref.is_some()) && !from_provider {
+                self.set_overridden(field);
+            }
+        }
+
+        self.add_alternate_title(previous_title);
+        for title in duplicate.titles() {
+            self.add_alternate_title(title.to_string());
+        }
+        if self.original_title.is_none() {
+            self.original_title = duplicate.original_title.clone();
         }
+        if self.backdrop_url.is_none() {
+            self.backdrop_url = duplicate.backdrop_url.clone();
+        }
+        if self.theme_music_local_uri.is_none() {
+            self.theme_music_local_uri = duplicate.theme_music_local_uri.clone();
+            self.theme_music_trim = duplicate.theme_music_trim;
+        }
+        if self.metadata == WorkMetadata::default() {
+            self.metadata = duplicate.metadata.clone();
+        }
+
+        if adopts_provider {
+            self.provider_ref = duplicate.provider_ref.clone();
+            self.origin = WorkOrigin::Provider;
+            self.metadata_refreshed_at = duplicate.metadata_refreshed_at;
+            self.match_candidates.clear();
+        }
+        for external_id in duplicate
+            .provider_ref
+            .iter()
+            .chain(duplicate.external_ids.iter())
+        {
+            self.add_external_id(external_id.clone());
+        }
+        for id in std::iter::once(duplicate.id).chain(duplicate.merged_ids.iter().copied()) {
+            if !self.merged_ids.contains(&id) {
+                self.merged_ids.push(id);
+            }
+        }
+        for id in &duplicate.distinct_from {
+            if *id != self.id && !self.distinct_from.contains(id) {
+                self.distinct_from.push(*id);
+            }
+        }
+    }
+
+    /// Add an alternate title unless the work already goes by it
+    pub fn add_alternate_title(&mut self, title: String) {
+        let known = self
+            .titles()
+            .iter()
+            .any(|t| normalize_title(t) == normalize_title(&title));
+        if !known && !title.trim().is_empty() {
+            self.alternate_titles.push(title);
+        }
+    }
+
+    pub fn is_overridden(&self, field: WorkField) -> bool {
+        self.overridden_fields.contains(&field)
+    }
+
+    /// Keep the current value of a field across future metadata refreshes
+    pub fn set_overridden(&mut self, field: WorkField) {
+        if !self.is_overridden(field) {
+            self.overridden_fields.push(field);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matching_title_checks_every_title() {
+        let mut work = Work::from_manual(WorkType::Movie, "Spirited Away".to_string(), Some(2001));
+        work.original_title = Some("千と千尋の神隠し".to_string());
+        work.alternate_titles = vec![
+            "Sen to Chihiro no Kamikakushi".to_string(),
+            "spirited away".to_string(),
+        ];
+
+        assert_eq!(work.titles().len(), 3);
+        assert_eq!(work.matching_title("SPIRITED"), Some("Spirited Away"));
+        assert_eq!(work.matching_title("千尋"), Some("千と千尋の神隠し"));
+        assert_eq!(
+            work.matching_title("sen-to chihiro"),
+            Some("Sen to Chihiro no Kamikakushi")
+        );
+        assert_eq!(work.matching_title("totoro"), None);
+    }
+
+    #[test]
+    fn test_link_to_provider_keeps_typed_title_searchable() {
+        let mut work = Work::from_manual(WorkType::Movie, "Chihiro".to_string(), None);
+        let result = SearchResult {
+            provider_ref: ProviderRef::new("tmdb", "129"),
+            title: "Spirited Away".to_string(),
+            original_title: Some("千と千尋の神隠し".to_string()),
+            year: Some(2001),
+            work_type: WorkType::Movie,
+            cover_url: Some("https://image.tmdb.org/t/p/w500/spirited.jpg".to_string()),
+            backdrop_url: None,
+            theme_music_url: None,
+        };
+
+        work.link_to_provider(&result);
+
+        assert_eq!(work.origin, WorkOrigin::Provider);
+        assert_eq!(work.provider_ref, Some(ProviderRef::new("tmdb", "129")));
+        assert_eq!(work.title_en, "Spirited Away");
+        assert_eq!(work.release_year, Some(2001));
+        assert!(work.cover_image_local_uri.is_some());
+        assert_eq!(work.matching_title("chihiro"), Some("Chihiro"));
+    }
+
+    #[test]
+    fn test_absorb_adopts_provider_and_keeps_picked_fields() {
+        let mut manual = Work::from_manual(WorkType::Movie, "Chihiro".to_string(), Some(2002));
+        let linked = Work::from_provider(
+            WorkType::Movie,
+            "Spirited Away".to_string(),
+            Some(2001),
+            Some("https://image.tmdb.org/t/p/w500/spirited.jpg".to_string()),
+            None,
+            ProviderRef::new("tmdb", "129"),
+        );
+
+        manual.absorb(&linked, &[WorkField::Title, WorkField::Cover]);
+
+        assert_eq!(manual.provider_ref, Some(ProviderRef::new("tmdb", "129")));
+        assert_eq!(manual.origin, WorkOrigin::Provider);
+        assert_eq!(manual.title_en, "Spirited Away");
+        assert!(manual.cover_image_local_uri.is_some());
+        // The typed year differs from the provider's, so refreshes leave it alone
+        assert_eq!(manual.release_year, Some(2002));
+        assert_eq!(manual.overridden_fields, vec![WorkField::ReleaseYear]);
+        assert_eq!(manual.matching_title("chihiro"), Some("Chihiro"));
+        assert_eq!(manual.merged_ids, vec![linked.id]);
+        assert!(manual.external_ids.is_empty());
+    }
+
+    #[test]
+    fn test_absorb_remembers_other_provider_entries() {
+        let mut tmdb = Work::from_provider(
+            WorkType::Movie,
+            "Spirited Away".to_string(),
+            Some(2001),
+            None,
+            None,
+            ProviderRef::new("tmdb", "129"),
+        );
+        let other = Work::from_provider(
+            WorkType::Movie,
+            "Spirited Away".to_string(),
+            Some(2001),
+            None,
+            None,
+            ProviderRef::new("anilist", "199"),
+        );
+
+        tmdb.absorb(&other, &[]);
+
+        assert!(tmdb.overridden_fields.is_empty());
+        assert!(tmdb.has_provider_ref(&ProviderRef::new("anilist", "199")));
+        assert!(tmdb.has_provider_ref(&ProviderRef::new("tmdb", "129")));
+        assert!(!tmdb.has_provider_ref(&ProviderRef::new("tmdb", "128")));
+
+        // Records saved before the field was renamed keep their merged entries
+        let mut json = serde_json::to_value(&tmdb).unwrap();
+        let refs = json["external_ids"].take();
+        json.as_object_mut().unwrap().remove("external_ids");
+        json["merged_provider_refs"] = refs;
+        let loaded: Work = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded.external_ids, tmdb.external_ids);
+    }
+
+    #[test]
+    fn test_set_covers_keeps_the_users_order() {
+        let mut work = Work::from_manual(WorkType::Game, "Hades".to_string(), None);
+
+        work.set_covers(vec![
+            "https://media.rawg.io/media/screenshots/b.jpg".to_string(),
+            "https://media.rawg.io/media/screenshots/a.jpg".to_string(),
+        ]);
+
+        assert_eq!(
+            work.covers().collect::<Vec<_>>(),
+            vec![
+                "https://media.rawg.io/media/screenshots/b.jpg",
+                "https://media.rawg.io/media/screenshots/a.jpg",
+            ]
+        );
+        assert!(work.is_overridden(WorkField::Cover));
+
+        // No covers left: the provider's cover comes back
+        work.set_covers(Vec::new());
+        assert_eq!(work.covers().count(), 0);
+        assert!(!work.is_overridden(WorkField::Cover));
     }
 }
@@ -0,0 +1,330 @@
+//! Shared HTTP layer for provider clients
+//!
+//! Wraps `reqwest` GET requests with the rate-limit handling every provider needs:
+//! - 429 responses honor `Retry-After` and put the provider into a cooldown
+//! - 5xx responses are retried with jittered exponential backoff
+//! - While a provider is cooling down, requests fail fast without hitting the network
+//!
+//! Cooldowns are tracked per provider name for the whole app, so `status()` on a
+//! client reports `ProviderStatus::RateLimited` until the cooldown expires.
+
+use crate::providers::{ProviderError, ProviderResult};
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use reqwest::header::RETRY_AFTER;
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock, PoisonError};
+use tracing::info;
+
+/// Cooldown expiry per provider name
+fn cooldowns() -> &'static Mutex<HashMap<&'static str, DateTime<Utc>>> {
+    static COOLDOWNS: OnceLock<Mutex<HashMap<&'static str, DateTime<Utc>>>> = OnceLock::new();
+    COOLDOWNS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Retry behaviour for idempotent provider requests
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Retries after the initial attempt
+    pub max_retries: u32,
+    /// Backoff ceiling for the first retry (doubles on each further retry)
+    pub base_delay_ms: u32,
+    /// Longest the client will wait before a retry; longer `Retry-After`s give up
+    pub max_delay_ms: u32,
+    /// Cooldown applied after a 429 that carried no `Retry-After`
+    pub default_cooldown_secs: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            base_delay_ms: 500,
+            max_delay_ms: 4_000,
+            default_cooldown_secs: 30,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Full-jitter exponential backoff for the given retry attempt (0-based)
+    pub fn backoff_ms(&self, attempt: u32, rng: &mut impl Rng) -> u32 {
+        let ceiling = self
+            .base_delay_ms
+            .saturating_mul(1u32.checked_shl(attempt).unwrap_or(u32::MAX))
+            .min(self.max_delay_ms);
+        rng.gen_range(0..=ceiling)
+    }
+}
+
+/// Parse a `Retry-After` header (delay in seconds or an HTTP date) into seconds
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<u64> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(seconds);
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some((date.with_timezone(&Utc) - now).num_seconds().max(0) as u64)
+}
+
+/// Wait before retrying (no-op for a zero delay)
+async fn sleep_ms(ms: u32) {
+    if ms > 0 {
+        gloo_timers::future::TimeoutFuture::new(ms).await;
+    }
+}
+
+/// Rate-limit aware HTTP client shared by provider implementations
+#[derive(Clone)]
+pub struct ProviderHttp {
+    /// Provider name used for cooldown tracking and logs
+    name: &'static str,
+    /// HTTP client
+    client: reqwest::Client,
+    /// Retry behaviour
+    policy: RetryPolicy,
+    /// Maps transport failures to provider errors
+    send_error: fn(reqwest::Error) -> ProviderError,
+}
+
+impl ProviderHttp {
+    /// Create a client for the named provider with the default retry policy
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            client: reqwest::Client::new(),
+            policy: RetryPolicy::default(),
+            send_error: |e| ProviderError::NetworkError(e.to_string()),
+        }
+    }
+
+    /// Use a custom retry policy
+    pub fn with_policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Use a custom mapping for transport failures
+    pub fn with_send_error(mut self, send_error: fn(reqwest::Error) -> ProviderError) -> Self {
+        self.send_error = send_error;
+        self
+    }
+
+    /// Seconds left on the provider's rate-limit cooldown, if any
+    pub fn cooldown_remaining(&self) -> Option<u64> {
+        let mut cooldowns = cooldowns().lock().unwrap_or_else(PoisonError::into_inner);
+        let until = *cooldowns.get(self.name)?;
+        let remaining = until - Utc::now();
+        if remaining > Duration::zero() {
+            // Round up so a sub-second cooldown still reports as active
+            Some(((remaining.num_milliseconds() + 999) / 1000) as u64)
+        } else {
+            cooldowns.remove(self.name);
+            None
+        }
+    }
+
+    /// Check if the provider is currently rate limited
+    pub fn is_cooling_down(&self) -> bool {
+        self.cooldown_remaining().is_some()
+    }
+
+    /// Start (or extend) the provider's cooldown
+    fn start_cooldown(&self, seconds: u64) {
+        let until = Utc::now() + Duration::seconds(seconds as i64);
+        let mut cooldowns = cooldowns().lock().unwrap_or_else(PoisonError::into_inner);
+        let entry = cooldowns.entry(self.name).or_insert(until);
+        if *entry < until {
+            *entry = until;
+        }
+        info!("{} rate limited, cooling down for {}s", self.name, seconds);
+    }
+
+    /// Send an idempotent GET request, retrying on 429 and 5xx responses
+    ///
+    /// Returns the response for any other status; callers handle non-success
+    /// codes themselves.
+    pub async fn get(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+    ) -> ProviderResult<reqwest::Response> {
+        if let Some(retry_after_secs) = self.cooldown_remaining() {
+            return Err(ProviderError::RateLimited { retry_after_secs });
+        }
+
+        let mut attempt = 0;
+        loop {
+            let mut request = self.client.get(url);
+            for (name, value) in headers {
+                request = request.header(*name, *value);
+            }
+
+            let response = request.send().await.map_err(self.send_error)?;
+            let status = response.status();
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| parse_retry_after(v, Utc::now()));
+            let can_retry = attempt < self.policy.max_retries;
+
+            if status == StatusCode::TOO_MANY_REQUESTS {
+                let cooldown_secs = retry_after.unwrap_or(self.policy.default_cooldown_secs);
+                let wait_ms = match retry_after {
+                    Some(secs) => secs.saturating_mul(1000),
+                    None => self.policy.backoff_ms(attempt, &mut rand::thread_rng()) as u64,
+                };
+
+                if can_retry && wait_ms <= self.policy.max_delay_ms as u64 {
+                    info!("{} returned 429, retrying in {}ms", self.name, wait_ms);
+                    sleep_ms(wait_ms as u32).await;
+                    attempt += 1;
+                    continue;
+                }
+
+                self.start_cooldown(cooldown_secs);
+                return Err(ProviderError::RateLimited {
+                    retry_after_secs: cooldown_secs,
+                });
+            }
+
+            if status.is_server_error() {
+                if can_retry {
+                    let wait_ms = retry_after
+                        .map(|secs| secs.saturating_mul(1000))
+                        .filter(|ms| *ms <= self.policy.max_delay_ms as u64)
+                        .unwrap_or_else(|| {
+                            self.policy.backoff_ms(attempt, &mut rand::thread_rng()) as u64
+                        });
+                    info!(
+                        "{} returned {}, retrying in {}ms",
+                        self.name, status, wait_ms
+                    );
+                    sleep_ms(wait_ms as u32).await;
+                    attempt += 1;
+                    continue;
+                }
+
+                return Err(ProviderError::Unavailable(format!(
+                    "{} returned {} after {} attempts",
+                    self.name,
+                    status.as_u16(),
+                    attempt + 1
+                )));
+            }
+
+            return Ok(response);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::test_server::{MockResponse, MockServer};
+
+    /// Retry policy that never actually sleeps
+    fn instant_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 2,
+            base_delay_ms: 0,
+            max_delay_ms: 1_000,
+            default_cooldown_secs: 30,
+        }
+    }
+
+    #[test]
+    fn test_parse_retry_after_seconds_and_date() {
+        let now = DateTime::parse_from_rfc2822("Wed, 21 Oct 2015 07:28:00 GMT")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(parse_retry_after("120", now), Some(120));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:29:30 GMT", now),
+            Some(90)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn test_backoff_stays_within_ceiling() {
+        let policy = RetryPolicy::default();
+        let mut rng = rand::thread_rng();
+        for attempt in 0..6 {
+            let ceiling = (policy.base_delay_ms << attempt).min(policy.max_delay_ms);
+            assert!(policy.backoff_ms(attempt, &mut rng) <= ceiling);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_server_errors_then_succeeds() {
+        let server = MockServer::start(vec![
+            MockResponse::new(503, "{}"),
+            MockResponse::new(200, r#"{"ok":true}"#),
+        ]);
+        let http = ProviderHttp::new("test-5xx-retry").with_policy(instant_policy());
+
+        let response = http.get(&server.url("/search"), &[]).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(server.request_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_persistent_server_errors_are_unavailable() {
+        let server = MockServer::start(vec![MockResponse::new(500, "{}")]);
+        let http = ProviderHttp::new("test-5xx-exhausted").with_policy(instant_policy());
+
+        let result = http.get(&server.url("/search"), &[]).await;
+        assert!(matches!(result, Err(ProviderError::Unavailable(_))));
+        assert_eq!(server.request_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_short_retry_after_is_retried() {
+        let server = MockServer::start(vec![
+            MockResponse::new(429, "{}").with_header("Retry-After", "0"),
+            MockResponse::new(200, "{}"),
+        ]);
+        let http = ProviderHttp::new("test-429-retry").with_policy(instant_policy());
+
+        let response = http.get(&server.url("/search"), &[]).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!http.is_cooling_down());
+    }
+
+    #[tokio::test]
+    async fn test_long_retry_after_starts_cooldown() {
+        let server = MockServer::start(vec![
+            MockResponse::new(429, "{}").with_header("Retry-After", "120")
+        ]);
+        let http = ProviderHttp::new("test-429-cooldown").with_policy(instant_policy());
+
+        let result = http.get(&server.url("/search"), &[]).await;
+        assert!(matches!(
+            result,
+            Err(ProviderError::RateLimited {
+                retry_after_secs: 120
+            })
+        ));
+        assert!(http.is_cooling_down());
+
+        // While cooling down, requests fail fast without reaching the server
+        let result = http.get(&server.url("/search"), &[]).await;
+        assert!(matches!(result, Err(ProviderError::RateLimited { .. })));
+        assert_eq!(server.request_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_are_returned_as_is() {
+        let server = MockServer::start(vec![MockResponse::new(401, "{}")]);
+        let http = ProviderHttp::new("test-401").with_policy(instant_policy());
+
+        let response = http.get(&server.url("/search"), &[]).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(server.request_count(), 1);
+    }
+}
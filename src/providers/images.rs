@@ -0,0 +1,209 @@
+//! Image size variants
+//!
+//! Provider images are stored at a single size; each view asks for the size it needs:
+//! - TMDB URLs carry a size segment before the file name (`.../t/p/w500/abc.jpg`),
+//!   which is swapped for the wanted size
+//! - RAWG media URLs are resized through `media/resize/{width}/-/...`
+//!
+//! Any other URL (custom covers, other hosts) is used as it is.
+
+/// Shape of an image, which decides the sizes TMDB offers
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ImageKind {
+    /// Portrait poster or cover
+    Poster,
+    /// Landscape backdrop or background
+    Backdrop,
+}
+
+/// Where an image is shown
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ImageSize {
+    /// Search result rows and pickers
+    Thumbnail,
+    /// Cards and previews
+    Details,
+    /// Full-screen hero
+    Hero,
+}
+
+/// Width used in `srcset` for TMDB's "original" size (posters are usually 2000px
+/// wide, backdrops 3840px)
+fn tmdb_original_width(kind: ImageKind) -> u32 {
+    match kind {
+        ImageKind::Poster => 2000,
+        ImageKind::Backdrop => 3840,
+    }
+}
+
+/// TMDB size segment for an image kind and view
+fn tmdb_size(kind: ImageKind, size: ImageSize) -> &'static str {
+    match (kind, size) {
+        (ImageKind::Poster, ImageSize::Thumbnail) => "w185",
+        (ImageKind::Poster, ImageSize::Details) => "w500",
+        (ImageKind::Poster, ImageSize::Hero) => "w780",
+        (ImageKind::Backdrop, ImageSize::Thumbnail) => "w300",
+        (ImageKind::Backdrop, ImageSize::Details) => "w780",
+        (ImageKind::Backdrop, ImageSize::Hero) => "w1280",
+    }
+}
+
+/// TMDB sizes offered in a hero `srcset`, smallest first
+fn tmdb_srcset_sizes(kind: ImageKind) -> [(&'static str, u32); 3] {
+    match kind {
+        ImageKind::Poster => [("w500", 500), ("w780", 780), ("original", 0)],
+        ImageKind::Backdrop => [("w780", 780), ("w1280", 1280), ("original", 0)],
+    }
+}
+
+/// RAWG resize width for a view
+fn rawg_width(size: ImageSize) -> u32 {
+    match size {
+        ImageSize::Thumbnail => 200,
+        ImageSize::Details => 640,
+        ImageSize::Hero => 1280,
+    }
+}
+
+/// RAWG widths offered in a hero `srcset`
+const RAWG_SRCSET_WIDTHS: [u32; 2] = [640, 1280];
+
+/// An image URL split around the part that selects its size
+enum SizedUrl<'a> {
+    /// TMDB: `{base}/{size}/{file}`
+    Tmdb { base: &'a str, file: &'a str },
+    /// RAWG: `{host}/media/{path}` (without any resize prefix)
+    Rawg { host: &'a str, path: &'a str },
+}
+
+/// Check if a path segment is a TMDB size ("w500", "h632", "original")
+fn is_tmdb_size(segment: &str) -> bool {
+    segment == "original"
+        || (segment.len() > 1
+            && segment.starts_with(['w', 'h'])
+            && segment[1..].chars().all(|c| c.is_ascii_digit()))
+}
+
+fn parse_sized_url(url: &str) -> Option<SizedUrl<'_>> {
+    if let Some(index) = url.find("media.rawg.io/media/") {
+        let split = index + "media.rawg.io/media/".len();
+        let (host, path) = (&url[..split - 1], &url[split..]);
+        // Drop an earlier resize or crop ("resize/640/-/", "crop/600/400/")
+        let path = if let Some(rest) = path.strip_prefix("resize/") {
+            rest.splitn(3, '/').nth(2)?
+        } else if let Some(rest) = path.strip_prefix("crop/") {
+            rest.splitn(3, '/').nth(2)?
+        } else {
+            path
+        };
+        return Some(SizedUrl::Rawg { host, path });
+    }
+
+    let (rest, file) = url.rsplit_once('/')?;
+    let (base, size) = rest.rsplit_once('/')?;
+    (is_tmdb_size(size) && !file.is_empty() && base.contains("://"))
+        .then_some(SizedUrl::Tmdb { base, file })
+}
+
+/// URL of an image at the size a view needs
+pub fn sized_image_url(url: &str, kind: ImageKind, size: ImageSize) -> String {
+    match parse_sized_url(url) {
+        Some(SizedUrl::Tmdb { base, file }) => {
+            format!("{}/{}/{}", base, tmdb_size(kind, size), file)
+        }
+        Some(SizedUrl::Rawg { host, path }) => {
+            format!("{}/resize/{}/-/{}", host, rawg_width(size), path)
+        }
+        None => url.to_string(),
+    }
+}
+
+/// Responsive `srcset` for a full-screen image, if the host offers several sizes
+pub fn hero_srcset(url: &str, kind: ImageKind) -> Option<String> {
+    let candidates: Vec<String> = match parse_sized_url(url)? {
+        SizedUrl::Tmdb { base, file } => tmdb_srcset_sizes(kind)
+            .into_iter()
+            .map(|(size, width)| {
+                let width = if width == 0 {
+                    tmdb_original_width(kind)
+                } else {
+                    width
+                };
+                format!("{}/{}/{} {}w", base, size, file, width)
+            })
+            .collect(),
+        SizedUrl::Rawg { host, path } => RAWG_SRCSET_WIDTHS
+            .into_iter()
+            .map(|width| format!("{}/resize/{}/-/{} {}w", host, width, path, width))
+            .collect(),
+    };
+    Some(candidates.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sized_urls_per_host() {
+        let poster = "https://image.tmdb.org/t/p/w500/abc123.jpg";
+        assert_eq!(
+            sized_image_url(poster, ImageKind::Poster, ImageSize::Thumbnail),
+            "https://image.tmdb.org/t/p/w185/abc123.jpg"
+        );
+        assert_eq!(
+            sized_image_url(
+                "http://localhost:8080/images/original/bd.jpg",
+                ImageKind::Backdrop,
+                ImageSize::Hero
+            ),
+            "http://localhost:8080/images/w1280/bd.jpg"
+        );
+
+        let rawg = "https://media.rawg.io/media/games/618/618c2031a07bbff6b4f611f10b6bcdbc.jpg";
+        assert_eq!(
+            sized_image_url(rawg, ImageKind::Backdrop, ImageSize::Thumbnail),
+            "https://media.rawg.io/media/resize/200/-/games/618/618c2031a07bbff6b4f611f10b6bcdbc.jpg"
+        );
+        let resized = sized_image_url(rawg, ImageKind::Backdrop, ImageSize::Details);
+        assert_eq!(
+            sized_image_url(&resized, ImageKind::Backdrop, ImageSize::Hero),
+            "https://media.rawg.io/media/resize/1280/-/games/618/618c2031a07bbff6b4f611f10b6bcdbc.jpg"
+        );
+
+        // Unknown hosts and local assets are left alone
+        let custom = "https://example.com/covers/w500.jpg";
+        assert_eq!(
+            sized_image_url(custom, ImageKind::Poster, ImageSize::Hero),
+            custom
+        );
+        assert_eq!(
+            sized_image_url(
+                "data:image/jpeg;base64,AAAA",
+                ImageKind::Poster,
+                ImageSize::Hero
+            ),
+            "data:image/jpeg;base64,AAAA"
+        );
+    }
+
+    #[test]
+    fn test_hero_srcset() {
+        assert_eq!(
+            hero_srcset(
+                "https://image.tmdb.org/t/p/w1280/bd.jpg",
+                ImageKind::Backdrop
+            )
+            .as_deref(),
+            Some(
+                "https://image.tmdb.org/t/p/w780/bd.jpg 780w, \
+                 https://image.tmdb.org/t/p/w1280/bd.jpg 1280w, \
+                 https://image.tmdb.org/t/p/original/bd.jpg 3840w"
+            )
+        );
+        assert_eq!(
+            hero_srcset("https://example.com/cover.jpg", ImageKind::Poster),
+            None
+        );
+    }
+}
@@ -3,13 +3,21 @@
 //! This module contains clients for various media databases:
 //! - TMDB (The Movie Database) - Movies and TV shows
 //! - RAWG (Video Games Database) - Video games
+//!
+//! Both clients send their requests through the shared, rate-limit aware `http` layer,
+//! and image URLs from either are resized per view through `images`.
 
 #![allow(dead_code)]
 
+pub mod http;
+pub mod images;
 pub mod rawg;
+#[cfg(test)]
+pub mod test_server;
 pub mod tmdb;
 
 use crate::models::{SearchResult, WorkType};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::future::Future;
 use std::pin::Pin;
 
@@ -24,8 +32,10 @@ pub enum ProviderError {
     ParseError(String),
     /// API token is missing or invalid
     AuthError(String),
-    /// Provider is not available (offline, rate limited, etc.)
+    /// Provider is not available (offline, server errors, etc.)
     Unavailable(String),
+    /// Provider is rate limiting requests; retry after the given delay
+    RateLimited { retry_after_secs: u64 },
 }
 
 impl std::fmt::Display for ProviderError {
@@ -38,6 +48,9 @@ impl std::fmt::Display for ProviderError {
             ProviderError::ParseError(msg) => write!(f, "Parse error: {}", msg),
             ProviderError::AuthError(msg) => write!(f, "Auth error: {}", msg),
             ProviderError::Unavailable(msg) => write!(f, "Provider unavailable: {}", msg),
+            ProviderError::RateLimited { retry_after_secs } => {
+                write!(f, "Rate limited (retry after {}s)", retry_after_secs)
+            }
         }
     }
 }
@@ -45,6 +58,92 @@ impl std::fmt::Display for ProviderError {
 /// Result type for provider operations
 pub type ProviderResult<T> = Result<T, ProviderError>;
 
+/// Outcome of testing a provider credential with a lightweight authenticated request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ConnectionCheck {
+    /// The provider accepted the credential
+    Valid,
+    /// The provider rejected the credential
+    Invalid(String),
+    /// The provider could not be reached
+    NetworkError(String),
+    /// The provider is rate limiting requests
+    RateLimited(String),
+}
+
+impl ConnectionCheck {
+    /// Classify the result of an authenticated test request
+    pub fn from_result(result: ProviderResult<()>) -> Self {
+        match result {
+            Ok(()) => ConnectionCheck::Valid,
+            Err(ProviderError::AuthError(message)) => ConnectionCheck::Invalid(message),
+            Err(ProviderError::ApiError {
+                status: 401 | 403,
+                message,
+            }) => ConnectionCheck::Invalid(error_message(&message)),
+            // Any other error says nothing about the credential
+            Err(ProviderError::ApiError { status, message }) => ConnectionCheck::NetworkError(
+                format!("API error ({}): {}", status, error_message(&message)),
+            ),
+            Err(ProviderError::RateLimited { retry_after_secs }) => {
+                ConnectionCheck::RateLimited(format!("Try again in {}s", retry_after_secs))
+            }
+            Err(ProviderError::NetworkError(message))
+            | Err(ProviderError::Unavailable(message))
+            | Err(ProviderError::ParseError(message)) => ConnectionCheck::NetworkError(message),
+        }
+    }
+
+    /// Check if the credential was accepted
+    pub fn is_valid(&self) -> bool {
+        matches!(self, ConnectionCheck::Valid)
+    }
+
+    /// Short label for display
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConnectionCheck::Valid => "Valid",
+            ConnectionCheck::Invalid(_) => "Invalid",
+            ConnectionCheck::NetworkError(_) => "Network error",
+            ConnectionCheck::RateLimited(_) => "Rate limited",
+        }
+    }
+
+    /// Detail message, if any
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            ConnectionCheck::Valid => None,
+            ConnectionCheck::Invalid(message)
+            | ConnectionCheck::NetworkError(message)
+            | ConnectionCheck::RateLimited(message) => Some(message),
+        }
+    }
+}
+
+/// Extract a readable message from a provider error body
+///
+/// TMDB answers with `status_message`, RAWG with `error` or `detail`; anything
+/// else is returned as-is.
+pub fn error_message(body: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|json| {
+            ["status_message", "error", "detail"]
+                .iter()
+                .find_map(|field| json.get(field)?.as_str().map(str::to_string))
+        })
+        .unwrap_or_else(|| body.trim().to_string())
+}
+
+/// Deserialize a JSON `null` as the type's default (providers send `null` for empty lists)
+pub(crate) fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
 /// Status of a provider (for UI feedback)
 #[derive(Debug, Clone, PartialEq)]
 pub enum ProviderStatus {
@@ -76,3 +175,56 @@ pub trait SearchProvider {
     /// Get the provider name for display
     fn name(&self) -> &'static str;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_message_extracts_provider_fields() {
+        assert_eq!(
+            error_message(r#"{"status_code":7,"status_message":"Invalid API key."}"#),
+            "Invalid API key."
+        );
+        assert_eq!(
+            error_message(r#"{"error":"The key parameter is not provided"}"#),
+            "The key parameter is not provided"
+        );
+        assert_eq!(error_message(" Unauthorized \n"), "Unauthorized");
+    }
+
+    #[test]
+    fn test_connection_check_classifies_errors() {
+        assert!(ConnectionCheck::from_result(Ok(())).is_valid());
+        assert!(matches!(
+            ConnectionCheck::from_result(Err(ProviderError::ApiError {
+                status: 401,
+                message: r#"{"status_message":"Invalid API key."}"#.to_string(),
+            })),
+            ConnectionCheck::Invalid(message) if message == "Invalid API key."
+        ));
+        assert!(matches!(
+            ConnectionCheck::from_result(Err(ProviderError::ApiError {
+                status: 502,
+                message: "Bad Gateway".to_string(),
+            })),
+            ConnectionCheck::NetworkError(_)
+        ));
+        assert!(matches!(
+            ConnectionCheck::from_result(Err(ProviderError::NetworkError(
+                "error sending request".to_string()
+            ))),
+            ConnectionCheck::NetworkError(_)
+        ));
+        assert!(matches!(
+            ConnectionCheck::from_result(Err(ProviderError::RateLimited {
+                retry_after_secs: 10
+            })),
+            ConnectionCheck::RateLimited(_)
+        ));
+        assert!(matches!(
+            ConnectionCheck::from_result(Err(ProviderError::Unavailable("down".to_string()))),
+            ConnectionCheck::NetworkError(_)
+        ));
+    }
+}
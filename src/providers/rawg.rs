@@ -1,25 +1,65 @@
 //! RAWG (Video Games Database) API client
 //!
-//! Provides search functionality for video games.
+//! Provides search and details lookup for video games.
 //! RAWG supports CORS and works from browser contexts.
 //! API Documentation: https://rawg.io/apidocs
 
 #![allow(dead_code)]
 
-use crate::models::{ProviderRef, SearchResult, WorkType};
-use crate::providers::{ProviderError, ProviderResult, ProviderStatus};
+use crate::constants::MAX_ALTERNATE_TITLES;
+use crate::models::{
+    ProviderImage, ProviderRef, SearchResult, SearchResultsPage, WorkDetails, WorkMetadata,
+    WorkType,
+};
+use crate::providers::http::ProviderHttp;
+use crate::providers::{
+    null_as_default, ConnectionCheck, ProviderError, ProviderResult, ProviderStatus,
+};
 use crate::settings;
 use serde::Deserialize;
 use tracing::info;
 
-/// RAWG API base URL
-const RAWG_API_BASE: &str = "https://api.rawg.io/api";
+/// Default RAWG API base URL
+pub const DEFAULT_RAWG_API_BASE: &str = "https://api.rawg.io/api";
+
+/// Number of results requested per RAWG page
+const RAWG_PAGE_SIZE: usize = 20;
+
+/// Credential and endpoint used by the RAWG client
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawgConfig {
+    /// API key
+    pub api_key: Option<String>,
+    /// API base URL (no trailing slash)
+    pub api_base: String,
+}
+
+impl RawgConfig {
+    /// Read the API key and endpoint from Settings
+    pub fn from_settings() -> Self {
+        Self {
+            api_key: settings::get_rawg_api_key(),
+            api_base: settings::rawg_api_base(),
+        }
+    }
+}
+
+impl Default for RawgConfig {
+    fn default() -> Self {
+        Self {
+            api_key: None,
+            api_base: DEFAULT_RAWG_API_BASE.to_string(),
+        }
+    }
+}
 
 /// RAWG API client
 #[derive(Clone)]
 pub struct RawgClient {
-    /// HTTP client
-    client: reqwest::Client,
+    /// Rate-limit aware HTTP client
+    http: ProviderHttp,
+    /// Fixed configuration; when `None` it is read from Settings on every request
+    config: Option<RawgConfig>,
 }
 
 // =============================================================================
@@ -33,6 +73,19 @@ struct RawgSearchResponse {
     results: Vec<RawgGame>,
 }
 
+impl RawgSearchResponse {
+    /// Convert to a zero-indexed results page
+    ///
+    /// RAWG only reports the total count, so the page total is derived from it.
+    fn into_page(self, page: usize) -> SearchResultsPage {
+        let total_count = self.count as usize;
+        let total_pages = total_count.div_ceil(RAWG_PAGE_SIZE);
+        let results = self.results.iter().map(|g| g.to_search_result()).collect();
+
+        SearchResultsPage::new(results, total_count, page, total_pages)
+    }
+}
+
 /// Individual game result from RAWG
 #[derive(Debug, Deserialize)]
 struct RawgGame {
@@ -57,44 +110,196 @@ impl RawgGame {
         SearchResult {
             provider_ref: ProviderRef::new("rawg", self.id.to_string()),
             title: self.name.clone(),
+            original_title: None,
             year,
             work_type: WorkType::Game,
             cover_url: self.background_image.clone(),
+            backdrop_url: self.background_image.clone(),
             theme_music_url: None,
         }
     }
 }
 
+/// Named entry in RAWG detail lists (genres, developers, publishers)
+#[derive(Debug, Deserialize)]
+struct RawgNamed {
+    name: String,
+}
+
+/// Platform entry in RAWG game details
+#[derive(Debug, Deserialize)]
+struct RawgPlatformEntry {
+    platform: RawgNamed,
+}
+
+/// Game details response
+#[derive(Debug, Deserialize)]
+struct RawgGameDetails {
+    #[serde(flatten)]
+    game: RawgGame,
+    #[serde(default)]
+    description_raw: Option<String>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    genres: Vec<RawgNamed>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    developers: Vec<RawgNamed>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    publishers: Vec<RawgNamed>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    platforms: Vec<RawgPlatformEntry>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    alternative_names: Vec<String>,
+}
+
+/// Screenshot entry of a game
+#[derive(Debug, Deserialize)]
+struct RawgScreenshot {
+    image: String,
+    #[serde(default)]
+    width: u32,
+    #[serde(default)]
+    height: u32,
+}
+
+/// Screenshots response
+#[derive(Debug, Deserialize)]
+struct RawgScreenshotsResponse {
+    #[serde(default, deserialize_with = "null_as_default")]
+    results: Vec<RawgScreenshot>,
+}
+
+impl RawgGameDetails {
+    /// Convert to our WorkDetails type
+    fn into_details(self) -> WorkDetails {
+        let names = |list: Vec<RawgNamed>| list.into_iter().map(|n| n.name).collect::<Vec<_>>();
+
+        let mut details: WorkDetails = self.game.to_search_result().into();
+        details.alternate_titles = self
+            .alternative_names
+            .into_iter()
+            .take(MAX_ALTERNATE_TITLES)
+            .collect();
+        details.metadata = WorkMetadata {
+            synopsis: self.description_raw.filter(|d| !d.trim().is_empty()),
+            genres: names(self.genres),
+            creators: names(self.developers),
+            studios: names(self.publishers),
+            platforms: self
+                .platforms
+                .into_iter()
+                .map(|p| p.platform.name)
+                .collect(),
+            ..Default::default()
+        };
+        details
+    }
+}
+
+/// Map a RAWG transport failure to a provider error
+///
+/// Without a response there is nothing certain to say about the API key: being
+/// offline and a response the browser blocked all look the same here, so they are
+/// reported as network errors. RAWG's 401 for an invalid key carries no CORS
+/// headers, though, so the browser blocks it and it fails this way too; the message
+/// points at the key as well.
+fn map_send_error(e: reqwest::Error) -> ProviderError {
+    info!("RAWG network error during request: {}", e);
+    if e.is_timeout() {
+        ProviderError::NetworkError("RAWG did not respond in time".to_string())
+    } else if e.is_request() {
+        ProviderError::NetworkError(format!(
+            "Could not reach RAWG. If you are online, check your API key: the browser \
+             hides RAWG's rejection of an invalid key ({})",
+            e
+        ))
+    } else {
+        ProviderError::NetworkError(e.to_string())
+    }
+}
+
 // =============================================================================
 // Client Implementation
 // =============================================================================
 
 impl RawgClient {
-    /// Create a new RAWG client
+    /// Create a new RAWG client configured from Settings
     pub fn new() -> Self {
         info!("RAWG client initialized");
         Self {
-            client: reqwest::Client::new(),
+            http: ProviderHttp::new("rawg").with_send_error(map_send_error),
+            config: None,
         }
     }
 
-    /// Check if the client has a valid API key configured (checks localStorage)
+    /// Create a client with a fixed configuration (e.g. a local stub server)
+    pub fn with_config(config: RawgConfig) -> Self {
+        info!("RAWG client initialized for {}", config.api_base);
+        Self {
+            http: ProviderHttp::new("rawg").with_send_error(map_send_error),
+            config: Some(config),
+        }
+    }
+
+    /// Current configuration
+    fn config(&self) -> RawgConfig {
+        self.config
+            .clone()
+            .unwrap_or_else(RawgConfig::from_settings)
+    }
+
+    /// Check if the client has an API key configured
     pub fn is_configured(&self) -> bool {
-        settings::is_rawg_configured()
+        self.config().api_key.is_some()
     }
 
-    /// Get the provider status (checks localStorage)
+    /// Get the provider status (checks the API key and rate-limit cooldown)
     pub fn status(&self) -> ProviderStatus {
-        if settings::is_rawg_configured() {
-            ProviderStatus::Available
-        } else {
+        if !self.is_configured() {
             ProviderStatus::NotConfigured
+        } else if self.http.is_cooling_down() {
+            ProviderStatus::RateLimited
+        } else {
+            ProviderStatus::Available
         }
     }
 
+    /// Test an API key with a lightweight authenticated request
+    ///
+    /// Uses the given key rather than the stored one so it can be checked before saving.
+    pub async fn check_connection(&self, api_key: &str) -> ConnectionCheck {
+        let api_key = api_key.trim();
+        if api_key.is_empty() {
+            return ConnectionCheck::Invalid("No API key entered".to_string());
+        }
+
+        let url = format!(
+            "{}/genres?key={}&page_size=1",
+            self.config().api_base,
+            api_key
+        );
+        info!("RAWG connection check");
+
+        let result = self.http.get(&url, &[("Accept", "application/json")]).await;
+
+        ConnectionCheck::from_result(match result {
+            Ok(response) if response.status().is_success() => Ok(()),
+            Ok(response) => Err(ProviderError::ApiError {
+                status: response.status().as_u16(),
+                message: response.text().await.unwrap_or_default(),
+            }),
+            Err(e) => Err(e),
+        })
+    }
+
     /// Search for games
-    pub async fn search(&self, query: &str, page: usize) -> ProviderResult<Vec<SearchResult>> {
-        let api_key = settings::get_rawg_api_key().ok_or_else(|| {
+    pub async fn search(
+        &self,
+        query: &str,
+        year: Option<u16>,
+        page: usize,
+    ) -> ProviderResult<SearchResultsPage> {
+        let config = self.config();
+        let api_key = config.api_key.ok_or_else(|| {
             ProviderError::AuthError(
                 "RAWG API key not configured. Add your key in Settings.".to_string(),
             )
@@ -102,53 +307,30 @@ impl RawgClient {
 
         // Don't search with empty query
         if query.trim().is_empty() {
-            return Ok(Vec::new());
+            return Ok(SearchResultsPage::empty(page));
         }
 
         // RAWG uses 1-indexed pages
         let rawg_page = page + 1;
-        let page_size = 20;
 
-        let url = format!(
+        let mut url = format!(
             "{}/games?key={}&search={}&page={}&page_size={}",
-            RAWG_API_BASE,
+            config.api_base,
             &api_key,
             urlencoding::encode(query),
             rawg_page,
-            page_size
+            RAWG_PAGE_SIZE
         );
+        if let Some(year) = year {
+            url.push_str(&format!("&dates={year}-01-01,{year}-12-31"));
+        }
 
         info!("RAWG search: {} (page {})", query, rawg_page);
 
         let response = self
-            .client
-            .get(&url)
-            .header("Accept", "application/json")
-            .send()
-            .await
-            .map_err(|e| {
-                let err_msg = e.to_string();
-                info!("RAWG network error during request: {}", err_msg);
-
-                // In browser/WASM, when RAWG returns 401 for invalid API key,
-                // the browser's CORS policy blocks the response and reqwest
-                // reports it as "error sending request" before we can check status.
-                // We need to treat this as an auth error, not a network error.
-                if err_msg.contains("error sending request") {
-                    info!("Detected 'error sending request' - likely CORS-blocked 401 from RAWG");
-                    ProviderError::ApiError {
-                        status: 401,
-                        message: "Invalid or missing API key".to_string(),
-                    }
-                } else if err_msg.contains("401") || err_msg.contains("Unauthorized") {
-                    ProviderError::ApiError {
-                        status: 401,
-                        message: "Invalid API key".to_string(),
-                    }
-                } else {
-                    ProviderError::NetworkError(err_msg)
-                }
-            })?;
+            .http
+            .get(&url, &[("Accept", "application/json")])
+            .await?;
 
         let status = response.status();
         if !status.is_success() {
@@ -170,13 +352,95 @@ impl RawgClient {
             search_response.count
         );
 
-        let results = search_response
+        Ok(search_response.into_page(page))
+    }
+
+    /// Fetch current metadata for a game
+    pub async fn details(&self, id: &str) -> ProviderResult<WorkDetails> {
+        let config = self.config();
+        let api_key = config.api_key.ok_or_else(|| {
+            ProviderError::AuthError(
+                "RAWG API key not configured. Add your key in Settings.".to_string(),
+            )
+        })?;
+
+        let url = format!(
+            "{}/games/{}?key={}",
+            config.api_base,
+            urlencoding::encode(id),
+            &api_key
+        );
+
+        info!("RAWG details: {}", id);
+
+        let response = self
+            .http
+            .get(&url, &[("Accept", "application/json")])
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(ProviderError::ApiError {
+                status: status.as_u16(),
+                message: error_text,
+            });
+        }
+
+        let game: RawgGameDetails = response
+            .json()
+            .await
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+
+        Ok(game.into_details())
+    }
+
+    /// Fetch the screenshots of a game
+    pub async fn screenshots(&self, id: &str) -> ProviderResult<Vec<ProviderImage>> {
+        let config = self.config();
+        let api_key = config.api_key.ok_or_else(|| {
+            ProviderError::AuthError(
+                "RAWG API key not configured. Add your key in Settings.".to_string(),
+            )
+        })?;
+
+        let url = format!(
+            "{}/games/{}/screenshots?key={}",
+            config.api_base,
+            urlencoding::encode(id),
+            &api_key
+        );
+
+        info!("RAWG screenshots: {}", id);
+
+        let response = self
+            .http
+            .get(&url, &[("Accept", "application/json")])
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(ProviderError::ApiError {
+                status: status.as_u16(),
+                message: error_text,
+            });
+        }
+
+        let screenshots: RawgScreenshotsResponse = response
+            .json()
+            .await
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+
+        Ok(screenshots
             .results
             .into_iter()
-            .map(|g| g.to_search_result())
-            .collect();
-
-        Ok(results)
+            .map(|s| ProviderImage {
+                url: s.image,
+                width: s.width,
+                height: s.height,
+            })
+            .collect())
     }
 }
 
@@ -189,6 +453,7 @@ impl Default for RawgClient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::providers::test_server::{MockResponse, MockServer};
 
     #[test]
     fn test_year_extraction() {
@@ -221,4 +486,126 @@ mod tests {
         assert_eq!(search_result.year, None);
         assert_eq!(search_result.cover_url, None);
     }
+
+    #[test]
+    fn test_total_pages_from_count() {
+        let response = RawgSearchResponse {
+            count: 41,
+            results: Vec::new(),
+        };
+
+        let page = response.into_page(1);
+        assert_eq!(page.total_pages, 3);
+        assert_eq!(page.page, 1);
+        assert!(page.has_next_page());
+    }
+
+    #[tokio::test]
+    async fn test_search_against_stub_server() {
+        let server = MockServer::start(vec![MockResponse::new(
+            200,
+            r#"{"count":1,"results":[
+                {"id":3328,"name":"The Witcher 3: Wild Hunt","released":"2015-05-18","background_image":null}
+            ]}"#,
+        )]);
+        let client = RawgClient::with_config(RawgConfig {
+            api_key: Some("test-key".to_string()),
+            api_base: server.url("/api"),
+        });
+
+        let page = client.search("witcher 3", None, 0).await.unwrap();
+        assert_eq!(page.results.len(), 1);
+        assert_eq!(page.results[0].year, Some(2015));
+        assert!(!page.has_next_page());
+
+        let request = &server.requests()[0];
+        assert!(request.starts_with("GET /api/games?key=test-key&search=witcher%203&page=1"));
+    }
+
+    #[tokio::test]
+    async fn test_connection_check_against_stub_server() {
+        let server = MockServer::start(vec![MockResponse::new(
+            401,
+            r#"{"error":"The key parameter is not provided"}"#,
+        )]);
+        let client = RawgClient::with_config(RawgConfig {
+            api_key: None,
+            api_base: server.url("/api"),
+        });
+
+        assert_eq!(
+            client.check_connection("wrong").await,
+            ConnectionCheck::Invalid("The key parameter is not provided".to_string())
+        );
+        assert!(server.requests()[0].starts_with("GET /api/genres?key=wrong"));
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_rawg_hints_at_the_api_key() {
+        // Nothing listens on the port, like a response the browser blocked
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let client = RawgClient::with_config(RawgConfig {
+            api_key: Some("wrong".to_string()),
+            api_base: format!("http://127.0.0.1:{}/api", port),
+        });
+
+        match client.search("witcher", None, 0).await {
+            Err(ProviderError::NetworkError(message)) => assert!(message.contains("API key")),
+            other => panic!("expected a network error, got {:?}", other),
+        }
+        assert!(matches!(
+            client.check_connection("wrong").await,
+            ConnectionCheck::NetworkError(message) if message.contains("API key")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_details_against_stub_server() {
+        let server = MockServer::start(vec![MockResponse::new(
+            200,
+            r#"{"id":3328,"name":"The Witcher 3: Wild Hunt","released":"2015-05-18","background_image":null,
+                "description_raw":"Geralt hunts monsters.","genres":[{"name":"RPG"}],
+                "developers":[{"name":"CD PROJEKT RED"}],"publishers":null,
+                "platforms":[{"platform":{"name":"PC"}},{"platform":{"name":"PlayStation 4"}}],
+                "alternative_names":["Wiedźmin 3: Dziki Gon"]}"#,
+        )]);
+        let client = RawgClient::with_config(RawgConfig {
+            api_key: Some("test-key".to_string()),
+            api_base: server.url("/api"),
+        });
+
+        let details = client.details("3328").await.unwrap();
+        assert_eq!(details.title, "The Witcher 3: Wild Hunt");
+        assert_eq!(details.alternate_titles, vec!["Wiedźmin 3: Dziki Gon"]);
+        assert_eq!(
+            details.metadata.synopsis.as_deref(),
+            Some("Geralt hunts monsters.")
+        );
+        assert_eq!(details.metadata.creators, vec!["CD PROJEKT RED"]);
+        assert!(details.metadata.studios.is_empty());
+        assert!(details.metadata.has_platform("playstation 4"));
+        assert!(server.requests()[0].starts_with("GET /api/games/3328?key=test-key"));
+    }
+
+    #[tokio::test]
+    async fn test_screenshots_against_stub_server() {
+        let server = MockServer::start(vec![MockResponse::new(
+            200,
+            r#"{"count":1,"results":[{"id":1,"image":"https://media.rawg.io/media/screenshots/a.jpg",
+                "width":1920,"height":1080}]}"#,
+        )]);
+        let client = RawgClient::with_config(RawgConfig {
+            api_key: Some("test-key".to_string()),
+            api_base: server.url("/api"),
+        });
+
+        let screenshots = client.screenshots("3328").await.unwrap();
+        assert_eq!(screenshots.len(), 1);
+        assert!(screenshots[0].is_landscape());
+        assert!(server.requests()[0].starts_with("GET /api/games/3328/screenshots?key=test-key"));
+    }
 }
@@ -0,0 +1,122 @@
+//! Minimal local HTTP server for exercising provider clients in tests
+//!
+//! Serves a fixed sequence of canned responses (repeating the last one) and
+//! records the request line of every request it receives.
+
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// A canned HTTP response
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl MockResponse {
+    /// Create a JSON response with the given status code
+    pub fn new(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.to_string(),
+        }
+    }
+
+    /// Add a response header
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    fn to_http(&self) -> String {
+        let mut head = format!(
+            "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+            self.status,
+            self.body.len()
+        );
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        format!("{}\r\n{}", head, self.body)
+    }
+}
+
+/// Local HTTP server bound to an ephemeral port
+pub struct MockServer {
+    base_url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockServer {
+    /// Start serving the given responses in order on a background thread
+    pub fn start(responses: Vec<MockResponse>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = requests.clone();
+        let mut queue: VecDeque<MockResponse> = responses.into();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+
+                // Read the request line and headers (provider requests have no body)
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                if reader.read_line(&mut request_line).is_err() {
+                    continue;
+                }
+                let mut header = String::new();
+                while reader
+                    .read_line(&mut header)
+                    .map(|n| n > 2)
+                    .unwrap_or(false)
+                {
+                    header.clear();
+                }
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push(request_line.trim().to_string());
+
+                let response = if queue.len() > 1 {
+                    queue.pop_front().unwrap()
+                } else {
+                    queue
+                        .front()
+                        .cloned()
+                        .unwrap_or_else(|| MockResponse::new(404, "{}"))
+                };
+                let _ = stream.write_all(response.to_http().as_bytes());
+                let _ = stream.flush();
+            }
+        });
+
+        Self { base_url, requests }
+    }
+
+    /// Base URL of the server (no trailing slash)
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Absolute URL for a path on the server
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Request lines received so far (e.g. `GET /search?query=x HTTP/1.1`)
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Number of requests received so far
+    pub fn request_count(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+}
@@ -1,27 +1,89 @@
 //! TMDB (The Movie Database) API client
 //!
-//! Provides search functionality for Movies and TV shows.
+//! Provides search and details lookup for Movies and TV shows.
 //! API Documentation: https://developer.themoviedb.org/reference/search-movie
 
 #![allow(dead_code)]
 
-use crate::models::{ProviderRef, SearchResult, WorkType};
-use crate::providers::{ProviderError, ProviderResult, ProviderStatus};
+use crate::constants::{DEFAULT_DISPLAY_LANGUAGE, MAX_ALTERNATE_TITLES};
+use crate::models::{
+    ProviderImage, ProviderRef, SearchResult, SearchResultsPage, WorkDetails, WorkMetadata,
+    WorkType,
+};
+use crate::providers::http::ProviderHttp;
+use crate::providers::{
+    null_as_default, ConnectionCheck, ProviderError, ProviderResult, ProviderStatus,
+};
 use crate::settings;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use tracing::info;
 
-/// TMDB API base URL
-const TMDB_API_BASE: &str = "https://api.themoviedb.org/3";
+/// Default TMDB API base URL
+pub const DEFAULT_TMDB_API_BASE: &str = "https://api.themoviedb.org/3";
+
+/// Default TMDB image base URL (image size is appended per use)
+pub const DEFAULT_TMDB_IMAGE_BASE: &str = "https://image.tmdb.org/t/p";
+
+/// TMDB image size stored for covers (views pick their own size, see `images`)
+const TMDB_COVER_SIZE: &str = "w500";
+
+/// TMDB image size stored for backdrops
+const TMDB_BACKDROP_SIZE: &str = "w1280";
+
+/// Credential and endpoints used by the TMDB client
+#[derive(Debug, Clone, PartialEq)]
+pub struct TmdbConfig {
+    /// API read access token
+    pub token: Option<String>,
+    /// API base URL (no trailing slash)
+    pub api_base: String,
+    /// Image base URL without the size segment (no trailing slash)
+    pub image_base: String,
+    /// Language for titles and overviews (e.g. "en-US", "ja-JP")
+    pub language: String,
+}
 
-/// TMDB image base URL (w500 size for covers)
-const TMDB_IMAGE_BASE: &str = "https://image.tmdb.org/t/p/w500";
+impl TmdbConfig {
+    /// Read the token and endpoints from Settings
+    pub fn from_settings() -> Self {
+        Self {
+            token: settings::get_tmdb_token(),
+            api_base: settings::tmdb_api_base(),
+            image_base: settings::tmdb_image_base(),
+            language: settings::display_language(),
+        }
+    }
+
+    /// Cover image URL for a poster path
+    fn cover_url(&self, path: &str) -> String {
+        format!("{}/{}{}", self.image_base, TMDB_COVER_SIZE, path)
+    }
+
+    /// Backdrop image URL for a backdrop path
+    fn backdrop_url(&self, path: &str) -> String {
+        format!("{}/{}{}", self.image_base, TMDB_BACKDROP_SIZE, path)
+    }
+}
+
+impl Default for TmdbConfig {
+    fn default() -> Self {
+        Self {
+            token: None,
+            api_base: DEFAULT_TMDB_API_BASE.to_string(),
+            image_base: DEFAULT_TMDB_IMAGE_BASE.to_string(),
+            language: DEFAULT_DISPLAY_LANGUAGE.to_string(),
+        }
+    }
+}
 
 /// TMDB API client
 #[derive(Clone)]
 pub struct TmdbClient {
-    /// HTTP client
-    client: reqwest::Client,
+    /// Rate-limit aware HTTP client
+    http: ProviderHttp,
+    /// Fixed configuration; when `None` it is read from Settings on every request
+    config: Option<TmdbConfig>,
 }
 
 // =============================================================================
@@ -37,6 +99,24 @@ struct TmdbSearchResponse {
     total_results: u32,
 }
 
+impl TmdbSearchResponse {
+    /// Convert to a zero-indexed results page
+    fn into_page(self, work_type: WorkType, config: &TmdbConfig) -> SearchResultsPage {
+        let results = self
+            .results
+            .iter()
+            .map(|r| r.to_search_result(work_type.clone(), config))
+            .collect();
+
+        SearchResultsPage::new(
+            results,
+            self.total_results as usize,
+            self.page.saturating_sub(1) as usize,
+            self.total_pages as usize,
+        )
+    }
+}
+
 /// Individual search result from TMDB
 #[derive(Debug, Deserialize)]
 struct TmdbSearchResult {
@@ -45,29 +125,37 @@ struct TmdbSearchResult {
     #[serde(default)]
     title: Option<String>,
     #[serde(default)]
+    original_title: Option<String>,
+    #[serde(default)]
     release_date: Option<String>,
     // TV fields
     #[serde(default)]
     name: Option<String>,
     #[serde(default)]
+    original_name: Option<String>,
+    #[serde(default)]
     first_air_date: Option<String>,
     // Common fields
     #[serde(default)]
     poster_path: Option<String>,
     #[serde(default)]
+    backdrop_path: Option<String>,
+    #[serde(default)]
     overview: Option<String>,
 }
 
 impl TmdbSearchResult {
     /// Convert to our SearchResult type
-    fn to_search_result(&self, work_type: WorkType) -> SearchResult {
-        let (title, date) = match work_type {
+    fn to_search_result(&self, work_type: WorkType, config: &TmdbConfig) -> SearchResult {
+        let (title, original_title, date) = match work_type {
             WorkType::Movie => (
                 self.title.clone().unwrap_or_default(),
+                self.original_title.clone(),
                 self.release_date.clone(),
             ),
             WorkType::TvAnime => (
                 self.name.clone().unwrap_or_default(),
+                self.original_name.clone(),
                 self.first_air_date.clone(),
             ),
             WorkType::Game => unreachable!("TMDB does not support games"),
@@ -80,94 +168,452 @@ impl TmdbSearchResult {
             .and_then(|y| y.parse::<u16>().ok());
 
         // Build cover URL if poster_path exists
-        let cover_url = self
-            .poster_path
+        let cover_url = self.poster_path.as_ref().map(|path| config.cover_url(path));
+        let backdrop_url = self
+            .backdrop_path
             .as_ref()
-            .map(|path| format!("{}{}", TMDB_IMAGE_BASE, path));
+            .map(|path| config.backdrop_url(path));
+
+        // Only keep the original title when it adds something
+        let original_title = original_title.filter(|o| !o.trim().is_empty() && *o != title);
 
         SearchResult {
             provider_ref: ProviderRef::new("tmdb", self.id.to_string()),
             title,
+            original_title,
             year,
             work_type,
             cover_url,
+            backdrop_url,
             theme_music_url: None, // TMDB doesn't provide music
         }
     }
 }
 
+/// Named entry in TMDB detail lists (genres, companies, networks, creators)
+#[derive(Debug, Deserialize)]
+struct TmdbNamed {
+    name: String,
+}
+
+/// Crew member from appended credits
+#[derive(Debug, Deserialize)]
+struct TmdbCrewMember {
+    name: String,
+    #[serde(default)]
+    job: String,
+}
+
+/// Credits appended to a details response
+#[derive(Debug, Default, Deserialize)]
+struct TmdbCredits {
+    #[serde(default, deserialize_with = "null_as_default")]
+    crew: Vec<TmdbCrewMember>,
+}
+
+/// Response of the external id lookup (`/find/{id}`)
+#[derive(Debug, Deserialize)]
+struct TmdbFindResponse {
+    #[serde(default, deserialize_with = "null_as_default")]
+    movie_results: Vec<TmdbSearchResult>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    tv_results: Vec<TmdbSearchResult>,
+}
+
+impl TmdbFindResponse {
+    /// First match, preferring movies (IMDb episodes also appear as TV results)
+    fn into_result(self, config: &TmdbConfig) -> Option<SearchResult> {
+        if let Some(movie) = self.movie_results.first() {
+            return Some(movie.to_search_result(WorkType::Movie, config));
+        }
+        self.tv_results
+            .first()
+            .map(|tv| tv.to_search_result(WorkType::TvAnime, config))
+    }
+}
+
+/// Alternative title entry
+#[derive(Debug, Deserialize)]
+struct TmdbAlternativeTitle {
+    title: String,
+}
+
+/// Alternative titles appended to a details response
+///
+/// Movies list them under `titles`, TV shows under `results`.
+#[derive(Debug, Default, Deserialize)]
+struct TmdbAlternativeTitles {
+    #[serde(default, deserialize_with = "null_as_default")]
+    titles: Vec<TmdbAlternativeTitle>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    results: Vec<TmdbAlternativeTitle>,
+}
+
+/// External ids appended to a details response
+#[derive(Debug, Default, Deserialize)]
+struct TmdbExternalIds {
+    #[serde(default)]
+    imdb_id: Option<String>,
+    #[serde(default)]
+    wikidata_id: Option<String>,
+}
+
+/// Image entry in an images response
+#[derive(Debug, Deserialize)]
+struct TmdbImage {
+    file_path: String,
+    #[serde(default)]
+    width: u32,
+    #[serde(default)]
+    height: u32,
+    /// Language of any text on the image (none for textless art)
+    #[serde(default)]
+    iso_639_1: Option<String>,
+}
+
+/// Posters and backdrops of a movie or TV show (best voted first)
+#[derive(Debug, Deserialize)]
+struct TmdbImagesResponse {
+    #[serde(default, deserialize_with = "null_as_default")]
+    posters: Vec<TmdbImage>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    backdrops: Vec<TmdbImage>,
+}
+
+impl TmdbImagesResponse {
+    /// Posters first, then backdrops, as full image URLs
+    ///
+    /// Each kind lists images in the display language first, then English, then
+    /// language-less ones, keeping TMDB's vote order within each language.
+    fn into_images(mut self, config: &TmdbConfig) -> Vec<ProviderImage> {
+        // "ja-JP" -> "ja"
+        let language = config.language.split('-').next().unwrap_or_default();
+        let rank = |image: &TmdbImage| match image.iso_639_1.as_deref() {
+            Some(code) if code == language => 0,
+            Some("en") => 1,
+            None => 2,
+            Some(_) => 3,
+        };
+        self.posters.sort_by_key(rank);
+        self.backdrops.sort_by_key(rank);
+
+        let posters = self.posters.into_iter().map(|image| ProviderImage {
+            url: config.cover_url(&image.file_path),
+            width: image.width,
+            height: image.height,
+        });
+        let backdrops = self.backdrops.into_iter().map(|image| ProviderImage {
+            url: config.backdrop_url(&image.file_path),
+            width: image.width,
+            height: image.height,
+        });
+        posters.chain(backdrops).collect()
+    }
+}
+
+/// Movie or TV details
+/// (requested with `append_to_response=credits,alternative_titles,external_ids`)
+#[derive(Debug, Deserialize)]
+struct TmdbDetailsResponse {
+    #[serde(flatten)]
+    summary: TmdbSearchResult,
+    #[serde(default, deserialize_with = "null_as_default")]
+    genres: Vec<TmdbNamed>,
+    // Movie fields
+    #[serde(default)]
+    runtime: Option<u32>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    production_companies: Vec<TmdbNamed>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    credits: TmdbCredits,
+    #[serde(default, deserialize_with = "null_as_default")]
+    alternative_titles: TmdbAlternativeTitles,
+    #[serde(default, deserialize_with = "null_as_default")]
+    external_ids: TmdbExternalIds,
+    // TV fields
+    #[serde(default)]
+    number_of_episodes: Option<u32>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    created_by: Vec<TmdbNamed>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    networks: Vec<TmdbNamed>,
+}
+
+impl TmdbDetailsResponse {
+    /// Convert to our WorkDetails type
+    fn into_details(self, work_type: WorkType, config: &TmdbConfig) -> WorkDetails {
+        let names = |list: Vec<TmdbNamed>| list.into_iter().map(|n| n.name).collect::<Vec<_>>();
+
+        let metadata = match work_type {
+            WorkType::TvAnime => WorkMetadata {
+                creators: names(self.created_by),
+                studios: if self.networks.is_empty() {
+                    names(self.production_companies)
+                } else {
+                    names(self.networks)
+                },
+                episode_count: self.number_of_episodes,
+                ..Default::default()
+            },
+            _ => WorkMetadata {
+                creators: self
+                    .credits
+                    .crew
+                    .into_iter()
+                    .filter(|c| c.job == "Director")
+                    .map(|c| c.name)
+                    .collect(),
+                studios: names(self.production_companies),
+                runtime_minutes: self.runtime,
+                ..Default::default()
+            },
+        };
+
+        let mut details: WorkDetails = self.summary.to_search_result(work_type, config).into();
+        let mut alternate_titles: Vec<String> = Vec::new();
+        for alt in self
+            .alternative_titles
+            .titles
+            .into_iter()
+            .chain(self.alternative_titles.results)
+        {
+            if alt.title != details.title && !alternate_titles.contains(&alt.title) {
+                alternate_titles.push(alt.title);
+            }
+        }
+        alternate_titles.truncate(MAX_ALTERNATE_TITLES);
+        details.alternate_titles = alternate_titles;
+        details.language = Some(config.language.clone());
+        details.external_ids = [
+            ("imdb", self.external_ids.imdb_id),
+            ("wikidata", self.external_ids.wikidata_id),
+        ]
+        .into_iter()
+        .filter_map(|(source, id)| {
+            id.filter(|id| !id.trim().is_empty())
+                .map(|id| ProviderRef::new(source, id))
+        })
+        .collect();
+        details.metadata = WorkMetadata {
+            synopsis: self.summary.overview.filter(|o| !o.trim().is_empty()),
+            genres: names(self.genres),
+            ..metadata
+        };
+        details
+    }
+}
+
 // =============================================================================
 // Client Implementation
 // =============================================================================
 
 impl TmdbClient {
-    /// Create a new TMDB client
+    /// Create a new TMDB client configured from Settings
     pub fn new() -> Self {
         info!("TMDB client initialized");
         Self {
-            client: reqwest::Client::new(),
+            http: ProviderHttp::new("tmdb"),
+            config: None,
+        }
+    }
+
+    /// Create a client with a fixed configuration (e.g. a local stub server)
+    pub fn with_config(config: TmdbConfig) -> Self {
+        info!("TMDB client initialized for {}", config.api_base);
+        Self {
+            http: ProviderHttp::new("tmdb"),
+            config: Some(config),
         }
     }
 
-    /// Check if the client has a valid token configured (checks localStorage)
+    /// Current configuration
+    fn config(&self) -> TmdbConfig {
+        self.config
+            .clone()
+            .unwrap_or_else(TmdbConfig::from_settings)
+    }
+
+    /// Check if the client has a token configured
     pub fn is_configured(&self) -> bool {
-        settings::is_tmdb_configured()
+        self.config().token.is_some()
     }
 
-    /// Get the provider status (checks localStorage)
+    /// Get the provider status (checks the token and rate-limit cooldown)
     pub fn status(&self) -> ProviderStatus {
-        if settings::is_tmdb_configured() {
-            ProviderStatus::Available
-        } else {
+        if !self.is_configured() {
             ProviderStatus::NotConfigured
+        } else if self.http.is_cooling_down() {
+            ProviderStatus::RateLimited
+        } else {
+            ProviderStatus::Available
+        }
+    }
+
+    /// Test a token with a lightweight authenticated request
+    ///
+    /// Uses the given token rather than the stored one so it can be checked before saving.
+    pub async fn check_connection(&self, token: &str) -> ConnectionCheck {
+        let token = token.trim();
+        if token.is_empty() {
+            return ConnectionCheck::Invalid("No token entered".to_string());
         }
+
+        let url = format!("{}/authentication", self.config().api_base);
+        info!("TMDB connection check");
+
+        let authorization = format!("Bearer {}", token);
+        let result = self
+            .http
+            .get(
+                &url,
+                &[
+                    ("Authorization", &authorization),
+                    ("Accept", "application/json"),
+                ],
+            )
+            .await;
+
+        ConnectionCheck::from_result(match result {
+            Ok(response) if response.status().is_success() => Ok(()),
+            Ok(response) => Err(ProviderError::ApiError {
+                status: response.status().as_u16(),
+                message: response.text().await.unwrap_or_default(),
+            }),
+            Err(e) => Err(e),
+        })
     }
 
-    /// Search for movies
+    /// Search for movies, optionally released in a given year
     pub async fn search_movies(
         &self,
         query: &str,
+        year: Option<u16>,
         page: usize,
-    ) -> ProviderResult<Vec<SearchResult>> {
-        self.search_internal(query, WorkType::Movie, page).await
+    ) -> ProviderResult<SearchResultsPage> {
+        self.search_internal(query, year, WorkType::Movie, page)
+            .await
     }
 
-    /// Search for TV shows
-    pub async fn search_tv(&self, query: &str, page: usize) -> ProviderResult<Vec<SearchResult>> {
-        self.search_internal(query, WorkType::TvAnime, page).await
+    /// Search for TV shows, optionally first aired in a given year
+    pub async fn search_tv(
+        &self,
+        query: &str,
+        year: Option<u16>,
+        page: usize,
+    ) -> ProviderResult<SearchResultsPage> {
+        self.search_internal(query, year, WorkType::TvAnime, page)
+            .await
     }
 
     /// Search for both movies and TV shows
     pub async fn search(
         &self,
         query: &str,
+        year: Option<u16>,
         work_type: WorkType,
         page: usize,
-    ) -> ProviderResult<Vec<SearchResult>> {
+    ) -> ProviderResult<SearchResultsPage> {
         match work_type {
-            WorkType::Movie => self.search_movies(query, page).await,
-            WorkType::TvAnime => self.search_tv(query, page).await,
+            WorkType::Movie => self.search_movies(query, year, page).await,
+            WorkType::TvAnime => self.search_tv(query, year, page).await,
             WorkType::Game => Err(ProviderError::Unavailable(
                 "TMDB does not support games".to_string(),
             )),
         }
     }
 
+    /// Fetch current metadata for a movie or TV show
+    ///
+    /// TMDB ids are only unique per media type, so the work type picks the endpoint.
+    pub async fn details(&self, id: &str, work_type: WorkType) -> ProviderResult<WorkDetails> {
+        let config = self.config();
+        let endpoint = media_endpoint(&work_type)?;
+
+        info!("TMDB details: {}/{}", endpoint, id);
+
+        let details: TmdbDetailsResponse = self
+            .get_json(
+                &config,
+                &format!("{}/{}", endpoint, urlencoding::encode(id)),
+                &[
+                    ("language", &config.language),
+                    (
+                        "append_to_response",
+                        "credits,alternative_titles,external_ids",
+                    ),
+                ],
+            )
+            .await?;
+
+        Ok(details.into_details(work_type, &config))
+    }
+
+    /// Fetch the posters and backdrops of a movie or TV show
+    ///
+    /// Posters come before backdrops; within each, images in the display language come
+    /// first, then English, then language-less ones.
+    pub async fn images(
+        &self,
+        id: &str,
+        work_type: WorkType,
+    ) -> ProviderResult<Vec<ProviderImage>> {
+        let config = self.config();
+        let endpoint = media_endpoint(&work_type)?;
+
+        // "ja-JP" -> "ja"
+        let language = config.language.split('-').next().unwrap_or_default();
+        let languages = format!("{},en,null", language);
+
+        info!("TMDB images: {}/{}", endpoint, id);
+
+        let images: TmdbImagesResponse = self
+            .get_json(
+                &config,
+                &format!("{}/{}/images", endpoint, urlencoding::encode(id)),
+                &[("include_image_language", &languages)],
+            )
+            .await?;
+
+        Ok(images.into_images(&config))
+    }
+
+    /// Look up a movie or TV show by its IMDb id (e.g. "tt0245429")
+    pub async fn find_by_imdb_id(&self, imdb_id: &str) -> ProviderResult<SearchResult> {
+        let config = self.config();
+
+        info!("TMDB find: {}", imdb_id);
+
+        let found: TmdbFindResponse = self
+            .get_json(
+                &config,
+                &format!("find/{}", urlencoding::encode(imdb_id)),
+                &[
+                    ("external_source", "imdb_id"),
+                    ("language", &config.language),
+                ],
+            )
+            .await?;
+
+        found
+            .into_result(&config)
+            .ok_or_else(|| ProviderError::ApiError {
+                status: 404,
+                message: format!("TMDB has no movie or TV show for {}", imdb_id),
+            })
+    }
+
     /// Internal search implementation
     async fn search_internal(
         &self,
         query: &str,
+        year: Option<u16>,
         work_type: WorkType,
         page: usize,
-    ) -> ProviderResult<Vec<SearchResult>> {
-        let token = settings::get_tmdb_token().ok_or_else(|| {
-            ProviderError::AuthError(
-                "TMDB API token not configured. Add your token in Settings.".to_string(),
-            )
-        })?;
+    ) -> ProviderResult<SearchResultsPage> {
+        let config = self.config();
 
         // TMDB uses 1-indexed pages
-        let tmdb_page = page + 1;
+        let tmdb_page = (page + 1).to_string();
 
         let endpoint = match work_type {
             WorkType::Movie => "search/movie",
@@ -175,24 +621,70 @@ impl TmdbClient {
             WorkType::Game => unreachable!(),
         };
 
-        let url = format!(
-            "{}/{}?query={}&page={}&include_adult=false&language=en-US",
-            TMDB_API_BASE,
-            endpoint,
-            urlencoding::encode(query),
-            tmdb_page
-        );
+        let encoded_query = urlencoding::encode(query);
+        let mut params = vec![
+            ("query", encoded_query.as_ref()),
+            ("page", tmdb_page.as_str()),
+            ("include_adult", "false"),
+            ("language", config.language.as_str()),
+        ];
+        let year = year.map(|year| year.to_string());
+        if let Some(year) = &year {
+            let param = match work_type {
+                WorkType::TvAnime => "first_air_date_year",
+                _ => "year",
+            };
+            params.push((param, year));
+        }
 
         info!("TMDB search: {} (page {})", query, tmdb_page);
 
+        let search_response: TmdbSearchResponse = self.get_json(&config, endpoint, &params).await?;
+
+        info!(
+            "TMDB returned {} results (page {}/{})",
+            search_response.results.len(),
+            search_response.page,
+            search_response.total_pages
+        );
+
+        Ok(search_response.into_page(work_type, &config))
+    }
+
+    /// GET an API endpoint with the configured token and parse the JSON response
+    ///
+    /// `path` is relative to the API base. Query values are sent as given, so callers
+    /// encode user input.
+    async fn get_json<T: DeserializeOwned>(
+        &self,
+        config: &TmdbConfig,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> ProviderResult<T> {
+        let token = config.token.as_deref().ok_or_else(|| {
+            ProviderError::AuthError(
+                "TMDB API token not configured. Add your token in Settings.".to_string(),
+            )
+        })?;
+
+        let query = query
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("&");
+        let url = format!("{}/{}?{}", config.api_base, path, query);
+
+        let authorization = format!("Bearer {}", token);
         let response = self
-            .client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", &token))
-            .header("Accept", "application/json")
-            .send()
-            .await
-            .map_err(|e| ProviderError::NetworkError(e.to_string()))?;
+            .http
+            .get(
+                &url,
+                &[
+                    ("Authorization", &authorization),
+                    ("Accept", "application/json"),
+                ],
+            )
+            .await?;
 
         let status = response.status();
         if !status.is_success() {
@@ -203,25 +695,21 @@ impl TmdbClient {
             });
         }
 
-        let search_response: TmdbSearchResponse = response
+        response
             .json()
             .await
-            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
-
-        info!(
-            "TMDB returned {} results (page {}/{})",
-            search_response.results.len(),
-            search_response.page,
-            search_response.total_pages
-        );
-
-        let results = search_response
-            .results
-            .into_iter()
-            .map(|r| r.to_search_result(work_type.clone()))
-            .collect();
+            .map_err(|e| ProviderError::ParseError(e.to_string()))
+    }
+}
 
-        Ok(results)
+/// Details endpoint of a work type ("movie" or "tv")
+fn media_endpoint(work_type: &WorkType) -> ProviderResult<&'static str> {
+    match work_type {
+        WorkType::Movie => Ok("movie"),
+        WorkType::TvAnime => Ok("tv"),
+        WorkType::Game => Err(ProviderError::Unavailable(
+            "TMDB does not support games".to_string(),
+        )),
     }
 }
 
@@ -234,25 +722,206 @@ impl Default for TmdbClient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::providers::test_server::{MockResponse, MockServer};
 
     #[test]
     fn test_year_extraction() {
         let result = TmdbSearchResult {
             id: 123,
             title: Some("Test Movie".to_string()),
+            original_title: Some("Test Movie".to_string()),
             release_date: Some("2024-03-15".to_string()),
             name: None,
+            original_name: None,
             first_air_date: None,
             poster_path: Some("/abc123.jpg".to_string()),
+            backdrop_path: Some("/wide.jpg".to_string()),
             overview: None,
         };
 
-        let search_result = result.to_search_result(WorkType::Movie);
+        let search_result = result.to_search_result(WorkType::Movie, &TmdbConfig::default());
         assert_eq!(search_result.year, Some(2024));
         assert_eq!(search_result.title, "Test Movie");
+        assert_eq!(search_result.original_title, None);
         assert_eq!(
             search_result.cover_url,
             Some("https://image.tmdb.org/t/p/w500/abc123.jpg".to_string())
         );
+        assert_eq!(
+            search_result.backdrop_url,
+            Some("https://image.tmdb.org/t/p/w1280/wide.jpg".to_string())
+        );
+    }
+
+    #[test]
+    fn test_response_page_is_zero_indexed() {
+        let response = TmdbSearchResponse {
+            page: 2,
+            results: Vec::new(),
+            total_pages: 3,
+            total_results: 45,
+        };
+
+        let page = response.into_page(WorkType::Movie, &TmdbConfig::default());
+        assert_eq!(page.page, 1);
+        assert_eq!(page.total_count, 45);
+        assert!(page.has_next_page());
+    }
+
+    fn stub_config(server: &MockServer) -> TmdbConfig {
+        TmdbConfig {
+            token: Some("test-token".to_string()),
+            api_base: server.url("/3"),
+            image_base: server.url("/images"),
+            language: "ja-JP".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_against_stub_server() {
+        let server = MockServer::start(vec![MockResponse::new(
+            200,
+            r#"{"page":1,"total_pages":2,"total_results":21,"results":[
+                {"id":129,"title":"Spirited Away","original_title":"千と千尋の神隠し","release_date":"2001-07-20","poster_path":"/spirited.jpg"}
+            ]}"#,
+        )]);
+        let client = TmdbClient::with_config(stub_config(&server));
+
+        let page = client
+            .search("spirited away", Some(2001), WorkType::Movie, 0)
+            .await
+            .unwrap();
+        assert_eq!(page.results.len(), 1);
+        assert_eq!(page.results[0].title, "Spirited Away");
+        assert_eq!(page.results[0].year, Some(2001));
+        assert_eq!(
+            page.results[0].original_title.as_deref(),
+            Some("千と千尋の神隠し")
+        );
+        assert_eq!(
+            page.results[0].cover_url,
+            Some(server.url("/images/w500/spirited.jpg"))
+        );
+        assert!(page.has_next_page());
+
+        let request = &server.requests()[0];
+        assert!(request.starts_with("GET /3/search/movie?query=spirited%20away&page=1"));
+        assert!(request.contains("&year=2001 "));
+    }
+
+    #[tokio::test]
+    async fn test_connection_check_against_stub_server() {
+        let server = MockServer::start(vec![
+            MockResponse::new(200, r#"{"success":true}"#),
+            MockResponse::new(
+                401,
+                r#"{"status_code":7,"status_message":"Invalid API key: You must be granted a valid key."}"#,
+            ),
+        ]);
+        let client = TmdbClient::with_config(stub_config(&server));
+
+        assert_eq!(
+            client.check_connection("good").await,
+            ConnectionCheck::Valid
+        );
+        assert_eq!(
+            client.check_connection("bad").await,
+            ConnectionCheck::Invalid(
+                "Invalid API key: You must be granted a valid key.".to_string()
+            )
+        );
+        assert!(server.requests()[0].starts_with("GET /3/authentication"));
+    }
+
+    #[tokio::test]
+    async fn test_details_against_stub_server() {
+        let server = MockServer::start(vec![MockResponse::new(
+            200,
+            r#"{"id":1396,"name":"Breaking Bad","original_name":"Breaking Bad","first_air_date":"2008-01-20","poster_path":"/bb.jpg",
+                "overview":"A chemistry teacher turns to crime.","genres":[{"id":18,"name":"Drama"}],
+                "created_by":[{"id":66633,"name":"Vince Gilligan"}],"networks":[{"id":174,"name":"AMC"}],
+                "number_of_episodes":62,"production_companies":null,
+                "alternative_titles":{"results":[{"title":"Breaking Bad"},{"title":"ブレイキング・バッド"}]},
+                "external_ids":{"imdb_id":"tt0903747","wikidata_id":"Q1079","tvdb_id":81189,"facebook_id":null}}"#,
+        )]);
+        let client = TmdbClient::with_config(stub_config(&server));
+
+        let details = client.details("1396", WorkType::TvAnime).await.unwrap();
+        assert_eq!(details.provider_ref, ProviderRef::new("tmdb", "1396"));
+        assert_eq!(details.title, "Breaking Bad");
+        assert_eq!(details.year, Some(2008));
+        assert_eq!(details.original_title, None);
+        assert_eq!(details.alternate_titles, vec!["ブレイキング・バッド"]);
+        assert_eq!(details.language.as_deref(), Some("ja-JP"));
+        assert_eq!(details.metadata.genres, vec!["Drama"]);
+        assert_eq!(details.metadata.creators, vec!["Vince Gilligan"]);
+        assert_eq!(details.metadata.studios, vec!["AMC"]);
+        assert_eq!(details.metadata.episode_count, Some(62));
+        assert_eq!(
+            details.external_ids,
+            vec![
+                ProviderRef::new("imdb", "tt0903747"),
+                ProviderRef::new("wikidata", "Q1079")
+            ]
+        );
+        assert!(server.requests()[0].starts_with("GET /3/tv/1396?language=ja-JP"));
+    }
+
+    #[tokio::test]
+    async fn test_images_against_stub_server() {
+        let server = MockServer::start(vec![MockResponse::new(
+            200,
+            r#"{"id":129,"posters":[{"file_path":"/en.jpg","width":2000,"height":3000,"iso_639_1":"en"},{"file_path":"/jp.jpg","width":2000,"height":3000,"iso_639_1":"ja"}],
+                "backdrops":[{"file_path":"/wide.jpg","width":3840,"height":2160,"iso_639_1":null}],"logos":[]}"#,
+        )]);
+        let client = TmdbClient::with_config(stub_config(&server));
+
+        let images = client.images("129", WorkType::Movie).await.unwrap();
+        assert_eq!(
+            images,
+            vec![
+                ProviderImage {
+                    url: server.url("/images/w500/jp.jpg"),
+                    width: 2000,
+                    height: 3000,
+                },
+                ProviderImage {
+                    url: server.url("/images/w500/en.jpg"),
+                    width: 2000,
+                    height: 3000,
+                },
+                ProviderImage {
+                    url: server.url("/images/w1280/wide.jpg"),
+                    width: 3840,
+                    height: 2160,
+                },
+            ]
+        );
+        assert!(images[2].is_landscape());
+        assert!(server.requests()[0]
+            .starts_with("GET /3/movie/129/images?include_image_language=ja,en,null"));
+    }
+
+    #[tokio::test]
+    async fn test_find_by_imdb_id_against_stub_server() {
+        let server = MockServer::start(vec![
+            MockResponse::new(
+                200,
+                r#"{"movie_results":[],"tv_results":[{"id":1396,"name":"Breaking Bad","first_air_date":"2008-01-20"}]}"#,
+            ),
+            MockResponse::new(200, r#"{"movie_results":[],"tv_results":null}"#),
+        ]);
+        let client = TmdbClient::with_config(stub_config(&server));
+
+        let result = client.find_by_imdb_id("tt0903747").await.unwrap();
+        assert_eq!(result.provider_ref, ProviderRef::new("tmdb", "1396"));
+        assert_eq!(result.work_type, WorkType::TvAnime);
+        assert!(server.requests()[0].starts_with("GET /3/find/tt0903747?external_source=imdb_id"));
+
+        let missing = client.find_by_imdb_id("tt0000000").await;
+        assert!(matches!(
+            missing,
+            Err(ProviderError::ApiError { status: 404, .. })
+        ));
     }
 }
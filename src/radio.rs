@@ -0,0 +1,484 @@
+//! Theme music radio
+//!
+//! Plays the themes of the whole library one after another, like a background radio:
+//! - The queue holds each work with theme music once, shuffled, optionally narrowed to
+//!   a feeling, a work type or a release decade
+//! - When a theme's segment ends the next one starts; the queue loops
+//! - While the radio is on it owns the audio controller, so hero navigation (or the
+//!   hero being paused) doesn't switch or stop the music
+//! - Media keys and OS media controls go through the Media Session API, reached via
+//!   `js_sys::Reflect` since web-sys only exposes it behind an unstable cfg
+
+use dioxus::prelude::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use uuid::Uuid;
+use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::app_state::{AppState, MnemonWithWork};
+use crate::audio::{AudioController, PlaybackStatus, ThemeTrack};
+use crate::constants::HERO_TRANSITION_MS;
+use crate::models::{release_decade, Work, WorkType};
+
+/// Which works the radio plays
+#[derive(Clone, PartialEq, Debug, Default)]
+pub enum RadioFilter {
+    #[default]
+    All,
+    Feeling(String),
+    Type(WorkType),
+    /// Release decade, as its first year (e.g. 1990)
+    Decade(u16),
+}
+
+impl RadioFilter {
+    /// Key used as the value of the filter picker
+    pub fn key(&self) -> String {
+        match self {
+            RadioFilter::All => "all".to_string(),
+            RadioFilter::Feeling(feeling) => format!("feeling:{}", feeling),
+            RadioFilter::Type(work_type) => format!("type:{}", work_type.label()),
+            RadioFilter::Decade(decade) => format!("decade:{}", decade),
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        if key == "all" {
+            return Some(RadioFilter::All);
+        }
+        let (kind, value) = key.split_once(':')?;
+        match kind {
+            "feeling" => Some(RadioFilter::Feeling(value.to_string())),
+            "type" => WorkType::ALL
+                .into_iter()
+                .find(|t| t.label() == value)
+                .map(RadioFilter::Type),
+            "decade" => value.parse().ok().map(RadioFilter::Decade),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            RadioFilter::All => "Everything".to_string(),
+            RadioFilter::Feeling(feeling) => feeling.clone(),
+            RadioFilter::Type(work_type) => work_type.label().to_string(),
+            RadioFilter::Decade(decade) => format!("{}s", decade),
+        }
+    }
+
+    fn matches(&self, mnemon_with_work: &MnemonWithWork) -> bool {
+        match self {
+            RadioFilter::All => true,
+            RadioFilter::Feeling(feeling) => mnemon_with_work.mnemon.feelings.contains(feeling),
+            RadioFilter::Type(work_type) => mnemon_with_work.work.work_type == *work_type,
+            RadioFilter::Decade(decade) => mnemon_with_work
+                .work
+                .release_year
+                .is_some_and(|year| release_decade(year) == *decade),
+        }
+    }
+}
+
+/// Decades of the works with theme music, oldest first
+pub fn radio_decades(works: &[Work], has_theme: impl Fn(&Work) -> bool) -> Vec<u16> {
+    let mut decades: Vec<u16> = works
+        .iter()
+        .filter(|w| has_theme(w))
+        .filter_map(|w| w.release_year.map(release_decade))
+        .collect();
+    decades.sort_unstable();
+    decades.dedup();
+    decades
+}
+
+/// Shuffled queue of the works to play (each work once, even with several mnemons)
+///
+/// A work matches a feeling when any of its mnemons has it.
+pub fn radio_queue(
+    mnemons_with_works: &[MnemonWithWork],
+    filter: &RadioFilter,
+    has_theme: impl Fn(&Work) -> bool,
+    rng: &mut impl Rng,
+) -> Vec<Uuid> {
+    let mut queue: Vec<Uuid> = Vec::new();
+    for mnemon_with_work in mnemons_with_works {
+        let work = &mnemon_with_work.work;
+        if !queue.contains(&work.id) && has_theme(work) && filter.matches(mnemon_with_work) {
+            queue.push(work.id);
+        }
+    }
+    queue.shuffle(rng);
+    queue
+}
+
+/// Media Session actions handled by the radio
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum MediaAction {
+    Play,
+    Pause,
+    Next,
+    Previous,
+}
+
+impl MediaAction {
+    const ALL: [MediaAction; 4] = [
+        MediaAction::Play,
+        MediaAction::Pause,
+        MediaAction::Next,
+        MediaAction::Previous,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            MediaAction::Play => "play",
+            MediaAction::Pause => "pause",
+            MediaAction::Next => "nexttrack",
+            MediaAction::Previous => "previoustrack",
+        }
+    }
+}
+
+/// `navigator.mediaSession`, where supported
+fn media_session() -> Option<JsValue> {
+    let navigator = web_sys::window()?.navigator();
+    js_sys::Reflect::get(&navigator, &"mediaSession".into())
+        .ok()
+        .filter(|session| !session.is_undefined() && !session.is_null())
+}
+
+/// Call a method of the media session with the given arguments
+fn call_media_session(method: &str, args: &[&JsValue]) -> Option<()> {
+    let session = media_session()?;
+    let function: js_sys::Function = js_sys::Reflect::get(&session, &method.into())
+        .ok()?
+        .dyn_into()
+        .ok()?;
+    let args: js_sys::Array = args.iter().copied().collect();
+    function.apply(&session, &args).ok()?;
+    Some(())
+}
+
+/// Route media keys and OS controls to the radio for the lifetime of the app
+fn register_media_actions(handle: Callback<MediaAction>) {
+    for action in MediaAction::ALL {
+        let handler = Closure::<dyn Fn()>::new(move || handle.call(action));
+        // Ignored where the browser doesn't support an action
+        let _ = call_media_session(
+            "setActionHandler",
+            &[&action.name().into(), handler.as_ref()],
+        );
+        handler.forget();
+    }
+}
+
+/// Show what's playing in the OS media controls (`None` clears them)
+fn set_media_metadata(now_playing: Option<(&str, &str, Option<&str>)>) {
+    let Some(session) = media_session() else {
+        return;
+    };
+    let metadata = now_playing.and_then(|(title, artist, artwork)| {
+        let window = web_sys::window()?;
+        let constructor: js_sys::Function = js_sys::Reflect::get(&window, &"MediaMetadata".into())
+            .ok()?
+            .dyn_into()
+            .ok()?;
+        let init = js_sys::Object::new();
+        js_sys::Reflect::set(&init, &"title".into(), &title.into()).ok()?;
+        js_sys::Reflect::set(&init, &"artist".into(), &artist.into()).ok()?;
+        js_sys::Reflect::set(&init, &"album".into(), &"Mnemon radio".into()).ok()?;
+        if let Some(src) = artwork {
+            let image = js_sys::Object::new();
+            js_sys::Reflect::set(&image, &"src".into(), &src.into()).ok()?;
+            js_sys::Reflect::set(&init, &"artwork".into(), &js_sys::Array::of1(&image)).ok()?;
+        }
+        js_sys::Reflect::construct(&constructor, &js_sys::Array::of1(&init)).ok()
+    });
+    let _ = js_sys::Reflect::set(
+        &session,
+        &"metadata".into(),
+        &metadata.unwrap_or(JsValue::NULL),
+    );
+}
+
+fn set_media_playback_state(state: &str) {
+    if let Some(session) = media_session() {
+        let _ = js_sys::Reflect::set(&session, &"playbackState".into(), &state.into());
+    }
+}
+
+/// Shared radio state, provided as context
+#[derive(Clone, Copy)]
+pub struct Radio {
+    app_state: Signal<AppState>,
+    audio: AudioController,
+    /// Whether the radio is on
+    pub active: Signal<bool>,
+    pub filter: Signal<RadioFilter>,
+    /// Works to play, in order
+    pub queue: Signal<Vec<Uuid>>,
+    /// Position of the playing work in the queue
+    pub position: Signal<usize>,
+}
+
+impl Radio {
+    fn new(app_state: Signal<AppState>, audio: AudioController) -> Self {
+        Self {
+            app_state,
+            audio,
+            active: Signal::new(false),
+            filter: Signal::new(RadioFilter::All),
+            queue: Signal::new(Vec::new()),
+            position: Signal::new(0),
+        }
+    }
+
+    /// Work whose theme the radio is on
+    pub fn current_work(&self) -> Option<Work> {
+        if !*self.active.read() {
+            return None;
+        }
+        let work_id = *self.queue.read().get(*self.position.read())?;
+        let state = self.app_state.read();
+        let work = state.works.read().iter().find(|w| w.id == work_id).cloned();
+        work
+    }
+
+    /// Turn the radio on with a freshly shuffled queue
+    ///
+    /// Returns false (and leaves the radio off) when nothing matches the filter.
+    pub fn start(&mut self, filter: RadioFilter) -> bool {
+        let queue = {
+            let state = self.app_state.peek();
+            radio_queue(
+                &state.get_mnemons_with_works(),
+                &filter,
+                |w| ThemeTrack::for_work(w, &state).is_some(),
+                &mut rand::thread_rng(),
+            )
+        };
+        self.filter.set(filter);
+        if queue.is_empty() {
+            self.stop();
+            return false;
+        }
+        self.queue.set(queue);
+        self.position.set(0);
+        self.active.set(true);
+        self.play_current();
+        true
+    }
+
+    /// Turn the radio off and stop the music
+    pub fn stop(&mut self) {
+        if *self.active.peek() {
+            self.active.set(false);
+            self.audio.stop();
+        }
+        set_media_metadata(None);
+    }
+
+    pub fn next(&mut self) {
+        self.step(1);
+    }
+
+    pub fn previous(&mut self) {
+        self.step(-1);
+    }
+
+    /// Pause or resume the playing theme
+    pub fn toggle(&mut self) {
+        let track = self.audio.track.peek().clone();
+        if let Some(track) = track {
+            self.audio.toggle(track);
+        }
+    }
+
+    /// Resume the loaded theme
+    pub fn play(&mut self) {
+        let track = self.audio.track.peek().clone();
+        if let Some(track) = track {
+            self.audio.play(track);
+        }
+    }
+
+    pub fn pause(&mut self) {
+        self.audio.pause();
+    }
+
+    fn step(&mut self, offset: isize) {
+        let len = self.queue.peek().len();
+        if !*self.active.peek() || len == 0 {
+            return;
+        }
+        let position = (*self.position.peek() as isize + offset).rem_euclid(len as isize);
+        self.position.set(position as usize);
+        self.play_current();
+    }
+
+    /// Play the theme at the current queue position, fading from the previous one
+    fn play_current(&mut self) {
+        let Some(work_id) = self.queue.peek().get(*self.position.peek()).copied() else {
+            return;
+        };
+        let track = {
+            let state = self.app_state.peek();
+            let works = state.works.peek();
+            works
+                .iter()
+                .find(|w| w.id == work_id)
+                .and_then(|w| ThemeTrack::for_work(w, &state))
+        };
+        let unchanged = track.is_some() && *self.audio.track.peek() == track;
+        match track {
+            // The same theme again (e.g. a queue of one looping): start it over, as
+            // a crossfade into the playing track does nothing
+            Some(track) if unchanged => {
+                self.audio.seek(0.0);
+                self.audio.play(track);
+            }
+            Some(track) => self.audio.crossfade_to(track, HERO_TRANSITION_MS),
+            // Removed from the library or its music was taken off: drop it
+            None => {
+                let remaining = {
+                    let mut queue = self.queue.write();
+                    queue.retain(|id| *id != work_id);
+                    queue.len()
+                };
+                if remaining == 0 {
+                    self.stop();
+                    return;
+                }
+                if *self.position.peek() >= remaining {
+                    self.position.set(0);
+                }
+                self.play_current();
+            }
+        }
+    }
+}
+
+/// Provide the radio to the app (after the app state and audio controller)
+pub fn use_radio_provider() -> Radio {
+    let app_state = use_context::<Signal<AppState>>();
+    let audio = use_context::<AudioController>();
+    let mut radio = use_context_provider(|| Radio::new(app_state, audio));
+
+    // Media keys and OS controls
+    let handle = use_callback(move |action: MediaAction| {
+        if !*radio.active.peek() {
+            return;
+        }
+        match action {
+            MediaAction::Play => radio.play(),
+            MediaAction::Pause => radio.pause(),
+            MediaAction::Next => radio.next(),
+            MediaAction::Previous => radio.previous(),
+        }
+    });
+    use_hook(move || register_media_actions(handle));
+
+    // Move on when a theme's segment has played to the end
+    let mut handled_finishes = use_signal(|| *audio.finished.peek());
+    use_effect(move || {
+        let finished = *audio.finished.read();
+        if finished == *handled_finishes.peek() {
+            return;
+        }
+        handled_finishes.set(finished);
+        if *radio.active.peek() {
+            radio.next();
+        }
+    });
+
+    // Keep the OS media controls in step with the radio
+    use_effect(move || {
+        let Some(work) = radio.current_work() else {
+            return;
+        };
+        let artist = match work.release_year {
+            Some(year) => format!("{} · {}", work.work_type.label(), year),
+            None => work.work_type.label().to_string(),
+        };
+        let artwork = work
+            .covers()
+            .next()
+            .and_then(|uri| app_state.peek().media_src(uri));
+        set_media_metadata(Some((&work.title_en, &artist, artwork.as_deref())));
+    });
+    use_effect(move || {
+        if !*radio.active.read() {
+            return;
+        }
+        let state = match *audio.status.read() {
+            PlaybackStatus::Playing | PlaybackStatus::Loading => "playing",
+            PlaybackStatus::Stopped => "none",
+            _ => "paused",
+        };
+        set_media_playback_state(state);
+    });
+
+    radio
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Mnemon;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn mnemon_with_work(title: &str, year: u16, feelings: &[&str]) -> MnemonWithWork {
+        let work = Work::from_manual(WorkType::Game, title.to_string(), Some(year));
+        let mnemon = Mnemon::new(
+            work.id,
+            None,
+            feelings.iter().map(|f| f.to_string()).collect(),
+            Vec::new(),
+        );
+        MnemonWithWork { mnemon, work }
+    }
+
+    #[test]
+    fn test_radio_queue_filters_and_dedups_works() {
+        let persona = mnemon_with_work("Persona 4", 2008, &["Nostalgic"]);
+        let mut replay = mnemon_with_work("Persona 4", 2008, &["Cozy"]);
+        replay.work = persona.work.clone();
+        replay.mnemon.work_id = persona.work.id;
+        let silent = mnemon_with_work("Tetris", 1984, &["Nostalgic"]);
+        let chrono = mnemon_with_work("Chrono Trigger", 1995, &["Nostalgic"]);
+        let all = vec![persona.clone(), replay, silent.clone(), chrono.clone()];
+        let has_theme = |w: &Work| w.id != silent.work.id;
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let mut queue = radio_queue(&all, &RadioFilter::All, has_theme, &mut rng);
+        queue.sort();
+        let mut expected = vec![persona.work.id, chrono.work.id];
+        expected.sort();
+        assert_eq!(queue, expected);
+
+        let cozy = RadioFilter::Feeling("Cozy".to_string());
+        assert_eq!(
+            radio_queue(&all, &cozy, has_theme, &mut rng),
+            vec![persona.work.id]
+        );
+        assert_eq!(
+            radio_queue(&all, &RadioFilter::Decade(1990), has_theme, &mut rng),
+            vec![chrono.work.id]
+        );
+        assert!(radio_queue(&all, &RadioFilter::Decade(1980), has_theme, &mut rng).is_empty());
+    }
+
+    #[test]
+    fn test_radio_filter_keys_round_trip() {
+        for filter in [
+            RadioFilter::All,
+            RadioFilter::Feeling("Cozy".to_string()),
+            RadioFilter::Type(WorkType::TvAnime),
+            RadioFilter::Decade(1990),
+        ] {
+            assert_eq!(RadioFilter::from_key(&filter.key()), Some(filter));
+        }
+    }
+}
@@ -3,9 +3,28 @@
 //! This module provides functions for storing and retrieving API tokens
 //! from localStorage, allowing users to configure their own API keys
 //! for TMDB and RAWG providers.
+//!
+//! The outcome of the last "Test connection" for each credential is stored
+//! alongside it, so the add flow can warn about a bad token before searching.
+//!
+//! Provider base URLs can be overridden too (e.g. to route through a CORS proxy
+//! or a local stub server); an unset endpoint falls back to the public API.
+//!
+//! The display language is passed to providers so titles and overviews come
+//! back localized where available.
+//!
+//! Theme music preferences (global mute, crossfade curve) apply as soon as they are
+//! changed, like the auto-cycle toggle. So does the slideshow filter, which is
+//! remembered across sessions.
 
 #![allow(dead_code)]
 
+use crate::constants::{DEFAULT_DISPLAY_LANGUAGE, DISPLAY_LANGUAGES};
+use crate::data::slideshow::SlideshowFilter;
+use crate::providers::rawg::DEFAULT_RAWG_API_BASE;
+use crate::providers::tmdb::{DEFAULT_TMDB_API_BASE, DEFAULT_TMDB_IMAGE_BASE};
+use crate::providers::ConnectionCheck;
+use serde::{Deserialize, Serialize};
 use tracing::info;
 use web_sys::window;
 
@@ -15,6 +34,33 @@ const TMDB_TOKEN_KEY: &str = "mnemon_tmdb_access_token";
 /// LocalStorage key for RAWG API key
 const RAWG_API_KEY_KEY: &str = "mnemon_rawg_api_key";
 
+/// LocalStorage key for the last TMDB token verification
+const TMDB_VERIFICATION_KEY: &str = "mnemon_tmdb_verification";
+
+/// LocalStorage key for the last RAWG API key verification
+const RAWG_VERIFICATION_KEY: &str = "mnemon_rawg_verification";
+
+/// LocalStorage key for a custom TMDB API base URL
+const TMDB_API_BASE_KEY: &str = "mnemon_tmdb_api_base";
+
+/// LocalStorage key for a custom TMDB image base URL
+const TMDB_IMAGE_BASE_KEY: &str = "mnemon_tmdb_image_base";
+
+/// LocalStorage key for a custom RAWG API base URL
+const RAWG_API_BASE_KEY: &str = "mnemon_rawg_api_base";
+
+/// LocalStorage key for the display language sent to providers
+const DISPLAY_LANGUAGE_KEY: &str = "mnemon_display_language";
+
+/// LocalStorage key for the global theme music mute
+const THEME_MUTED_KEY: &str = "mnemon_theme_muted";
+
+/// LocalStorage key for the theme music crossfade curve
+const FADE_CURVE_KEY: &str = "mnemon_theme_fade_curve";
+
+/// LocalStorage key for the slideshow filter (JSON)
+const SLIDESHOW_FILTER_KEY: &str = "mnemon_slideshow_filter";
+
 /// Get the localStorage object
 fn get_local_storage() -> Option<web_sys::Storage> {
     window()?.local_storage().ok()?
@@ -100,17 +146,274 @@ pub fn get_rawg_api_key() -> Option<String> {
     load_rawg_api_key()
 }
 
+/// Validate and normalize a provider base URL
+///
+/// Returns `Ok(None)` for an empty value (use the default endpoint) and strips
+/// trailing slashes so paths can be appended with `format!("{}/...", base)`.
+pub fn validate_base_url(value: &str) -> Result<Option<String>, String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+
+    let url = reqwest::Url::parse(trimmed)
+        .map_err(|e| format!("{} is not a valid URL ({})", trimmed, e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("{} must start with http:// or https://", trimmed));
+    }
+    if url.host_str().is_none() {
+        return Err(format!("{} has no host", trimmed));
+    }
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err(format!("{} must not contain a query or fragment", trimmed));
+    }
+
+    Ok(Some(trimmed.trim_end_matches('/').to_string()))
+}
+
+/// Save a custom endpoint to localStorage (an empty value restores the default)
+fn save_endpoint(key: &str, value: &str) -> bool {
+    let Some(storage) = get_local_storage() else {
+        return false;
+    };
+    match validate_base_url(value) {
+        Ok(Some(url)) => storage.set_item(key, &url).is_ok(),
+        Ok(None) => storage.remove_item(key).is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// Load a custom endpoint from localStorage
+fn load_endpoint(key: &str) -> Option<String> {
+    let value = get_local_storage()?.get_item(key).ok()??;
+    validate_base_url(&value).ok().flatten()
+}
+
+/// TMDB API base URL (custom endpoint or the public API)
+pub fn tmdb_api_base() -> String {
+    load_endpoint(TMDB_API_BASE_KEY).unwrap_or_else(|| DEFAULT_TMDB_API_BASE.to_string())
+}
+
+/// TMDB image base URL, without the size segment (custom endpoint or the public CDN)
+pub fn tmdb_image_base() -> String {
+    load_endpoint(TMDB_IMAGE_BASE_KEY).unwrap_or_else(|| DEFAULT_TMDB_IMAGE_BASE.to_string())
+}
+
+/// RAWG API base URL (custom endpoint or the public API)
+pub fn rawg_api_base() -> String {
+    load_endpoint(RAWG_API_BASE_KEY).unwrap_or_else(|| DEFAULT_RAWG_API_BASE.to_string())
+}
+
+/// Check if a language tag is one of the supported display languages
+pub fn is_supported_language(tag: &str) -> bool {
+    DISPLAY_LANGUAGES.iter().any(|(code, _)| *code == tag)
+}
+
+/// Save the display language (the default language removes the key)
+pub fn save_display_language(tag: &str) -> bool {
+    let Some(storage) = get_local_storage() else {
+        return false;
+    };
+    if tag == DEFAULT_DISPLAY_LANGUAGE {
+        storage.remove_item(DISPLAY_LANGUAGE_KEY).is_ok()
+    } else if is_supported_language(tag) {
+        storage.set_item(DISPLAY_LANGUAGE_KEY, tag).is_ok()
+    } else {
+        false
+    }
+}
+
+/// Display language passed to providers (saved preference or the default)
+pub fn display_language() -> String {
+    get_local_storage()
+        .and_then(|storage| storage.get_item(DISPLAY_LANGUAGE_KEY).ok().flatten())
+        .filter(|tag| is_supported_language(tag))
+        .unwrap_or_else(|| DEFAULT_DISPLAY_LANGUAGE.to_string())
+}
+
+/// Save whether theme music is muted (unmuted removes the key)
+pub fn save_theme_muted(muted: bool) -> bool {
+    let Some(storage) = get_local_storage() else {
+        return false;
+    };
+    if muted {
+        storage.set_item(THEME_MUTED_KEY, "true").is_ok()
+    } else {
+        storage.remove_item(THEME_MUTED_KEY).is_ok()
+    }
+}
+
+/// Whether theme music is muted
+pub fn theme_muted() -> bool {
+    get_local_storage()
+        .and_then(|storage| storage.get_item(THEME_MUTED_KEY).ok().flatten())
+        .is_some_and(|value| value == "true")
+}
+
+/// Save the crossfade curve's key (`None`, for the default curve, removes it)
+pub fn save_fade_curve(key: Option<&str>) -> bool {
+    let Some(storage) = get_local_storage() else {
+        return false;
+    };
+    match key {
+        Some(key) => storage.set_item(FADE_CURVE_KEY, key).is_ok(),
+        None => storage.remove_item(FADE_CURVE_KEY).is_ok(),
+    }
+}
+
+/// Key of the saved crossfade curve, if one was picked
+pub fn fade_curve_key() -> Option<String> {
+    get_local_storage().and_then(|storage| storage.get_item(FADE_CURVE_KEY).ok().flatten())
+}
+
+/// Save the slideshow filter (an empty filter removes the key)
+pub fn save_slideshow_filter(filter: &SlideshowFilter) -> bool {
+    let Some(storage) = get_local_storage() else {
+        return false;
+    };
+    if filter.is_empty() {
+        return storage.remove_item(SLIDESHOW_FILTER_KEY).is_ok();
+    }
+    match serde_json::to_string(filter) {
+        Ok(json) => storage.set_item(SLIDESHOW_FILTER_KEY, &json).is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// Slideshow filter from the last session (empty if none was saved)
+pub fn slideshow_filter() -> SlideshowFilter {
+    get_local_storage()
+        .and_then(|storage| storage.get_item(SLIDESHOW_FILTER_KEY).ok().flatten())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Result of the last connection test for a credential
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TokenVerification {
+    /// When the credential was tested
+    pub checked_at: chrono::DateTime<chrono::Utc>,
+    /// What the provider answered
+    pub outcome: ConnectionCheck,
+    /// Fingerprint of the tested credential (the credential itself is not duplicated)
+    token_fingerprint: u64,
+}
+
+impl TokenVerification {
+    /// Record a verification made now for the given credential
+    pub fn new(token: &str, outcome: ConnectionCheck) -> Self {
+        Self {
+            checked_at: chrono::Utc::now(),
+            outcome,
+            token_fingerprint: token_fingerprint(token),
+        }
+    }
+
+    /// Check if this verification was made for the given credential
+    pub fn applies_to(&self, token: &str) -> bool {
+        self.token_fingerprint == token_fingerprint(token)
+    }
+}
+
+/// Fingerprint a credential so a verification can be matched to it
+///
+/// FNV-1a, so fingerprints saved by one build still match in the next (std's
+/// `DefaultHasher` may change between Rust releases).
+fn token_fingerprint(token: &str) -> u64 {
+    token
+        .trim()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+/// Save a verification record to localStorage
+fn save_verification(key: &str, verification: &TokenVerification) -> bool {
+    let Some(storage) = get_local_storage() else {
+        return false;
+    };
+    match serde_json::to_string(verification) {
+        Ok(json) => storage.set_item(key, &json).is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// Load a verification record, if it was made for the given credential
+fn load_verification(key: &str, token: Option<String>) -> Option<TokenVerification> {
+    let token = token?;
+    let json = get_local_storage()?.get_item(key).ok()??;
+    let verification: TokenVerification = serde_json::from_str(&json).ok()?;
+    verification.applies_to(&token).then_some(verification)
+}
+
+/// Record the outcome of testing a TMDB token
+pub fn save_tmdb_verification(token: &str, outcome: ConnectionCheck) -> TokenVerification {
+    let verification = TokenVerification::new(token, outcome);
+    if save_verification(TMDB_VERIFICATION_KEY, &verification) {
+        info!("Saved TMDB token verification to localStorage");
+    }
+    verification
+}
+
+/// Record the outcome of testing a RAWG API key
+pub fn save_rawg_verification(api_key: &str, outcome: ConnectionCheck) -> TokenVerification {
+    let verification = TokenVerification::new(api_key, outcome);
+    if save_verification(RAWG_VERIFICATION_KEY, &verification) {
+        info!("Saved RAWG API key verification to localStorage");
+    }
+    verification
+}
+
+/// Last verification of the stored TMDB token (ignored once the token changes)
+pub fn load_tmdb_verification() -> Option<TokenVerification> {
+    load_verification(TMDB_VERIFICATION_KEY, load_tmdb_token())
+}
+
+/// Last verification of the stored RAWG API key (ignored once the key changes)
+pub fn load_rawg_verification() -> Option<TokenVerification> {
+    load_verification(RAWG_VERIFICATION_KEY, load_rawg_api_key())
+}
+
+/// Last verification of a TMDB token that may not be saved yet
+pub fn load_tmdb_verification_for(token: &str) -> Option<TokenVerification> {
+    load_verification(TMDB_VERIFICATION_KEY, Some(token.to_string()))
+}
+
+/// Last verification of a RAWG API key that may not be saved yet
+pub fn load_rawg_verification_for(api_key: &str) -> Option<TokenVerification> {
+    load_verification(RAWG_VERIFICATION_KEY, Some(api_key.to_string()))
+}
+
 /// Clear all stored settings
+///
+/// Mute, crossfade and the slideshow filter are also held in memory; callers reset
+/// those too (`AudioController::reload_preferences`, `AppState::set_slideshow_filter`).
 pub fn clear_all_settings() -> bool {
-    if let Some(storage) = get_local_storage() {
-        let tmdb_cleared = storage.remove_item(TMDB_TOKEN_KEY).is_ok();
-        let rawg_cleared = storage.remove_item(RAWG_API_KEY_KEY).is_ok();
-        if tmdb_cleared && rawg_cleared {
-            info!("Cleared all settings from localStorage");
-            return true;
-        }
+    let Some(storage) = get_local_storage() else {
+        return false;
+    };
+    // Remove every key even if one fails, so as little as possible is left behind
+    let failed = [
+        TMDB_TOKEN_KEY,
+        TMDB_VERIFICATION_KEY,
+        TMDB_API_BASE_KEY,
+        TMDB_IMAGE_BASE_KEY,
+        RAWG_API_KEY_KEY,
+        RAWG_VERIFICATION_KEY,
+        RAWG_API_BASE_KEY,
+        DISPLAY_LANGUAGE_KEY,
+        THEME_MUTED_KEY,
+        FADE_CURVE_KEY,
+        SLIDESHOW_FILTER_KEY,
+    ]
+    .into_iter()
+    .filter(|key| storage.remove_item(key).is_err())
+    .count();
+    if failed == 0 {
+        info!("Cleared all settings from localStorage");
     }
-    false
+    failed == 0
 }
 
 /// Settings state for UI display
@@ -118,6 +421,14 @@ pub fn clear_all_settings() -> bool {
 pub struct ApiTokenSettings {
     pub tmdb_token: String,
     pub rawg_api_key: String,
+    /// Custom TMDB API base URL (empty for the default)
+    pub tmdb_api_base: String,
+    /// Custom TMDB image base URL (empty for the default)
+    pub tmdb_image_base: String,
+    /// Custom RAWG API base URL (empty for the default)
+    pub rawg_api_base: String,
+    /// Display language tag (empty for the default)
+    pub display_language: String,
 }
 
 impl ApiTokenSettings {
@@ -126,14 +437,47 @@ impl ApiTokenSettings {
         Self {
             tmdb_token: load_tmdb_token().unwrap_or_default(),
             rawg_api_key: load_rawg_api_key().unwrap_or_default(),
+            tmdb_api_base: load_endpoint(TMDB_API_BASE_KEY).unwrap_or_default(),
+            tmdb_image_base: load_endpoint(TMDB_IMAGE_BASE_KEY).unwrap_or_default(),
+            rawg_api_base: load_endpoint(RAWG_API_BASE_KEY).unwrap_or_default(),
+            display_language: display_language(),
         }
     }
 
+    /// Validate the custom endpoints and language, returning the normalized settings
+    pub fn validated(&self) -> Result<Self, String> {
+        let display_language = match self.display_language.trim() {
+            "" => DEFAULT_DISPLAY_LANGUAGE.to_string(),
+            tag if is_supported_language(tag) => tag.to_string(),
+            tag => return Err(format!("Display language: {} is not supported", tag)),
+        };
+        let normalize = |label: &str, value: &str| {
+            validate_base_url(value)
+                .map(Option::unwrap_or_default)
+                .map_err(|e| format!("{}: {}", label, e))
+        };
+
+        Ok(Self {
+            tmdb_token: self.tmdb_token.clone(),
+            rawg_api_key: self.rawg_api_key.clone(),
+            tmdb_api_base: normalize("TMDB API URL", &self.tmdb_api_base)?,
+            tmdb_image_base: normalize("TMDB image URL", &self.tmdb_image_base)?,
+            rawg_api_base: normalize("RAWG API URL", &self.rawg_api_base)?,
+            display_language,
+        })
+    }
+
     /// Save current settings to localStorage
+    ///
+    /// Endpoints should be checked with `validated()` first; invalid ones are not saved.
     pub fn save(&self) -> bool {
         let tmdb_saved = save_tmdb_token(&self.tmdb_token);
         let rawg_saved = save_rawg_api_key(&self.rawg_api_key);
-        tmdb_saved && rawg_saved
+        let endpoints_saved = save_endpoint(TMDB_API_BASE_KEY, &self.tmdb_api_base)
+            && save_endpoint(TMDB_IMAGE_BASE_KEY, &self.tmdb_image_base)
+            && save_endpoint(RAWG_API_BASE_KEY, &self.rawg_api_base);
+        let language_saved = save_display_language(&self.display_language);
+        tmdb_saved && rawg_saved && endpoints_saved && language_saved
     }
 
     /// Check if TMDB is configured (has a value)
@@ -146,3 +490,57 @@ impl ApiTokenSettings {
         !self.rawg_api_key.trim().is_empty()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_base_url() {
+        assert_eq!(validate_base_url("  "), Ok(None));
+        assert_eq!(
+            validate_base_url(" http://127.0.0.1:8080/tmdb/3/ "),
+            Ok(Some("http://127.0.0.1:8080/tmdb/3".to_string()))
+        );
+        assert!(validate_base_url("api.themoviedb.org/3").is_err());
+        assert!(validate_base_url("ftp://mirror.example.com").is_err());
+        assert!(validate_base_url("https://proxy.example.com/?url=").is_err());
+    }
+
+    #[test]
+    fn test_token_fingerprint_is_stable() {
+        // Published FNV-1a test vectors: saved fingerprints must keep matching
+        assert_eq!(token_fingerprint(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(token_fingerprint(" a "), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_validated_reports_the_bad_field() {
+        let settings = ApiTokenSettings {
+            rawg_api_base: "not a url".to_string(),
+            tmdb_api_base: "https://proxy.example.com/3/".to_string(),
+            ..Default::default()
+        };
+
+        let err = settings.validated().unwrap_err();
+        assert!(err.starts_with("RAWG API URL"));
+
+        let fixed = ApiTokenSettings {
+            rawg_api_base: String::new(),
+            ..settings
+        }
+        .validated()
+        .unwrap();
+        assert_eq!(fixed.tmdb_api_base, "https://proxy.example.com/3");
+        assert_eq!(fixed.display_language, DEFAULT_DISPLAY_LANGUAGE);
+
+        let unsupported = ApiTokenSettings {
+            display_language: "xx-XX".to_string(),
+            ..fixed
+        };
+        assert!(unsupported
+            .validated()
+            .unwrap_err()
+            .starts_with("Display language"));
+    }
+}
@@ -0,0 +1,80 @@
+//! Schema migrations for stored records
+//!
+//! Works carry the schema version they were written with. Older records are
+//! upgraded when loaded and written back, so each migration runs once per record.
+
+use crate::models::{Work, WORK_SCHEMA_VERSION};
+
+/// Upgrade a work to the current schema, returning whether it changed
+pub fn migrate_work(work: &mut Work) -> bool {
+    if work.schema_version >= WORK_SCHEMA_VERSION {
+        return false;
+    }
+
+    // v1: works gained descriptive metadata (defaulted on deserialize). Clear the
+    // refresh stamp so the background refresh fetches it for provider-backed works.
+    if work.schema_version < 1 {
+        work.metadata_refreshed_at = None;
+    }
+
+    // v2: `merged_provider_refs` became `external_ids`. The old name still loads
+    // through a serde alias; writing the record back stores it under the new one.
+    // The other fields added since v1 default on deserialize and need nothing here.
+
+    work.schema_version = WORK_SCHEMA_VERSION;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ProviderRef, WorkType};
+
+    #[test]
+    fn test_migrates_legacy_work_once() {
+        let mut work = Work::from_provider(
+            WorkType::Movie,
+            "Akira".to_string(),
+            Some(1988),
+            None,
+            None,
+            ProviderRef::new("tmdb", "149"),
+        );
+        work.schema_version = 0;
+        work.metadata_refreshed_at = Some(chrono::Utc::now());
+
+        assert!(migrate_work(&mut work));
+        assert_eq!(work.schema_version, WORK_SCHEMA_VERSION);
+        assert_eq!(work.metadata_refreshed_at, None);
+        assert!(work.metadata.is_empty());
+
+        assert!(!migrate_work(&mut work));
+
+        // A v1 record is rewritten but keeps its refresh stamp
+        let refreshed_at = Some(chrono::Utc::now());
+        work.schema_version = 1;
+        work.metadata_refreshed_at = refreshed_at;
+        assert!(migrate_work(&mut work));
+        assert_eq!(work.metadata_refreshed_at, refreshed_at);
+    }
+
+    #[test]
+    fn test_legacy_record_deserializes_with_defaults() {
+        let json = r#"{
+            "id": "6f1c1f2e-8d2a-4f5e-9b1a-3c4d5e6f7a8b",
+            "work_type": "Game",
+            "title_en": "Tetris",
+            "release_year": 1984,
+            "cover_image_local_uri": null,
+            "theme_music_local_uri": null,
+            "provider_ref": null,
+            "origin": "Manual",
+            "created_at": "2024-01-01T00:00:00Z"
+        }"#;
+
+        let work: Work = serde_json::from_str(json).unwrap();
+        assert_eq!(work.schema_version, 0);
+        assert!(work.metadata.is_empty());
+        assert!(work.overridden_fields.is_empty());
+    }
+}
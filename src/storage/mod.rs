@@ -3,8 +3,16 @@
 //! This module provides web persistence using IndexedDB via the rexie crate.
 //! Data is stored in object stores and survives page reloads.
 //! Supports storing both structured data (JSON) and binary blobs (images, audio).
+//! Works written with an older schema are migrated as they are loaded (see `migration`).
+//! Hero impressions (when each mnemon was last shown) are kept in their own store so
+//! recording one doesn't rewrite the mnemon.
+//! Uploaded assets are saved as soon as they are picked; the ones no work refers to
+//! (a cancelled form, a replaced cover) are deleted by `AppState`.
 
-use crate::models::{Mnemon, Work};
+pub mod migration;
+
+use crate::models::{Impression, Mnemon, SearchResult, Work, WORK_SCHEMA_VERSION};
+use base64::Engine;
 use rexie::{ObjectStore, Rexie, TransactionMode};
 use tracing::info;
 
@@ -12,12 +20,14 @@ use tracing::info;
 const DB_NAME: &str = "mnemon_db";
 
 /// Database version - increment when schema changes
-const DB_VERSION: u32 = 1;
+const DB_VERSION: u32 = 3;
 
 /// Object store names
 const WORKS_STORE: &str = "works";
 const MNEMONS_STORE: &str = "mnemons";
 const ASSETS_STORE: &str = "assets";
+const SEARCH_CACHE_STORE: &str = "search_cache";
+const IMPRESSIONS_STORE: &str = "impressions";
 
 /// Storage error type
 #[derive(Debug)]
@@ -68,6 +78,8 @@ async fn open_database() -> StorageResult<Rexie> {
         .add_object_store(ObjectStore::new(WORKS_STORE).key_path("id"))
         .add_object_store(ObjectStore::new(MNEMONS_STORE).key_path("id"))
         .add_object_store(ObjectStore::new(ASSETS_STORE).key_path("id"))
+        .add_object_store(ObjectStore::new(SEARCH_CACHE_STORE).key_path("id"))
+        .add_object_store(ObjectStore::new(IMPRESSIONS_STORE).key_path("id"))
         .build()
         .await?;
 
@@ -146,13 +158,33 @@ pub async fn load_works() -> StorageResult<Vec<Work>> {
         .map_err(|e| StorageError::Store(e.to_string()))?;
 
     let mut works = Vec::new();
+    let mut migrated = Vec::new();
     for js_value in js_values {
-        let work: Work = serde_wasm_bindgen::from_value(js_value)
+        let mut work: Work = serde_wasm_bindgen::from_value(js_value)
             .map_err(|e| StorageError::Deserialize(e.to_string()))?;
+        if migration::migrate_work(&mut work) {
+            migrated.push(work.clone());
+        }
         works.push(work);
     }
 
     info!("Loaded {} works from IndexedDB", works.len());
+
+    // Write migrated records back so they are only upgraded once. A failed write
+    // keeps the migrated copy in memory; the record is migrated again next load.
+    for work in &migrated {
+        if let Err(e) = save_work(work).await {
+            tracing::error!("Failed to write back migrated work {}: {}", work.id, e);
+        }
+    }
+    if !migrated.is_empty() {
+        info!(
+            "Migrated {} works to schema v{}",
+            migrated.len(),
+            WORK_SCHEMA_VERSION
+        );
+    }
+
     Ok(works)
 }
 
@@ -189,6 +221,7 @@ pub async fn load_mnemons() -> StorageResult<Vec<Mnemon>> {
 pub struct PersistedData {
     pub works: Vec<Work>,
     pub mnemons: Vec<Mnemon>,
+    pub impressions: Vec<Impression>,
 }
 
 /// Delete a mnemon from IndexedDB by ID
@@ -218,6 +251,33 @@ pub async fn delete_mnemon(mnemon_id: &uuid::Uuid) -> StorageResult<()> {
     Ok(())
 }
 
+/// Delete a work from IndexedDB by ID
+pub async fn delete_work(work_id: &uuid::Uuid) -> StorageResult<()> {
+    let db = open_database().await?;
+
+    let transaction = db
+        .transaction(&[WORKS_STORE], TransactionMode::ReadWrite)
+        .map_err(|e| StorageError::Transaction(e.to_string()))?;
+
+    let store = transaction
+        .store(WORKS_STORE)
+        .map_err(|e| StorageError::Store(e.to_string()))?;
+
+    let js_key = serde_wasm_bindgen::to_value(&work_id.to_string())?;
+    store
+        .delete(js_key)
+        .await
+        .map_err(|e| StorageError::Store(e.to_string()))?;
+
+    transaction
+        .done()
+        .await
+        .map_err(|e| StorageError::Transaction(e.to_string()))?;
+
+    info!("Deleted work {} from IndexedDB", work_id);
+    Ok(())
+}
+
 /// Load all persisted data (async version)
 pub async fn load_all_async() -> PersistedData {
     let works = load_works().await.unwrap_or_else(|e| {
@@ -230,9 +290,101 @@ pub async fn load_all_async() -> PersistedData {
         Vec::new()
     });
 
-    PersistedData { works, mnemons }
+    let impressions = load_impressions().await.unwrap_or_else(|e| {
+        info!("Failed to load impressions: {}", e);
+        Vec::new()
+    });
+
+    PersistedData {
+        works,
+        mnemons,
+        impressions,
+    }
+}
+
+/// Save a mnemon's hero impression
+pub async fn save_impression(impression: &Impression) -> StorageResult<()> {
+    let db = open_database().await?;
+
+    let transaction = db
+        .transaction(&[IMPRESSIONS_STORE], TransactionMode::ReadWrite)
+        .map_err(|e| StorageError::Transaction(e.to_string()))?;
+
+    let store = transaction
+        .store(IMPRESSIONS_STORE)
+        .map_err(|e| StorageError::Store(e.to_string()))?;
+
+    let js_value = serde_wasm_bindgen::to_value(impression)?;
+    store
+        .put(&js_value, None)
+        .await
+        .map_err(|e| StorageError::Store(e.to_string()))?;
+
+    transaction
+        .done()
+        .await
+        .map_err(|e| StorageError::Transaction(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Load all hero impressions
+pub async fn load_impressions() -> StorageResult<Vec<Impression>> {
+    let db = open_database().await?;
+
+    let transaction = db
+        .transaction(&[IMPRESSIONS_STORE], TransactionMode::ReadOnly)
+        .map_err(|e| StorageError::Transaction(e.to_string()))?;
+
+    let store = transaction
+        .store(IMPRESSIONS_STORE)
+        .map_err(|e| StorageError::Store(e.to_string()))?;
+
+    let js_values = store
+        .get_all(None, None)
+        .await
+        .map_err(|e| StorageError::Store(e.to_string()))?;
+
+    let mut impressions = Vec::new();
+    for js_value in js_values {
+        let impression: Impression = serde_wasm_bindgen::from_value(js_value)
+            .map_err(|e| StorageError::Deserialize(e.to_string()))?;
+        impressions.push(impression);
+    }
+
+    Ok(impressions)
 }
 
+/// Delete a mnemon's hero impression
+pub async fn delete_impression(mnemon_id: &uuid::Uuid) -> StorageResult<()> {
+    let db = open_database().await?;
+
+    let transaction = db
+        .transaction(&[IMPRESSIONS_STORE], TransactionMode::ReadWrite)
+        .map_err(|e| StorageError::Transaction(e.to_string()))?;
+
+    let store = transaction
+        .store(IMPRESSIONS_STORE)
+        .map_err(|e| StorageError::Store(e.to_string()))?;
+
+    let js_key = serde_wasm_bindgen::to_value(&mnemon_id.to_string())?;
+    store
+        .delete(js_key)
+        .await
+        .map_err(|e| StorageError::Store(e.to_string()))?;
+
+    transaction
+        .done()
+        .await
+        .map_err(|e| StorageError::Transaction(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Prefix of URIs that point at a stored asset instead of the network
+/// (e.g. "asset:cover-1b4e28ba")
+const ASSET_URI_PREFIX: &str = "asset:";
+
 /// Asset metadata for storing binary data
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct StoredAsset {
@@ -245,8 +397,54 @@ pub struct StoredAsset {
     pub data_base64: String,
 }
 
+impl StoredAsset {
+    /// URI stored on works to refer to this asset
+    pub fn uri(&self) -> String {
+        Self::uri_of(&self.id)
+    }
+
+    /// URI of the asset with the given id
+    pub fn uri_of(id: &str) -> String {
+        format!("{}{}", ASSET_URI_PREFIX, id)
+    }
+
+    /// `data:` URL for displaying or playing the asset
+    pub fn data_url(&self) -> String {
+        format!("data:{};base64,{}", self.mime_type, self.data_base64)
+    }
+
+    /// `blob:` URL for displaying or playing the asset, holding the decoded bytes in
+    /// the browser rather than a base64 string in app memory (revoke with
+    /// `revoke_asset_url`); falls back to a `data:` URL
+    pub fn object_url(&self) -> String {
+        let object_url = base64::engine::general_purpose::STANDARD
+            .decode(&self.data_base64)
+            .ok()
+            .and_then(|bytes| {
+                let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes.as_slice()));
+                let options = web_sys::BlobPropertyBag::new();
+                options.set_type(&self.mime_type);
+                let blob =
+                    web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options).ok()?;
+                web_sys::Url::create_object_url_with_blob(&blob).ok()
+            });
+        object_url.unwrap_or_else(|| self.data_url())
+    }
+}
+
+/// Release the memory behind an asset URL made by `StoredAsset::object_url`
+pub fn revoke_asset_url(url: &str) {
+    if url.starts_with("blob:") {
+        let _ = web_sys::Url::revoke_object_url(url);
+    }
+}
+
+/// Asset id of an asset URI, or `None` for network URLs
+pub fn asset_id(uri: &str) -> Option<&str> {
+    uri.strip_prefix(ASSET_URI_PREFIX)
+}
+
 /// Save an asset (image/audio) to IndexedDB
-#[allow(dead_code)]
 pub async fn save_asset(asset: &StoredAsset) -> StorageResult<()> {
     let db = open_database().await?;
 
@@ -274,7 +472,6 @@ pub async fn save_asset(asset: &StoredAsset) -> StorageResult<()> {
 }
 
 /// Load an asset by ID from IndexedDB
-#[allow(dead_code)]
 pub async fn load_asset(id: &str) -> StorageResult<Option<StoredAsset>> {
     let db = open_database().await?;
 
@@ -306,6 +503,165 @@ pub async fn load_asset(id: &str) -> StorageResult<Option<StoredAsset>> {
     }
 }
 
+/// Ids of every stored asset
+pub async fn load_asset_ids() -> StorageResult<Vec<String>> {
+    let db = open_database().await?;
+
+    let transaction = db
+        .transaction(&[ASSETS_STORE], TransactionMode::ReadOnly)
+        .map_err(|e| StorageError::Transaction(e.to_string()))?;
+
+    let store = transaction
+        .store(ASSETS_STORE)
+        .map_err(|e| StorageError::Store(e.to_string()))?;
+
+    let keys = store
+        .get_all_keys(None, None)
+        .await
+        .map_err(|e| StorageError::Store(e.to_string()))?;
+
+    Ok(keys.into_iter().filter_map(|key| key.as_string()).collect())
+}
+
+/// Delete an asset by ID from IndexedDB
+pub async fn delete_asset(id: &str) -> StorageResult<()> {
+    let db = open_database().await?;
+
+    let transaction = db
+        .transaction(&[ASSETS_STORE], TransactionMode::ReadWrite)
+        .map_err(|e| StorageError::Transaction(e.to_string()))?;
+
+    let store = transaction
+        .store(ASSETS_STORE)
+        .map_err(|e| StorageError::Store(e.to_string()))?;
+
+    let js_key = serde_wasm_bindgen::to_value(id)?;
+    store
+        .delete(js_key)
+        .await
+        .map_err(|e| StorageError::Store(e.to_string()))?;
+
+    transaction
+        .done()
+        .await
+        .map_err(|e| StorageError::Transaction(e.to_string()))?;
+
+    info!("Deleted asset '{}' from IndexedDB", id);
+    Ok(())
+}
+
+/// A cached page of provider search results
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StoredSearchPage {
+    /// Cache key (provider, work type, normalized query and page)
+    pub id: String,
+    /// The results of the page
+    pub results: Vec<SearchResult>,
+    /// Total result count reported by the provider
+    pub total_count: usize,
+    /// Zero-indexed page number
+    pub page: usize,
+    /// Whether the provider reported further pages
+    pub has_next_page: bool,
+    /// When the page was fetched from the provider
+    pub cached_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Save a cached search page to IndexedDB
+pub async fn save_search_page(entry: &StoredSearchPage) -> StorageResult<()> {
+    let db = open_database().await?;
+
+    let transaction = db
+        .transaction(&[SEARCH_CACHE_STORE], TransactionMode::ReadWrite)
+        .map_err(|e| StorageError::Transaction(e.to_string()))?;
+
+    let store = transaction
+        .store(SEARCH_CACHE_STORE)
+        .map_err(|e| StorageError::Store(e.to_string()))?;
+
+    let js_value = serde_wasm_bindgen::to_value(entry)?;
+    store
+        .put(&js_value, None)
+        .await
+        .map_err(|e| StorageError::Store(e.to_string()))?;
+
+    transaction
+        .done()
+        .await
+        .map_err(|e| StorageError::Transaction(e.to_string()))?;
+
+    info!("Cached search page '{}' in IndexedDB", entry.id);
+    Ok(())
+}
+
+/// Load a cached search page by key from IndexedDB
+pub async fn load_search_page(id: &str) -> StorageResult<Option<StoredSearchPage>> {
+    let db = open_database().await?;
+
+    let transaction = db
+        .transaction(&[SEARCH_CACHE_STORE], TransactionMode::ReadOnly)
+        .map_err(|e| StorageError::Transaction(e.to_string()))?;
+
+    let store = transaction
+        .store(SEARCH_CACHE_STORE)
+        .map_err(|e| StorageError::Store(e.to_string()))?;
+
+    let js_key = serde_wasm_bindgen::to_value(id)?;
+    let result = store
+        .get(js_key)
+        .await
+        .map_err(|e| StorageError::Store(e.to_string()))?;
+
+    match result {
+        Some(js_value) => {
+            let entry: StoredSearchPage = serde_wasm_bindgen::from_value(js_value)
+                .map_err(|e| StorageError::Deserialize(e.to_string()))?;
+            Ok(Some(entry))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Delete cached search pages fetched before the given time
+pub async fn prune_search_pages(before: chrono::DateTime<chrono::Utc>) -> StorageResult<usize> {
+    let db = open_database().await?;
+
+    let transaction = db
+        .transaction(&[SEARCH_CACHE_STORE], TransactionMode::ReadWrite)
+        .map_err(|e| StorageError::Transaction(e.to_string()))?;
+
+    let store = transaction
+        .store(SEARCH_CACHE_STORE)
+        .map_err(|e| StorageError::Store(e.to_string()))?;
+
+    let js_values = store
+        .get_all(None, None)
+        .await
+        .map_err(|e| StorageError::Store(e.to_string()))?;
+
+    let mut pruned = 0;
+    for js_value in js_values {
+        let entry: StoredSearchPage = serde_wasm_bindgen::from_value(js_value)
+            .map_err(|e| StorageError::Deserialize(e.to_string()))?;
+        if entry.cached_at < before {
+            let js_key = serde_wasm_bindgen::to_value(&entry.id)?;
+            store
+                .delete(js_key)
+                .await
+                .map_err(|e| StorageError::Store(e.to_string()))?;
+            pruned += 1;
+        }
+    }
+
+    transaction
+        .done()
+        .await
+        .map_err(|e| StorageError::Transaction(e.to_string()))?;
+
+    info!("Pruned {} cached search pages from IndexedDB", pruned);
+    Ok(pruned)
+}
+
 /// Clear all stored data (useful for testing/reset)
 #[allow(dead_code)]
 pub async fn clear_all() -> StorageResult<()> {
@@ -313,7 +669,7 @@ pub async fn clear_all() -> StorageResult<()> {
 
     let transaction = db
         .transaction(
-            &[WORKS_STORE, MNEMONS_STORE, ASSETS_STORE],
+            &[WORKS_STORE, MNEMONS_STORE, ASSETS_STORE, SEARCH_CACHE_STORE],
             TransactionMode::ReadWrite,
         )
         .map_err(|e| StorageError::Transaction(e.to_string()))?;
@@ -342,6 +698,14 @@ pub async fn clear_all() -> StorageResult<()> {
         .await
         .map_err(|e| StorageError::Store(e.to_string()))?;
 
+    let search_cache_store = transaction
+        .store(SEARCH_CACHE_STORE)
+        .map_err(|e| StorageError::Store(e.to_string()))?;
+    search_cache_store
+        .clear()
+        .await
+        .map_err(|e| StorageError::Store(e.to_string()))?;
+
     transaction
         .done()
         .await
@@ -0,0 +1,325 @@
+//! Local files picked by the user
+//!
+//! Uploaded covers are cropped to the hero's aspect ratio (centered) and downscaled in
+//! the browser before they are stored as an asset, so a phone photo doesn't end up
+//! as a 10MB record in IndexedDB.
+//!
+//! Theme music files are stored as they are; trimming only records the segment to
+//! play (see `ThemeTrim`), so the segment can be changed later without re-uploading.
+
+use base64::Engine;
+use dioxus::html::FileData;
+use uuid::Uuid;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+use crate::constants::{
+    CUSTOM_COVER_ASPECT_RATIO, CUSTOM_COVER_JPEG_QUALITY, CUSTOM_COVER_MAX_FILE_BYTES,
+    CUSTOM_COVER_MAX_WIDTH, THEME_MUSIC_MAX_FILE_BYTES, THEME_MUSIC_MIN_SEGMENT_SECS,
+    THEME_MUSIC_TYPES,
+};
+use crate::models::ThemeTrim;
+use crate::storage::StoredAsset;
+
+/// Upload error type
+#[derive(Debug)]
+pub enum UploadError {
+    /// The file isn't of an accepted type
+    UnsupportedType(String),
+    /// The file is larger than accepted
+    TooLarge,
+    /// The file couldn't be read or decoded
+    Decode(String),
+    /// The trim points don't describe a playable segment
+    InvalidTrim(String),
+}
+
+impl std::fmt::Display for UploadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UploadError::UnsupportedType(mime) => write!(f, "Unsupported file type: {}", mime),
+            UploadError::TooLarge => write!(f, "File is too large"),
+            UploadError::Decode(msg) => write!(f, "Could not read file: {}", msg),
+            UploadError::InvalidTrim(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl From<JsValue> for UploadError {
+    fn from(e: JsValue) -> Self {
+        UploadError::Decode(format!("{:?}", e))
+    }
+}
+
+/// Result type for uploads
+pub type UploadResult<T> = Result<T, UploadError>;
+
+/// Part of an image to keep, in source pixels
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CropRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Largest centered crop of an image with the given aspect ratio (width / height)
+pub fn centered_crop(width: u32, height: u32, aspect_ratio: f64) -> CropRect {
+    let source_ratio = width as f64 / height as f64;
+    if source_ratio > aspect_ratio {
+        // Too wide: trim the sides
+        let crop_width = ((height as f64 * aspect_ratio).round() as u32).clamp(1, width);
+        CropRect {
+            x: (width - crop_width) / 2,
+            y: 0,
+            width: crop_width,
+            height,
+        }
+    } else {
+        // Too tall: trim top and bottom
+        let crop_height = ((width as f64 / aspect_ratio).round() as u32).clamp(1, height);
+        CropRect {
+            x: 0,
+            y: (height - crop_height) / 2,
+            width,
+            height: crop_height,
+        }
+    }
+}
+
+/// Size of an image scaled down to at most `max_width`, keeping its proportions
+/// (images are never scaled up)
+pub fn downscaled_size(width: u32, height: u32, max_width: u32) -> (u32, u32) {
+    if width <= max_width {
+        return (width, height);
+    }
+    let scaled_height = (height as f64 * max_width as f64 / width as f64).round() as u32;
+    (max_width, scaled_height.max(1))
+}
+
+/// Check a picked file's type and size before reading it
+fn check_file(
+    file: &FileData,
+    accepts: impl Fn(&str) -> bool,
+    max_bytes: u64,
+) -> UploadResult<String> {
+    let mime_type = file.content_type().unwrap_or_default();
+    if !accepts(&mime_type) {
+        return Err(UploadError::UnsupportedType(if mime_type.is_empty() {
+            file.name()
+        } else {
+            mime_type
+        }));
+    }
+    if file.size() > max_bytes {
+        return Err(UploadError::TooLarge);
+    }
+    Ok(mime_type)
+}
+
+/// Temporary `blob:` URL for a file's bytes (revoke with `Url::revoke_object_url`)
+fn object_url(bytes: &[u8], mime_type: &str) -> UploadResult<String> {
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes));
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options)?;
+    Ok(web_sys::Url::create_object_url_with_blob(&blob)?)
+}
+
+/// Crop and downscale an image, returning it as a JPEG `data:` URL
+async fn render_cover(source_url: &str) -> UploadResult<String> {
+    let image = web_sys::HtmlImageElement::new()?;
+    image.set_src(source_url);
+    JsFuture::from(image.decode()).await?;
+
+    let crop = centered_crop(
+        image.natural_width(),
+        image.natural_height(),
+        CUSTOM_COVER_ASPECT_RATIO,
+    );
+    let (width, height) = downscaled_size(crop.width, crop.height, CUSTOM_COVER_MAX_WIDTH);
+
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or_else(|| UploadError::Decode("No document".to_string()))?;
+    let canvas: web_sys::HtmlCanvasElement = document
+        .create_element("canvas")?
+        .dyn_into()
+        .map_err(|_| UploadError::Decode("No canvas".to_string()))?;
+    canvas.set_width(width);
+    canvas.set_height(height);
+    let context: web_sys::CanvasRenderingContext2d = canvas
+        .get_context("2d")?
+        .and_then(|context| context.dyn_into().ok())
+        .ok_or_else(|| UploadError::Decode("No canvas context".to_string()))?;
+    context.draw_image_with_html_image_element_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(
+        &image,
+        crop.x as f64,
+        crop.y as f64,
+        crop.width as f64,
+        crop.height as f64,
+        0.0,
+        0.0,
+        width as f64,
+        height as f64,
+    )?;
+
+    Ok(canvas.to_data_url_with_type_and_encoder_options(
+        "image/jpeg",
+        &JsValue::from_f64(CUSTOM_COVER_JPEG_QUALITY),
+    )?)
+}
+
+/// Turn a picked image file into a cover asset, ready to be saved
+pub async fn prepare_cover(file: &FileData) -> UploadResult<StoredAsset> {
+    let mime_type = check_file(
+        file,
+        |mime| mime.starts_with("image/"),
+        CUSTOM_COVER_MAX_FILE_BYTES,
+    )?;
+    let bytes = file
+        .read_bytes()
+        .await
+        .map_err(|e| UploadError::Decode(e.to_string()))?;
+
+    let source_url = object_url(&bytes, &mime_type)?;
+    let rendered = render_cover(&source_url).await;
+    let _ = web_sys::Url::revoke_object_url(&source_url);
+
+    let data_url = rendered?;
+    let (header, data_base64) = data_url
+        .split_once(',')
+        .ok_or_else(|| UploadError::Decode("Unexpected image data".to_string()))?;
+    let mime_type = header
+        .trim_start_matches("data:")
+        .trim_end_matches(";base64")
+        .to_string();
+
+    Ok(StoredAsset {
+        id: format!("cover-{}", Uuid::new_v4()),
+        mime_type,
+        data_base64: data_base64.to_string(),
+    })
+}
+
+/// Turn a picked audio file into a theme music asset, ready to be saved
+pub async fn prepare_theme_music(file: &FileData) -> UploadResult<StoredAsset> {
+    let mime_type = check_file(
+        file,
+        |mime| THEME_MUSIC_TYPES.contains(&mime),
+        THEME_MUSIC_MAX_FILE_BYTES,
+    )?;
+    let bytes = file
+        .read_bytes()
+        .await
+        .map_err(|e| UploadError::Decode(e.to_string()))?;
+
+    Ok(StoredAsset {
+        id: format!("theme-{}", Uuid::new_v4()),
+        mime_type,
+        data_base64: base64::engine::general_purpose::STANDARD.encode(&bytes),
+    })
+}
+
+/// Check trim points against the track length (when known)
+///
+/// Returns `None` when the points cover the whole track.
+pub fn validate_trim(
+    start_secs: f64,
+    end_secs: Option<f64>,
+    duration_secs: Option<f64>,
+) -> UploadResult<Option<ThemeTrim>> {
+    if start_secs < 0.0 {
+        return Err(UploadError::InvalidTrim(
+            "Start can't be before the beginning of the track".to_string(),
+        ));
+    }
+    let end_of_segment = end_secs.or(duration_secs);
+    if let (Some(end), Some(duration)) = (end_of_segment, duration_secs) {
+        if end > duration {
+            return Err(UploadError::InvalidTrim(
+                "End is after the end of the track".to_string(),
+            ));
+        }
+    }
+    if let Some(end) = end_of_segment {
+        if end - start_secs < THEME_MUSIC_MIN_SEGMENT_SECS {
+            return Err(UploadError::InvalidTrim(
+                "End must be at least a second after start".to_string(),
+            ));
+        }
+    }
+
+    // An end at the end of the track is the same as no end
+    let end_secs = end_secs.filter(|end| duration_secs.is_none_or(|d| *end < d));
+    if start_secs == 0.0 && end_secs.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(ThemeTrim {
+        start_secs,
+        end_secs,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_centered_crop_to_hero_aspect() {
+        // Portrait phone photo: keep the middle band
+        assert_eq!(
+            centered_crop(3000, 4000, 16.0 / 9.0),
+            CropRect {
+                x: 0,
+                y: 1156,
+                width: 3000,
+                height: 1688,
+            }
+        );
+        // Ultrawide screenshot: trim the sides
+        assert_eq!(
+            centered_crop(3440, 1440, 16.0 / 9.0),
+            CropRect {
+                x: 440,
+                y: 0,
+                width: 2560,
+                height: 1440,
+            }
+        );
+    }
+
+    #[test]
+    fn test_downscaled_size_never_upscales() {
+        assert_eq!(downscaled_size(3000, 1688, 1920), (1920, 1080));
+        assert_eq!(downscaled_size(800, 450, 1920), (800, 450));
+    }
+
+    #[test]
+    fn test_validate_trim() {
+        let trim = validate_trim(12.0, Some(42.5), Some(180.0)).unwrap();
+        assert_eq!(
+            trim,
+            Some(ThemeTrim {
+                start_secs: 12.0,
+                end_secs: Some(42.5),
+            })
+        );
+        assert_eq!(validate_trim(0.0, None, Some(180.0)).unwrap(), None);
+        assert_eq!(validate_trim(0.0, Some(180.0), Some(180.0)).unwrap(), None);
+
+        // Without a known length only the start is kept
+        assert_eq!(
+            validate_trim(5.0, None, None).unwrap(),
+            Some(ThemeTrim {
+                start_secs: 5.0,
+                end_secs: None,
+            })
+        );
+
+        assert!(validate_trim(-1.0, None, Some(180.0)).is_err());
+        assert!(validate_trim(179.5, None, Some(180.0)).is_err());
+        assert!(validate_trim(30.0, Some(30.5), Some(180.0)).is_err());
+        assert!(validate_trim(30.0, Some(200.0), Some(180.0)).is_err());
+    }
+}
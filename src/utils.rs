@@ -30,3 +30,50 @@ pub fn is_mobile_device() -> bool {
 
     width < 768.0 && max_touch_points > 0
 }
+
+/// Check if the browser reports a network connection
+pub fn is_online() -> bool {
+    window().map(|w| w.navigator().on_line()).unwrap_or(false)
+}
+
+/// Today's date in the user's time zone
+pub fn today() -> chrono::NaiveDate {
+    chrono::Local::now().date_naive()
+}
+
+/// Format a position in a track as "m:ss"
+pub fn format_timestamp(secs: f64) -> String {
+    let total = secs.max(0.0).floor() as u64;
+    format!("{}:{:02}", total / 60, total % 60)
+}
+
+/// Parse a position typed as seconds ("83", "83.5") or minutes and seconds ("1:23")
+pub fn parse_timestamp(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let secs = match text.split_once(':') {
+        Some((minutes, seconds)) => {
+            let minutes: u32 = minutes.trim().parse().ok()?;
+            let seconds: f64 = seconds.trim().parse().ok()?;
+            if !(0.0..60.0).contains(&seconds) {
+                return None;
+            }
+            minutes as f64 * 60.0 + seconds
+        }
+        None => text.parse().ok()?,
+    };
+    (secs.is_finite() && secs >= 0.0).then_some(secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamps_round_trip() {
+        assert_eq!(format_timestamp(83.7), "1:23");
+        assert_eq!(parse_timestamp("1:23"), Some(83.0));
+        assert_eq!(parse_timestamp(" 42.5 "), Some(42.5));
+        assert_eq!(parse_timestamp("1:75"), None);
+        assert_eq!(parse_timestamp("-3"), None);
+    }
+}